@@ -0,0 +1,113 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    time::Instant,
+};
+
+use stellar_diagnostics::DiagnosticsEmitter;
+
+use crate::{
+    lockfile::{lock_scope, sync_lockfile},
+    log::{log_error, log_info},
+    package::{analyze_package, compiler_config, PackageArgs},
+    workspace::{build_directory, Workspace},
+};
+
+pub fn check_command(args: &PackageArgs) {
+    run("Checking", args);
+}
+
+/// There is no code generation yet, so building a package only analyzes it
+/// and prepares the build directory of the selected profile, e.g. `build/release`.
+pub fn build_command(args: &PackageArgs) {
+    run("Compiling", args);
+
+    let build_directory = match build_directory(Path::new(".")) {
+        Err(err) => {
+            log_error(err);
+            process::exit(1);
+        }
+        Ok(build_directory) => build_directory.join(args.profile().name()),
+    };
+
+    if let Err(err) = fs::create_dir_all(&build_directory) {
+        log_error(format!(
+            "cannot create the build directory {}: {err}",
+            build_directory.display()
+        ));
+        process::exit(1);
+    }
+}
+
+fn run(action: &str, args: &PackageArgs) {
+    let now = Instant::now();
+
+    let packages = match packages_to_process(Path::new(".")) {
+        Err(err) => {
+            log_error(err);
+            process::exit(1);
+        }
+        Ok(packages) => packages,
+    };
+
+    if let Err(err) = sync_lockfile(Path::new("."), args.locked) {
+        log_error(err);
+        process::exit(1);
+    }
+
+    let config = match lock_scope(Path::new("."))
+        .and_then(|(lock_root, _)| compiler_config(&lock_root, args.profile()))
+    {
+        Err(err) => {
+            log_error(err);
+            process::exit(1);
+        }
+        Ok(config) => config,
+    };
+
+    let mut diagnostics_emitter = DiagnosticsEmitter::new();
+
+    for root in &packages {
+        let analyzed = match analyze_package(root, args, false) {
+            Err(err) => {
+                log_error(err);
+                process::exit(1);
+            }
+            Ok(analyzed) => analyzed,
+        };
+
+        let name = analyzed.package.name(analyzed.state.db());
+
+        log_info(action, format!("{name} ({})", root.display()));
+
+        diagnostics_emitter.emit_global_diagnostics(analyzed.state.diagnostics());
+
+        if analyzed.state.diagnostics().is_fatal() {
+            log_error(format!(
+                "could not process `{name}` due to the previous errors"
+            ));
+            process::exit(1);
+        }
+    }
+
+    log_info(
+        "Finished",
+        format!(
+            "`{}` profile [{}] {} package(s) in {}s",
+            config.profile(),
+            config.description(),
+            packages.len(),
+            now.elapsed().as_secs_f64()
+        ),
+    );
+}
+
+/// Returns roots of packages to process: every member in dependency order in
+/// a workspace root, or the current package otherwise.
+fn packages_to_process(root: &Path) -> Result<Vec<PathBuf>, String> {
+    match Workspace::load(root)? {
+        Some(workspace) => workspace.members_in_dependency_order(),
+        None => Ok(vec![root.to_owned()]),
+    }
+}
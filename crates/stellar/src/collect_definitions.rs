@@ -26,7 +26,7 @@ pub fn command() {
 
             now = Instant::now();
 
-            let hir = LowerToHir::run_all(&mut state, ast);
+            let hir = LowerToHir::run_all(ast);
 
             log_info("Lowered", format!("in {}s", now.elapsed().as_secs_f64()));
 
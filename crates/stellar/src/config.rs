@@ -0,0 +1,92 @@
+//! Reads the configuration of the package manager from `.stellar/config.toml`
+//! in the package or workspace root:
+//!
+//! ```toml
+//! [registry]
+//! path = "../registry"
+//!
+//! [vendor]
+//! path = "vendor"
+//!
+//! [licenses]
+//! deny = ["GPL-3.0-only", "AGPL-3.0-only"]
+//! ```
+//!
+//! Relative paths are resolved against the root.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use stellar_filesystem::path_resolver::PackagePathResolver;
+
+/// The configuration of the package manager.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The `[registry]` section.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<RegistryConfig>,
+
+    /// The `[vendor]` section, written by `stellar vendor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<VendorConfig>,
+
+    /// The `[licenses]` section, used by `stellar licenses`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub licenses: Option<LicensesConfig>,
+}
+
+/// The `[registry]` section of the configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// The directory of the package registry.
+    pub path: PathBuf,
+}
+
+/// The `[vendor]` section of the configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct VendorConfig {
+    /// The directory, that contains vendored copies of registry and git packages.
+    pub path: PathBuf,
+}
+
+/// The `[licenses]` section of the configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LicensesConfig {
+    /// SPDX identifiers of licenses, that dependencies must not require.
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl Config {
+    /// Reads the configuration of the package or workspace located in a given
+    /// directory. Returns the default configuration, if there is no configuration file.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let path = PackagePathResolver::new(root).config();
+
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        let source = fs::read_to_string(&path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        let mut config: Self = toml::from_str(&source)
+            .map_err(|err| format!("cannot parse {}: {}", path.display(), err.message()))?;
+
+        if let Some(registry) = &mut config.registry {
+            registry.path = root.join(&registry.path);
+        }
+
+        if let Some(vendor) = &mut config.vendor {
+            vendor.path = root.join(&vendor.path);
+        }
+
+        Ok(config)
+    }
+}
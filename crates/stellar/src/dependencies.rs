@@ -0,0 +1,163 @@
+use std::{fs, path::Path, process};
+
+use stellar_diagnostics::diagnostic::Severity;
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_interner::PathId;
+use stellar_manifest::{parse_manifest, DependencyTable, ManifestEditor, TomlDependency};
+
+use crate::log::{log_error, log_info};
+
+/// Command line arguments of `stellar add`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct AddArgs {
+    /// The name of the dependency.
+    name: String,
+
+    /// The path to the local dependency's folder.
+    #[arg(long)]
+    path: Option<String>,
+
+    /// The version requirement of the dependency, e.g. `^1.2`.
+    #[arg(long)]
+    version: Option<String>,
+
+    /// The author of the dependency.
+    #[arg(long)]
+    author: Option<String>,
+
+    /// Adds the dependency to `[dev-dependencies]`.
+    #[arg(long)]
+    dev: bool,
+}
+
+/// Command line arguments of `stellar remove`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct RemoveArgs {
+    /// The name of the dependency.
+    name: String,
+
+    /// Removes the dependency from `[dev-dependencies]`.
+    #[arg(long)]
+    dev: bool,
+}
+
+pub fn add_command(args: AddArgs) {
+    if let Err(err) = add(args) {
+        log_error(err);
+        process::exit(1);
+    }
+}
+
+pub fn remove_command(args: &RemoveArgs) {
+    if let Err(err) = remove(args) {
+        log_error(err);
+        process::exit(1);
+    }
+}
+
+fn add(args: AddArgs) -> Result<(), String> {
+    if args.path.is_none() && args.version.is_none() {
+        return Err("specify either `--path` or `--version` of the dependency".to_owned());
+    }
+
+    let mut dependency = TomlDependency::new();
+
+    if let Some(version) = &args.version {
+        dependency = dependency.with_version(
+            version
+                .parse()
+                .map_err(|err| format!("invalid version requirement `{version}`: {err}"))?,
+        );
+    }
+
+    if let Some(path) = args.path {
+        if !PackagePathResolver::new(Path::new(&path))
+            .manifest()
+            .is_file()
+        {
+            return Err(format!("cannot find a package manifest in {path}"));
+        }
+
+        dependency = dependency.with_path(path);
+    }
+
+    if let Some(author) = args.author {
+        dependency = dependency.with_author(author);
+    }
+
+    let table = dependency_table(args.dev);
+
+    edit_manifest(|editor| editor.add_dependency(table, &args.name, &dependency))?;
+
+    log_info(
+        "Adding",
+        match &dependency.version {
+            Some(version) => format!("{} {version} to {table}", args.name),
+            None => format!("{} to {table}", args.name),
+        },
+    );
+
+    Ok(())
+}
+
+fn remove(args: &RemoveArgs) -> Result<(), String> {
+    let table = dependency_table(args.dev);
+
+    edit_manifest(|editor| {
+        if editor.remove_dependency(table, &args.name) {
+            Ok(())
+        } else {
+            Err(format!(
+                "the dependency `{}` could not be found in `{table}`",
+                args.name
+            ))
+        }
+    })?;
+
+    log_info("Removing", format!("{} from {table}", args.name));
+
+    Ok(())
+}
+
+const fn dependency_table(dev: bool) -> DependencyTable {
+    if dev {
+        DependencyTable::DevDependencies
+    } else {
+        DependencyTable::Dependencies
+    }
+}
+
+/// Edits the manifest of the current package and writes it back, if it's
+/// still valid after the edit.
+fn edit_manifest(
+    edit: impl FnOnce(&mut ManifestEditor) -> Result<(), String>,
+) -> Result<(), String> {
+    let manifest_path = PackagePathResolver::new(Path::new(".")).manifest();
+
+    let source = fs::read_to_string(&manifest_path)
+        .map_err(|err| format!("cannot read {}: {err}", manifest_path.display()))?;
+
+    let mut editor = ManifestEditor::new(&source)
+        .map_err(|err| format!("cannot parse {}: {err}", manifest_path.display()))?;
+
+    edit(&mut editor)?;
+
+    let source = editor.to_string();
+    let errors = parse_manifest(PathId::from(&manifest_path), &source)
+        .diagnostics
+        .diagnostics
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .map(|diagnostic| diagnostic.message)
+        .collect::<Vec<_>>();
+
+    if !errors.is_empty() {
+        return Err(format!(
+            "the manifest would become invalid after the change: {}",
+            errors.join(", ")
+        ));
+    }
+
+    fs::write(&manifest_path, source)
+        .map_err(|err| format!("cannot write {}: {err}", manifest_path.display()))
+}
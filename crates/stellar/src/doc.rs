@@ -0,0 +1,74 @@
+use std::{path::Path, time::Instant};
+
+use clap::ValueEnum;
+use stellar_diagnostics::DiagnosticsEmitter;
+use stellar_doc::{html::generate_html, json::generate_json, Documentation};
+
+use crate::{
+    lockfile::sync_lockfile,
+    log::{log_error, log_info},
+    package::{analyze_package, PackageArgs},
+    workspace::build_directory,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DocFormat {
+    /// A static HTML site.
+    Html,
+    /// A versioned JSON description of the package's public API.
+    Json,
+}
+
+pub fn command(format: DocFormat, args: &PackageArgs) {
+    let now = Instant::now();
+    let root = Path::new(".");
+
+    let analyzed =
+        match sync_lockfile(root, args.locked).and_then(|()| analyze_package(root, args, false)) {
+            Err(err) => {
+                log_error(err);
+                return;
+            }
+            Ok(analyzed) => analyzed,
+        };
+
+    DiagnosticsEmitter::new().emit_global_diagnostics(analyzed.state.diagnostics());
+
+    if analyzed.state.diagnostics().is_fatal() {
+        log_error("cannot document the package due to the previous errors");
+        return;
+    }
+
+    let documentation = Documentation::new(analyzed.state.db(), analyzed.package, &analyzed.hir);
+    let output_directory = match build_directory(root) {
+        Err(err) => {
+            log_error(err);
+            return;
+        }
+        Ok(build_directory) => build_directory.join("doc"),
+    };
+
+    let generated = match format {
+        DocFormat::Html => generate_html(&documentation, &output_directory)
+            .map(|()| output_directory.join("index.html")),
+        DocFormat::Json => generate_json(&documentation, &output_directory),
+    };
+
+    let generated = match generated {
+        Err(err) => {
+            log_error(format!("cannot write the documentation: {err}"));
+            return;
+        }
+        Ok(generated) => generated,
+    };
+
+    log_info(
+        "Documented",
+        format!(
+            "{} in {}s",
+            documentation.package_name,
+            now.elapsed().as_secs_f64()
+        ),
+    );
+    log_info("Generated", generated.display().to_string());
+}
@@ -0,0 +1,90 @@
+use std::{fs, path::Path, process};
+
+use stellar_diagnostics::DiagnosticsEmitter;
+use stellar_doc::doctest::{collect_doctests, run_doctest, DoctestOutcome};
+use stellar_filesystem::location::Location;
+
+use crate::{
+    lockfile::sync_lockfile,
+    log::{log_error, log_info},
+    package::{analyze_package, PackageArgs},
+};
+
+pub fn command(args: &PackageArgs) {
+    let root = Path::new(".");
+    let analyzed =
+        match sync_lockfile(root, args.locked).and_then(|()| analyze_package(root, args, true)) {
+            Err(err) => {
+                log_error(err);
+                process::exit(1);
+            }
+            Ok(analyzed) => analyzed,
+        };
+
+    let mut diagnostics_emitter = DiagnosticsEmitter::new();
+    diagnostics_emitter.emit_global_diagnostics(analyzed.state.diagnostics());
+
+    if analyzed.state.diagnostics().is_fatal() {
+        log_error("cannot run doctests due to the previous errors");
+        process::exit(1);
+    }
+
+    let doctests = collect_doctests(
+        analyzed.state.db(),
+        analyzed
+            .hir
+            .iter()
+            .filter(|(module, _)| module.package() == analyzed.package),
+    );
+
+    log_info("Running", format!("{} doctests", doctests.len()));
+
+    let (mut passed, mut failed, mut ignored) = (0, 0, 0);
+
+    for doctest in &doctests {
+        let name = format!("{} ({})", doctest.path, display_location(doctest.location));
+
+        match run_doctest(&analyzed.state, analyzed.package, doctest) {
+            DoctestOutcome::Passed => {
+                passed += 1;
+                log_info("Passed", name);
+            }
+            DoctestOutcome::Ignored => {
+                ignored += 1;
+                log_info("Ignored", name);
+            }
+            DoctestOutcome::Failed(diagnostics) => {
+                failed += 1;
+                log_error(format!("doctest {name} failed\n"));
+                diagnostics_emitter.emit_global_diagnostics(&diagnostics);
+            }
+            DoctestOutcome::UnexpectedlyPassed => {
+                failed += 1;
+                log_error(format!(
+                    "doctest {name} is marked with `should_fail`, but it compiled successfully\n"
+                ));
+            }
+        }
+    }
+
+    log_info(
+        "Doctests",
+        format!("{passed} passed; {failed} failed; {ignored} ignored"),
+    );
+
+    if failed != 0 {
+        process::exit(1);
+    }
+}
+
+/// Returns `<file>:<line>` for a given location.
+fn display_location(location: Location) -> String {
+    let line = fs::read_to_string(location.filepath.as_path()).map_or(0, |source| {
+        source[..location.start.0.min(source.len())]
+            .matches('\n')
+            .count()
+            + 1
+    });
+
+    format!("{}:{line}", location.filepath)
+}
@@ -0,0 +1,357 @@
+//! Fetches dependencies from git repositories.
+//!
+//! Repositories are cloned into the cache directory (`$STELLAR_HOME`, or
+//! `~/.stellar` by default) and shared between packages:
+//!
+//! ```txt
+//! git/db/<name>-<hash>/                 a bare clone of the repository
+//! git/checkouts/<name>-<hash>/<commit>/ a checkout of a commit
+//! ```
+//!
+//! The `git` command is used, so that local repositories, `file://` URLs and
+//! credentials configured for git work as usual. Repositories and revisions are
+//! always passed after `--` or `--end-of-options`, so that they cannot be
+//! interpreted as options. Once a commit is recorded in
+//! the lockfile, the repository is only fetched again if the commit is missing
+//! in the cache. When dependencies are vendored (see [`crate::vendor`]), locked
+//! commits are taken from the vendor directory instead.
+
+use std::{
+    env, fs,
+    path::{self, Component, Path, PathBuf},
+    process::Command,
+};
+
+use sha2::{Digest, Sha256};
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::PathId;
+use stellar_manifest::{
+    lockfile::{GitSource, Lockfile, PackageSource},
+    parse_manifest, GitReference, TomlDependency,
+};
+use walkdir::WalkDir;
+
+use crate::{lockfile::relative_path, log::log_info, vendor::vendored_git_package};
+
+/// Checks out git dependencies of packages, that share the lockfile located in
+/// `lock_root`.
+pub struct GitFetcher {
+    lock_root: PathBuf,
+    lockfile: Option<Lockfile>,
+
+    /// Commits, that references were already resolved to, by repository URLs and
+    /// references, so that a repository is fetched at most once per build.
+    commits: FxHashMap<(String, GitReference), String>,
+
+    /// The vendor directory, if packages are taken from vendored checkouts
+    /// instead of repositories.
+    vendor_directory: Option<PathBuf>,
+}
+
+impl GitFetcher {
+    /// Returns a fetcher, that prefers commits recorded in a given lockfile.
+    pub fn new(lock_root: impl Into<PathBuf>, lockfile: Option<Lockfile>) -> Self {
+        Self {
+            lock_root: lock_root.into(),
+            lockfile,
+            commits: FxHashMap::default(),
+            vendor_directory: None,
+        }
+    }
+
+    /// Builds a new fetcher, that takes packages from vendored checkouts of
+    /// locked commits in a given vendor directory.
+    #[must_use]
+    pub fn with_vendor_directory(mut self, vendor_directory: impl Into<PathBuf>) -> Self {
+        self.vendor_directory = Some(vendor_directory.into());
+        self
+    }
+
+    /// Checks out the repository of a given dependency of the package located in
+    /// `dependent` and returns the directory of the package with a given name
+    /// inside of the checkout, together with the checked out commit.
+    ///
+    /// When dependencies are vendored, the vendored checkout is returned instead.
+    pub fn fetch(
+        &mut self,
+        name: &str,
+        dependency: &TomlDependency,
+        dependent: &Path,
+    ) -> Result<(PathBuf, GitSource), String> {
+        let location = dependency.git.as_deref().unwrap_or_default();
+        let reference = dependency.git_reference();
+
+        // Paths to local repositories are resolved against the dependent package,
+        // but recorded in the lockfile relative to the lockfile directory.
+        let (url, remote) = if is_url(location) {
+            (location.to_owned(), location.to_owned())
+        } else {
+            let path = dependent.join(location);
+            let path = match path.canonicalize() {
+                Ok(path) => path,
+                // Vendored checkouts don't need the repository, so it may be missing.
+                Err(_) if self.vendor_directory.is_some() => normalize_path(
+                    &path::absolute(&path)
+                        .map_err(|err| format!("cannot resolve {}: {err}", path.display()))?,
+                ),
+                Err(err) => {
+                    return Err(format!(
+                        "cannot find git repository {}: {err}",
+                        path.display()
+                    ))
+                }
+            };
+
+            (
+                relative_path(&self.lock_root, &path),
+                path.to_string_lossy().into_owned(),
+            )
+        };
+
+        if let Some(vendor_directory) = &self.vendor_directory {
+            return vendored_git_package(
+                vendor_directory,
+                name,
+                &url,
+                &reference,
+                self.lockfile.as_ref(),
+            );
+        }
+
+        let identifier = format!(
+            "{name}-{}",
+            &hex::encode(Sha256::digest(remote.as_bytes()))[..16]
+        );
+        let cache = home_directory()?.join("git");
+        let database = cache.join("db").join(&identifier);
+
+        let key = (url.clone(), reference.clone());
+        let commit = if let Some(commit) = self.commits.get(&key) {
+            commit.clone()
+        } else {
+            let commit = self.resolve_commit(name, &url, &reference, &remote, &database)?;
+            self.commits.insert(key, commit.clone());
+            commit
+        };
+
+        let checkout = cache.join("checkouts").join(&identifier).join(&commit);
+
+        if !checkout.exists() {
+            // The commit is checked out into a temporary directory first, so that an
+            // interrupted build doesn't leave a partial checkout behind.
+            let temporary = checkout.with_file_name(format!(".{commit}"));
+            drop(fs::remove_dir_all(&temporary));
+
+            git(
+                None,
+                &[
+                    "clone",
+                    "--quiet",
+                    "--no-checkout",
+                    "--",
+                    &database.to_string_lossy(),
+                    &temporary.to_string_lossy(),
+                ],
+            )?;
+            git(
+                Some(&temporary),
+                &["checkout", "--quiet", "--detach", &commit, "--"],
+            )?;
+            fs::rename(&temporary, &checkout)
+                .map_err(|err| format!("cannot write {}: {err}", checkout.display()))?;
+        }
+
+        let root = find_package(&checkout, name).ok_or_else(|| {
+            format!("cannot find package `{name}` in {location} at commit {commit}")
+        })?;
+
+        Ok((root, GitSource::new(url, reference, commit)))
+    }
+
+    /// Returns the commit, that a given reference points to. The commit recorded in
+    /// the lockfile is used, unless the repository or the reference changed.
+    fn resolve_commit(
+        &self,
+        name: &str,
+        url: &str,
+        reference: &GitReference,
+        remote: &str,
+        database: &Path,
+    ) -> Result<String, String> {
+        let locked = self
+            .lockfile
+            .iter()
+            .flat_map(|lockfile| &lockfile.packages)
+            .filter(|package| package.name == name)
+            .find_map(|package| match &package.source {
+                Some(PackageSource::Git(source))
+                    if source.url == url && source.reference == *reference =>
+                {
+                    Some(source.commit.clone())
+                }
+                _ => None,
+            });
+
+        if let Some(commit) = &locked {
+            if database.exists()
+                && git(
+                    Some(database),
+                    &[
+                        "cat-file",
+                        "-e",
+                        "--end-of-options",
+                        &format!("{commit}^{{commit}}"),
+                    ],
+                )
+                .is_ok()
+            {
+                return Ok(commit.clone());
+            }
+        }
+
+        log_info("Updating", format!("git repository {remote}"));
+
+        if database.exists() {
+            git(
+                Some(database),
+                &[
+                    "fetch",
+                    "--quiet",
+                    "--force",
+                    "--tags",
+                    "--",
+                    remote,
+                    "+refs/heads/*:refs/heads/*",
+                ],
+            )?;
+        } else {
+            fs::create_dir_all(database.parent().unwrap_or(database))
+                .map_err(|err| format!("cannot create {}: {err}", database.display()))?;
+            git(
+                None,
+                &[
+                    "clone",
+                    "--quiet",
+                    "--bare",
+                    "--",
+                    remote,
+                    &database.to_string_lossy(),
+                ],
+            )?;
+        }
+
+        let revision = match (&locked, reference) {
+            (Some(commit), _) | (None, GitReference::Rev(commit)) => commit.clone(),
+            (None, GitReference::DefaultBranch) => "HEAD".to_owned(),
+            (None, GitReference::Branch(branch)) => format!("refs/heads/{branch}"),
+            (None, GitReference::Tag(tag)) => format!("refs/tags/{tag}"),
+        };
+
+        git(
+            Some(database),
+            &[
+                "rev-parse",
+                "--verify",
+                "--end-of-options",
+                &format!("{revision}^{{commit}}"),
+            ],
+        )
+        .map(|commit| commit.trim().to_owned())
+        .map_err(|_| {
+            if locked.is_some() {
+                format!("commit {revision} of `{name}`, that is recorded in package.lock, is not found in {remote}")
+            } else {
+                let reference = match reference {
+                    GitReference::DefaultBranch => "the default branch".to_owned(),
+                    GitReference::Branch(branch) => format!("branch `{branch}`"),
+                    GitReference::Tag(tag) => format!("tag `{tag}`"),
+                    GitReference::Rev(rev) => format!("revision `{rev}`"),
+                };
+
+                format!("cannot find {reference} of `{name}` in {remote}")
+            }
+        })
+    }
+}
+
+/// Returns `true` if a given git repository location is a URL rather than a path,
+/// e.g. `https://github.com/quantumatic/json` or `git@github.com:quantumatic/json`.
+pub fn is_url(location: &str) -> bool {
+    location.contains("://") || location.starts_with("git@")
+}
+
+/// Removes `.` and `..` components from a given absolute path without accessing
+/// the filesystem.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Returns the directory, that caches repositories and other downloaded files.
+fn home_directory() -> Result<PathBuf, String> {
+    if let Some(home) = env::var_os("STELLAR_HOME") {
+        return Ok(PathBuf::from(home));
+    }
+
+    env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".stellar"))
+        .ok_or_else(|| "cannot find the home directory, set `STELLAR_HOME`".to_owned())
+}
+
+/// Returns the root of the package with a given name in a checkout: either the
+/// checkout root, or one of its subdirectories.
+fn find_package(checkout: &Path, name: &str) -> Option<PathBuf> {
+    WalkDir::new(checkout)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_dir())
+        .map(walkdir::DirEntry::into_path)
+        .find(|root| {
+            let manifest_path = PackagePathResolver::new(root).manifest();
+
+            fs::read_to_string(&manifest_path).is_ok_and(|source| {
+                parse_manifest(PathId::from(&manifest_path), source)
+                    .manifest
+                    .and_then(|manifest| manifest.package)
+                    .is_some_and(|package| package.name == name)
+            })
+        })
+}
+
+/// Runs `git` with given arguments in a given directory and returns its output.
+fn git(directory: Option<&Path>, args: &[&str]) -> Result<String, String> {
+    let mut command = Command::new("git");
+
+    if let Some(directory) = directory {
+        command.arg("-C").arg(directory);
+    }
+
+    let output = command
+        .args(args)
+        .output()
+        .map_err(|err| format!("cannot run git: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
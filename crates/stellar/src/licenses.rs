@@ -0,0 +1,102 @@
+//! Lists licenses of packages in the resolved dependency graph:
+//!
+//! ```txt
+//! app v0.1.0: MIT
+//! http v1.0.0: MIT OR Apache-2.0
+//! json v0.1.0: no license
+//! ```
+//!
+//! Licenses, that dependencies must not require, are read from the `[licenses]`
+//! section of the configuration and from `--deny` arguments. The command fails,
+//! if a dependency cannot be used without one of them. Packages without a
+//! license are reported, but not treated as denied.
+
+use std::{path::Path, process};
+
+use stellar_fx_hash::FxHashSet;
+use stellar_manifest::license::{is_license_id, LicenseExpression};
+
+use crate::{config::Config, lockfile::lock_scope, log::log_error, package::dependency_graph};
+
+/// Command line arguments of `stellar licenses`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct LicensesArgs {
+    /// Fails if a dependency requires a license with a given SPDX identifier, in
+    /// addition to licenses denied in the configuration.
+    #[arg(long, value_name = "LICENSE")]
+    deny: Vec<String>,
+}
+
+pub fn command(args: &LicensesArgs) {
+    if let Err(err) = licenses(args) {
+        log_error(err);
+        process::exit(1);
+    }
+}
+
+fn licenses(args: &LicensesArgs) -> Result<(), String> {
+    let (lock_root, members) = lock_scope(Path::new("."))?;
+    let config = Config::load(&lock_root)?;
+    let denied = config
+        .licenses
+        .into_iter()
+        .flat_map(|licenses| licenses.deny)
+        .chain(args.deny.iter().cloned())
+        .collect::<Vec<_>>();
+
+    if let Some(license) = denied.iter().find(|license| !is_license_id(license)) {
+        return Err(format!(
+            "`{license}` is not an SPDX license identifier, see https://spdx.org/licenses"
+        ));
+    }
+
+    let graph = dependency_graph(&lock_root, &members)?;
+    let mut packages = graph
+        .packages()
+        .map(|node| graph.package(node))
+        .map(|package| {
+            let license = package
+                .manifest
+                .package
+                .as_ref()
+                .and_then(|package| package.license.clone());
+
+            (package.name.clone(), package.version.to_string(), license)
+        })
+        .collect::<FxHashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    packages.sort();
+
+    let mut violations = Vec::new();
+
+    for (name, version, license) in packages {
+        let Some(license) = license else {
+            println!("{name} v{version}: no license");
+            continue;
+        };
+
+        println!("{name} v{version}: {license}");
+
+        let allowed = LicenseExpression::parse(&license)
+            .is_ok_and(|expression| expression.is_allowed(&denied));
+
+        if !allowed {
+            violations.push(format!("{name} v{version} ({license})"));
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "packages require denied licenses:\n{}",
+            violations
+                .iter()
+                .map(|violation| format!("  {violation}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ))
+    }
+}
@@ -0,0 +1,234 @@
+//! Keeps `package.lock` in sync with manifests.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use stellar_depgraph::{DependencyGraph, PackageKind};
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_manifest::lockfile::{LockedPackage, Lockfile, PackageSource};
+use walkdir::WalkDir;
+
+use crate::{
+    log::log_info,
+    package::{canonicalize, dependency_graph},
+    registry::registry,
+    workspace::Workspace,
+};
+
+/// Brings the lockfile of the package located in a given directory, or of the
+/// workspace it's a member of, up to date with the manifests.
+///
+/// Every package reachable from the package (or workspace members) is locked,
+/// including optional and dev dependencies, so that the lockfile doesn't depend
+/// on enabled features.
+///
+/// When `locked` is `true`, the lockfile is never written, and an error is returned
+/// if it's missing or out of date.
+pub fn sync_lockfile(root: &Path, locked: bool) -> Result<(), String> {
+    let (lock_root, members) = lock_scope(root)?;
+    let graph = dependency_graph(&lock_root, &members)?;
+
+    update_lockfile(&lock_root, &graph, locked)
+}
+
+/// Brings the lockfile located in `lock_root` up to date with a given dependency
+/// graph. See [`sync_lockfile`] for more details.
+pub fn update_lockfile(
+    lock_root: &Path,
+    graph: &DependencyGraph,
+    locked: bool,
+) -> Result<(), String> {
+    let lockfile_path = PackagePathResolver::new(lock_root).lockfile();
+    let old_lockfile = if lockfile_path.is_file() {
+        let source = fs::read_to_string(&lockfile_path)
+            .map_err(|err| format!("cannot read {}: {err}", lockfile_path.display()))?;
+
+        Some(
+            Lockfile::parse(&source)
+                .map_err(|err| format!("cannot parse {}: {err}", lockfile_path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    let lockfile = resolve_lockfile(lock_root, graph, old_lockfile.as_ref())?;
+
+    let changes = match &old_lockfile {
+        Some(old_lockfile) => old_lockfile.changes(&lockfile),
+        None => vec!["the lockfile doesn't exist".to_owned()],
+    };
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    if locked {
+        return Err(format!(
+            "{} needs to be updated, but `--locked` was passed:\n{}",
+            lockfile_path.display(),
+            changes
+                .iter()
+                .map(|change| format!("  {change}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ));
+    }
+
+    fs::write(&lockfile_path, lockfile.to_string())
+        .map_err(|err| format!("cannot write {}: {err}", lockfile_path.display()))?;
+
+    if old_lockfile.is_some() {
+        for change in changes {
+            log_info("Updating", format!("package.lock: {change}"));
+        }
+    } else {
+        log_info("Locking", format!("{} package(s)", lockfile.packages.len()));
+    }
+
+    Ok(())
+}
+
+/// Returns the directory containing the lockfile and canonical roots of packages
+/// being built: members of the workspace or the package itself.
+pub fn lock_scope(root: &Path) -> Result<(PathBuf, Vec<PathBuf>), String> {
+    let workspace = match Workspace::load(root)? {
+        Some(workspace) => Some(workspace),
+        None => Workspace::find(root)?,
+    };
+
+    if let Some(workspace) = workspace {
+        return Ok((workspace.root, workspace.members));
+    }
+
+    let root = canonicalize(root)?;
+
+    Ok((root.clone(), vec![root]))
+}
+
+/// Returns a lockfile listing every package in a given dependency graph.
+///
+/// Registry packages keep their source from the old lockfile, if no registry is
+/// configured, e.g. because they are vendored and the `[registry]` section was
+/// removed.
+fn resolve_lockfile(
+    lock_root: &Path,
+    graph: &DependencyGraph,
+    old_lockfile: Option<&Lockfile>,
+) -> Result<Lockfile, String> {
+    let registry = registry(lock_root)?;
+    let mut packages = Vec::new();
+
+    for node in graph.packages() {
+        let package = graph.package(node);
+        let mut locked = LockedPackage::new(&package.name, package.version.clone());
+
+        if let Some(author) = package
+            .manifest
+            .package
+            .as_ref()
+            .and_then(|section| section.author.as_ref())
+        {
+            locked = locked.with_author(author);
+        }
+
+        let source = match (package.kind, &registry) {
+            (PackageKind::Root, _) => None,
+            (PackageKind::Git, _) => package.git.clone().map(PackageSource::Git),
+            (PackageKind::Registry, Some(registry)) => Some(PackageSource::Registry(
+                relative_path(lock_root, registry.path()),
+            )),
+            (PackageKind::Registry, None) => old_lockfile
+                .into_iter()
+                .flat_map(|lockfile| &lockfile.packages)
+                .find(|locked| {
+                    locked.name == package.name
+                        && locked.version == package.version
+                        && matches!(locked.source, Some(PackageSource::Registry(_)))
+                })
+                .and_then(|locked| locked.source.clone())
+                .or_else(|| {
+                    Some(PackageSource::Path(relative_path(
+                        lock_root,
+                        &package.canonical_path,
+                    )))
+                }),
+            _ => Some(PackageSource::Path(relative_path(
+                lock_root,
+                &package.canonical_path,
+            ))),
+        };
+
+        // Path dependencies are edited in place like packages being built, so
+        // only packages fetched from a registry or a git repository are checked.
+        if matches!(
+            source,
+            Some(PackageSource::Registry(_) | PackageSource::Git(_))
+        ) {
+            locked = locked.with_checksum(package_checksum(&package.canonical_path)?);
+        }
+
+        if let Some(source) = source {
+            locked = locked.with_source(source);
+        }
+
+        packages.push(
+            locked.with_dependencies(
+                graph
+                    .dependencies(node)
+                    .into_iter()
+                    .map(|(_, dependency)| graph.package(dependency).name.as_str()),
+            ),
+        );
+    }
+
+    Ok(Lockfile::new(packages))
+}
+
+/// Returns the SHA-256 checksum of the manifest and source files of the package
+/// located in a given directory.
+pub fn package_checksum(root: &Path) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+
+    for (name, path) in package_files(root)? {
+        let contents =
+            fs::read(&path).map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(contents);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Returns the manifest and source files of the package located in a given
+/// directory, together with their paths relative to the package root, in
+/// a stable order.
+pub fn package_files(root: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let path_resolver = PackagePathResolver::new(root);
+    let mut files = vec![("package.toml".to_owned(), path_resolver.manifest())];
+
+    for entry in WalkDir::new(path_resolver.source_directory()).sort_by_file_name() {
+        let entry = entry.map_err(|err| err.to_string())?;
+
+        if entry.file_type().is_file() {
+            files.push((relative_path(root, entry.path()), entry.into_path()));
+        }
+    }
+
+    Ok(files)
+}
+
+/// Returns a path relative to a given base with `/` as a separator.
+pub fn relative_path(base: &Path, path: &Path) -> String {
+    pathdiff::diff_paths(path, base)
+        .unwrap_or_else(|| path.to_owned())
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -28,7 +28,7 @@ pub fn command(filepath: &str) {
 
             now = Instant::now();
 
-            let hir = LowerToHir::run_all(&mut state, vec![ast]);
+            let hir = LowerToHir::run_all(vec![ast]);
             let hir = hir.first_key_value().unwrap().1;
 
             log_info("Lowered", format!("in {}s", now.elapsed().as_secs_f64()));
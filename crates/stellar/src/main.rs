@@ -59,16 +59,31 @@
 
 use clap::{Parser, Subcommand};
 
+mod check;
+mod config;
+mod dependencies;
+mod doc;
+mod doctest;
+mod git;
 #[cfg(feature = "debug")]
 // mod collect_definitions;
 // mod collect_signatures;
 mod lex;
+mod licenses;
+mod lockfile;
 mod log;
+mod migrate;
 // mod lower;
+mod package;
 mod parse;
 mod parse_manifest;
+mod publish;
+mod registry;
+mod tree;
+mod vendor;
 // mod resolve_imports;
 mod version;
+mod workspace;
 
 #[derive(Parser)]
 #[command(name = "stellar")]
@@ -118,6 +133,65 @@ enum Commands {
     ParseManifest { filepath: String },
     #[command(about = "Creates a new package")]
     New { package_name: String },
+    #[command(about = "Analyzes the current package or every member of the current workspace")]
+    Check {
+        #[command(flatten)]
+        args: package::PackageArgs,
+    },
+    #[command(about = "Builds the current package or every member of the current workspace")]
+    Build {
+        #[command(flatten)]
+        args: package::PackageArgs,
+    },
+    #[command(about = "Adds a dependency to the manifest of the current package")]
+    Add {
+        #[command(flatten)]
+        args: dependencies::AddArgs,
+    },
+    #[command(about = "Removes a dependency from the manifest of the current package")]
+    Remove {
+        #[command(flatten)]
+        args: dependencies::RemoveArgs,
+    },
+    #[command(about = "Packs the current package into an archive, that can be published")]
+    Package,
+    #[command(about = "Publishes the current package into a package registry")]
+    Publish {
+        #[command(flatten)]
+        args: publish::PublishArgs,
+    },
+    #[command(about = "Copies registry and git dependencies into the vendor directory")]
+    Vendor,
+    #[command(about = "Migrates the current package or workspace to the next edition")]
+    Migrate,
+    #[command(about = "Prints the dependency graph of the current package or workspace")]
+    Tree {
+        #[command(flatten)]
+        args: tree::TreeArgs,
+    },
+    #[command(about = "Lists licenses of the current package and its dependencies")]
+    Licenses {
+        #[command(flatten)]
+        args: licenses::LicensesArgs,
+    },
+    #[command(about = "Generates documentation for the current package")]
+    Doc {
+        #[arg(long, value_enum, default_value_t = doc::DocFormat::Html)]
+        format: doc::DocFormat,
+        #[command(flatten)]
+        args: package::PackageArgs,
+    },
+    #[command(
+        about = "Checks code examples in docstrings of the current package",
+        long_about = "Checks code examples in docstrings of the current package.\n\n\
+                      Examples are parsed and go through name resolution, but bodies of \
+                      functions are not type-checked yet, so only syntax errors and \
+                      unresolved names and imports are reported."
+    )]
+    Doctest {
+        #[command(flatten)]
+        args: package::PackageArgs,
+    },
     #[command(about = "Prints current version of the compiler")]
     CompilerVersion,
     #[command(about = "Prints current version of the standart library")]
@@ -148,6 +222,8 @@ fn main() {
         .with_level(false)
         .init();
 
+    stellar_lint::register_lints();
+
     match Cli::parse().command {
         // #[cfg(feature = "debug")]
         // Commands::CollectDefinitions => collect_definitions::command(),
@@ -155,6 +231,18 @@ fn main() {
         // Commands::CollectSignatures => collect_signatures::command(),
         // #[cfg(feature = "debug")]
         // Commands::ResolveImports => resolve_imports::command(),
+        Commands::Check { args } => check::check_command(&args),
+        Commands::Build { args } => check::build_command(&args),
+        Commands::Add { args } => dependencies::add_command(args),
+        Commands::Remove { args } => dependencies::remove_command(&args),
+        Commands::Package => publish::package_command(),
+        Commands::Publish { args } => publish::publish_command(&args),
+        Commands::Vendor => vendor::command(),
+        Commands::Migrate => migrate::command(),
+        Commands::Tree { args } => tree::command(&args),
+        Commands::Licenses { args } => licenses::command(&args),
+        Commands::Doc { format, args } => doc::command(format, &args),
+        Commands::Doctest { args } => doctest::command(&args),
         Commands::CompilerVersion => version::compiler_version_command(),
         Commands::StdVersion => version::std_version_command(),
         Commands::PackageManagerVersion => version::package_manager_version_command(),
@@ -0,0 +1,129 @@
+//! Migrates packages to the next edition of the language.
+//!
+//! Identifiers, that become keywords in the next edition, are rewritten as
+//! wrapped identifiers (`async` becomes `` `async` ``), so that the package
+//! keeps compiling, and then the edition in the manifest is updated.
+
+use std::{fs, path::Path, process};
+
+use stellar_ast::token::{resolve_next_edition_keyword, Edition, RawToken};
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_interner::DUMMY_PATH_ID;
+use stellar_lexer::Lexer;
+use stellar_manifest::ManifestEditor;
+use walkdir::WalkDir;
+
+use crate::{
+    lockfile::lock_scope,
+    log::{log_error, log_info},
+    package::read_manifest,
+};
+
+pub fn command() {
+    if let Err(err) = migrate_all(Path::new(".")) {
+        log_error(err);
+        process::exit(1);
+    }
+}
+
+/// Migrates the package located in a given directory, or every member of the
+/// workspace located there.
+fn migrate_all(root: &Path) -> Result<(), String> {
+    let (_, members) = lock_scope(root)?;
+
+    for member in members {
+        migrate(&member)?;
+    }
+
+    Ok(())
+}
+
+fn migrate(root: &Path) -> Result<(), String> {
+    let Some(package) = read_manifest(root)?.package else {
+        return Ok(());
+    };
+    let edition = package.edition();
+
+    let Some(next) = edition.next() else {
+        log_info(
+            "Skipping",
+            format!("{} already uses the latest edition", package.name),
+        );
+        return Ok(());
+    };
+
+    let mut wrapped = 0;
+
+    for entry in WalkDir::new(PackagePathResolver::new(root).source_directory()) {
+        let path = entry.map_err(|err| err.to_string())?.into_path();
+
+        if path.extension().is_none_or(|extension| extension != "sr") {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        let (migrated, count) = wrap_next_edition_keywords(&source, edition);
+
+        if count > 0 {
+            fs::write(&path, migrated)
+                .map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+            wrapped += count;
+        }
+    }
+
+    let manifest_path = PackagePathResolver::new(root).manifest();
+    let source = fs::read_to_string(&manifest_path)
+        .map_err(|err| format!("cannot read {}: {err}", manifest_path.display()))?;
+    let mut editor = ManifestEditor::new(&source)
+        .map_err(|err| format!("cannot parse {}: {err}", manifest_path.display()))?;
+
+    editor.set_edition(next)?;
+    fs::write(&manifest_path, editor.to_string())
+        .map_err(|err| format!("cannot write {}: {err}", manifest_path.display()))?;
+
+    log_info(
+        "Migrated",
+        format!(
+            "{} to the {next} edition ({wrapped} identifier(s) wrapped)",
+            package.name
+        ),
+    );
+
+    Ok(())
+}
+
+/// Rewrites identifiers in a given source, that become keywords in the edition
+/// after a given one, as wrapped identifiers. Returns the new source and the
+/// number of rewritten identifiers.
+fn wrap_next_edition_keywords(source: &str, edition: Edition) -> (String, usize) {
+    let mut lexer = Lexer::new(DUMMY_PATH_ID, source).with_edition(edition);
+    let mut migrated = String::with_capacity(source.len());
+    let mut offset = 0;
+    let mut count = 0;
+
+    loop {
+        let token = lexer.next_token();
+
+        match token.raw {
+            RawToken::EndOfFile => break,
+            RawToken::Identifier => {
+                let (start, end) = (token.location.start.0, token.location.end.0);
+
+                if resolve_next_edition_keyword(&source[start..end], edition).is_some() {
+                    migrated.push_str(&source[offset..start]);
+                    migrated.push('`');
+                    migrated.push_str(&source[start..end]);
+                    migrated.push('`');
+                    offset = end;
+                    count += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    migrated.push_str(&source[offset..]);
+
+    (migrated, count)
+}
@@ -0,0 +1,639 @@
+//! Loads a package from the filesystem, runs lints and name resolution on it.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use parking_lot::Mutex;
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{Config, ModuleId, PackageData, PackageId, Profile, State};
+use stellar_depgraph::{
+    Dependency, DependencyGraph, DependencyGraphError, DependencyKind, NodeIndex, PackageKind,
+    PackageNode, PackageProvider,
+};
+use stellar_diagnostics::{
+    lint::{is_lint_or_group, lint_and_group_names, LintLevel, LintLevels, WARNINGS_GROUP},
+    Diagnostics, DiagnosticsEmitter,
+};
+use stellar_english_commons::similarity::find_similar;
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{IdentifierId, PathId};
+use stellar_lint::RunLints;
+use stellar_manifest::{
+    binary_location, lockfile::GitSource, parse_manifest, EnabledFeatures, FeatureSelection,
+    TomlDependency, TomlManifest, TomlPackage,
+};
+use stellar_parser::{module_directory, parse_target_source_files, CfgOptions};
+use stellar_typechecker::{
+    entry_point::check_entry_point,
+    resolution::{
+        collect_definitions::CollectDefinitions, resolve_doc_links::ResolveDocLinks,
+        resolve_imports::ResolveImports,
+    },
+};
+
+use crate::{
+    git::GitFetcher,
+    lockfile::lock_scope,
+    registry::{fetch_registry_packages, read_lockfile},
+    workspace::Workspace,
+};
+
+/// Command line flags of commands, that analyze packages.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct PackageArgs {
+    #[command(flatten)]
+    pub features: FeatureArgs,
+
+    /// Fails instead of updating `package.lock`, if it's missing or out of date.
+    #[arg(long)]
+    pub locked: bool,
+
+    /// Uses the `release` profile.
+    #[arg(long, conflicts_with = "profile")]
+    pub release: bool,
+
+    /// Uses a given profile, `dev` or `release`.
+    #[arg(long, value_name = "PROFILE")]
+    pub profile: Option<Profile>,
+
+    #[command(flatten)]
+    pub lints: LintArgs,
+}
+
+/// Command line flags, that override levels of lints from the `[lints]` table.
+/// Lints are allowed first, then set to `warn` and then denied, regardless of the
+/// order of the flags.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct LintArgs {
+    /// Allows a lint or a lint group.
+    #[arg(short = 'A', long = "allow", value_name = "LINT")]
+    allow: Vec<String>,
+
+    /// Reports a lint or a lint group as a warning.
+    #[arg(short = 'W', long = "warn", value_name = "LINT")]
+    warn: Vec<String>,
+
+    /// Reports a lint or a lint group as an error, e.g. `--deny warnings`.
+    #[arg(short = 'D', long = "deny", value_name = "LINT")]
+    deny: Vec<String>,
+}
+
+impl LintArgs {
+    /// Adds levels requested by the flags to given lint levels.
+    pub fn apply(&self, levels: &mut LintLevels) -> Result<(), String> {
+        for (names, level) in [
+            (&self.allow, LintLevel::Allow),
+            (&self.warn, LintLevel::Warn),
+            (&self.deny, LintLevel::Deny),
+        ] {
+            for name in names {
+                if !is_lint_or_group(name) {
+                    return Err(find_similar(name, lint_and_group_names()).map_or_else(
+                        || format!("unknown lint `{name}`"),
+                        |similar| format!("unknown lint `{name}`, did you mean `{similar}`?"),
+                    ));
+                }
+
+                levels.set_level(name.clone(), level);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PackageArgs {
+    /// Returns the profile selected by the flags, `dev` by default.
+    pub fn profile(&self) -> Profile {
+        if self.release {
+            Profile::Release
+        } else {
+            self.profile.unwrap_or_default()
+        }
+    }
+}
+
+/// Command line flags, that select features of the package.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct FeatureArgs {
+    /// Comma-separated list of features to enable.
+    #[arg(long, value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// Enables every feature of the package.
+    #[arg(long)]
+    all_features: bool,
+
+    /// Doesn't enable the `default` feature of the package.
+    #[arg(long)]
+    no_default_features: bool,
+}
+
+impl FeatureArgs {
+    /// Returns the feature selection requested by the flags.
+    pub fn selection(&self) -> FeatureSelection {
+        let mut selection = FeatureSelection::new().with_features(self.features.iter());
+
+        if self.all_features {
+            selection = selection.with_all_features();
+        }
+
+        if self.no_default_features {
+            selection = selection.without_default_features();
+        }
+
+        selection
+    }
+}
+
+/// A package, that was parsed, lowered and went through name resolution.
+pub struct AnalyzedPackage {
+    pub state: State,
+    pub package: PackageId,
+    pub hir: FxHashMap<ModuleId, stellar_hir::Module>,
+}
+
+/// Loads the package located in a given directory: reads its manifest, parses its
+/// source files and source files of its path dependencies, lowers them into HIR
+/// and resolves names.
+///
+/// Features of the package are enabled according to the flags, while
+/// dependencies get the features requested in manifests of their dependents.
+/// Optional dependencies are only loaded, when a feature enables them. When `test`
+/// is `true`, dev dependencies of the package are loaded as well and the `test`
+/// condition of `cfg` directives is satisfied in its source files.
+///
+/// Dependencies, that only specify a version, are loaded from the registry
+/// configured for the package or its workspace, and `git` dependencies are
+/// checked out at commits recorded in the lockfile.
+///
+/// The library of every package is loaded, while binaries are only loaded for
+/// the package itself and must define the entry point. Every binary is allocated
+/// as a separate package with its own module tree, that depends on the library.
+///
+/// The compiler is configured according to the selected profile, see
+/// [`compiler_config`]. Levels of lints are taken from the `[lints]` table of the
+/// package, the profile (`warnings-as-errors` denies `warnings`) and the flags,
+/// in that order. They only apply to files of the package itself, while every
+/// dependency uses its own `[lints]` table, see [`dependency_lint_levels`].
+/// HIR lints are only checked in local packages, i.e. the package itself and its
+/// `path` dependencies, because lints of other dependencies are never reported.
+///
+/// Diagnostics are stored in the returned state.
+pub fn analyze_package(
+    root: &Path,
+    args: &PackageArgs,
+    test: bool,
+) -> Result<AnalyzedPackage, String> {
+    let (lock_root, _) = lock_scope(root)?;
+    let config = compiler_config(&lock_root, args.profile())?;
+    let graph = dependency_graph(&lock_root, &[root.to_owned()])?;
+    let root = graph.roots()[0];
+    let resolved = resolve_features(&graph, root, &args.features.selection(), test)?;
+
+    let mut lint_levels = graph.package(root).manifest.lint_levels();
+
+    if config.warnings_as_errors() {
+        lint_levels.set_level(WARNINGS_GROUP, LintLevel::Deny);
+    }
+
+    args.lints.apply(&mut lint_levels)?;
+
+    let mut state = State::new().with_config(config);
+    state.diagnostics_mut().set_lint_levels(lint_levels.clone());
+    let packages = graph.alloc_packages(state.db_mut(), |node, dependency| {
+        is_dependency_enabled(&graph, &resolved, node, dependency, test && node == root)
+    });
+    let mut ast = Vec::new();
+    let mut binaries = Vec::new();
+    let mut local_packages = Vec::new();
+
+    for (node, package) in &packages {
+        let mut cfg_options =
+            CfgOptions::new().with_features(resolved[node].features.features.iter());
+
+        if test && *node == root {
+            cfg_options = cfg_options.with_test();
+        }
+
+        let package_node = graph.package(*node);
+        let manifest = &package_node.manifest;
+
+        if matches!(package_node.kind, PackageKind::Root | PackageKind::Path) {
+            local_packages.push(*package);
+        }
+
+        state.diagnostics_mut().set_package_lint_levels(
+            &package_node.path,
+            if *node == root {
+                lint_levels.clone()
+            } else {
+                dependency_lint_levels(package_node)
+            },
+        );
+
+        let edition = manifest
+            .package
+            .as_ref()
+            .map(TomlPackage::edition)
+            .unwrap_or_default();
+        let targets = Targets::new(&package_node.path, manifest);
+
+        ast.extend(parse_target_source_files(
+            &mut state,
+            *package,
+            &targets.library,
+            &targets.excluded(&targets.library),
+            &cfg_options,
+            edition,
+        )?);
+
+        if *node != root {
+            continue;
+        }
+
+        let manifest_path = PackagePathResolver::new(&package_node.path).manifest();
+        let manifest_source = fs::read_to_string(&manifest_path).unwrap_or_default();
+
+        for (binary, root_module) in manifest.binaries().zip(&targets.binaries) {
+            let id = PackageData::alloc(
+                state.db_mut(),
+                IdentifierId::from(binary.name.as_str()),
+                PathId::from(&package_node.path),
+            );
+
+            for (name, dependency) in package.dependencies(state.db()).clone() {
+                id.add_dependency(state.db_mut(), name, dependency);
+            }
+
+            let library_name = package.name(state.db());
+            id.add_dependency(state.db_mut(), library_name, *package);
+
+            ast.extend(parse_target_source_files(
+                &mut state,
+                id,
+                root_module,
+                &targets.excluded(root_module),
+                &cfg_options,
+                edition,
+            )?);
+            local_packages.push(id);
+            binaries.push((
+                binary.name.clone(),
+                binary_location(PathId::from(&manifest_path), &manifest_source, &binary.name),
+                id,
+            ));
+        }
+    }
+
+    // The root package comes last in the build order.
+    let package = packages[packages.len() - 1].1;
+    let hir = LowerToHir::run_all(ast);
+    RunLints::run_all(&mut state, &hir, &local_packages);
+
+    for (name, location, binary) in &binaries {
+        check_entry_point(&mut state, name, *location, *binary, &hir);
+    }
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+    ResolveDocLinks::run_all(&mut state, &hir);
+
+    state.diagnostics_mut().report_unused_suppressions();
+
+    Ok(AnalyzedPackage {
+        state,
+        package,
+        hir,
+    })
+}
+
+/// Returns levels of lints in a given dependency. Levels are taken from the
+/// `[lints]` table of the dependency, but capped like with `--cap-lints` in
+/// cargo, so that lints of dependencies never fail the build: lints of `path`
+/// dependencies are at most warnings, while lints of registry and git
+/// dependencies are not reported at all.
+fn dependency_lint_levels(package: &PackageNode) -> LintLevels {
+    let cap = match package.kind {
+        PackageKind::Root | PackageKind::Path => LintLevel::Warn,
+        PackageKind::Registry | PackageKind::Git => LintLevel::Allow,
+    };
+
+    package.manifest.lint_levels().with_cap(cap)
+}
+
+/// Returns the configuration of the compiler for a given profile: default settings
+/// of the profile overridden by the `[profile]` table of the manifest located in
+/// `lock_root`, i.e. the workspace root or the package root.
+pub fn compiler_config(lock_root: &Path, profile: Profile) -> Result<Config, String> {
+    let manifest = read_manifest(lock_root)?;
+    let mut config = Config::for_profile(profile);
+
+    let Some(overrides) = manifest.profile.and_then(|profiles| match profile {
+        Profile::Dev => profiles.dev,
+        Profile::Release => profiles.release,
+    }) else {
+        return Ok(config);
+    };
+
+    if let Some(opt_level) = overrides.opt_level {
+        config = config.with_optimization_level(opt_level);
+    }
+
+    if let Some(debug) = overrides.debug {
+        config = config.with_debug_info(debug);
+    }
+
+    if let Some(overflow_checks) = overrides.overflow_checks {
+        config = config.with_overflow_checks(overflow_checks);
+    }
+
+    if let Some(strip_assertions) = overrides.strip_assertions {
+        config = config.with_strip_assertions(strip_assertions);
+    }
+
+    if let Some(warnings_as_errors) = overrides.warnings_as_errors {
+        config = config.with_warnings_as_errors(warnings_as_errors);
+    }
+
+    Ok(config)
+}
+
+/// Root modules of build targets of a package.
+struct Targets {
+    library: PathBuf,
+    binaries: Vec<PathBuf>,
+
+    /// Root modules and module directories of all targets, as well as `src/bin`,
+    /// which only contains binaries.
+    paths: Vec<PathBuf>,
+}
+
+impl Targets {
+    fn new(root: &Path, manifest: &TomlManifest) -> Self {
+        let library = root.join(manifest.library_root_module());
+        let binaries = manifest
+            .binaries()
+            .map(|binary| root.join(binary.root_module()))
+            .collect::<Vec<_>>();
+        let mut paths = vec![
+            library.clone(),
+            PackagePathResolver::new(root)
+                .source_directory()
+                .join("bin"),
+        ];
+
+        for root_module in &binaries {
+            paths.push(root_module.clone());
+            paths.push(module_directory(root_module));
+        }
+
+        Self {
+            library,
+            binaries,
+            paths,
+        }
+    }
+
+    /// Returns paths, that are not a part of the module tree of the target with
+    /// a given root module.
+    fn excluded(&self, root_module: &Path) -> Vec<PathBuf> {
+        let module_directory = module_directory(root_module);
+
+        self.paths
+            .iter()
+            .filter(|path| **path != root_module && **path != module_directory)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Reads manifests of packages in the dependency graph from the filesystem.
+struct FilesystemPackageProvider {
+    /// Roots of unpacked registry packages by their names.
+    registry_packages: FxHashMap<String, PathBuf>,
+
+    git: GitFetcher,
+}
+
+impl PackageProvider for FilesystemPackageProvider {
+    fn load_manifest(&mut self, root: &Path) -> Result<TomlManifest, String> {
+        load_manifest(root)
+    }
+
+    /// Registry packages are skipped until their versions are resolved.
+    fn fetch_registry_package(
+        &mut self,
+        name: &str,
+        _: &TomlDependency,
+    ) -> Result<Option<PathBuf>, String> {
+        Ok(self.registry_packages.get(name).cloned())
+    }
+
+    fn fetch_git_package(
+        &mut self,
+        name: &str,
+        dependency: &TomlDependency,
+        dependent: &Path,
+    ) -> Result<(PathBuf, GitSource), String> {
+        self.git.fetch(name, dependency, dependent)
+    }
+}
+
+/// Builds the dependency graph of packages located in given directories, that
+/// share the lockfile located in `lock_root`.
+///
+/// Versions of registry dependencies are resolved once the graph of local and git
+/// packages is known, and then the graph is built again with unpacked registry
+/// packages (or their vendored copies). Git packages are taken from vendored
+/// checkouts, if dependencies were vendored.
+///
+/// A dependency cycle is emitted as a diagnostic pointing to the manifests.
+pub fn dependency_graph(lock_root: &Path, roots: &[PathBuf]) -> Result<DependencyGraph, String> {
+    resolve_dependency_graph(lock_root, roots, true)
+}
+
+/// Builds the dependency graph like [`dependency_graph`], but always takes
+/// registry and git packages from the registry and repositories, even if they
+/// were vendored.
+pub fn unvendored_dependency_graph(
+    lock_root: &Path,
+    roots: &[PathBuf],
+) -> Result<DependencyGraph, String> {
+    resolve_dependency_graph(lock_root, roots, false)
+}
+
+fn resolve_dependency_graph(
+    lock_root: &Path,
+    roots: &[PathBuf],
+    vendored: bool,
+) -> Result<DependencyGraph, String> {
+    let mut git = GitFetcher::new(lock_root, read_lockfile(lock_root));
+
+    if let Some(vendor) = crate::config::Config::load(lock_root)?
+        .vendor
+        .filter(|_| vendored)
+    {
+        git = git.with_vendor_directory(vendor.path);
+    }
+
+    let mut provider = FilesystemPackageProvider {
+        registry_packages: FxHashMap::default(),
+        git,
+    };
+    let graph = build_dependency_graph(roots, &mut provider)?;
+
+    match fetch_registry_packages(lock_root, &graph, vendored)? {
+        Some(registry_packages) => {
+            provider.registry_packages = registry_packages;
+            build_dependency_graph(roots, &mut provider)
+        }
+        None => Ok(graph),
+    }
+}
+
+fn build_dependency_graph(
+    roots: &[PathBuf],
+    provider: &mut FilesystemPackageProvider,
+) -> Result<DependencyGraph, String> {
+    DependencyGraph::build(roots, provider).map_err(|err| match err {
+        DependencyGraphError::Package(message) => message,
+        DependencyGraphError::Cycle(cycle) => {
+            let mut diagnostics = Diagnostics::new();
+            diagnostics.add_diagnostic(cycle);
+            DiagnosticsEmitter::new().emit_global_diagnostics(&diagnostics);
+
+            "could not resolve dependencies due to the previous error".to_owned()
+        }
+    })
+}
+
+/// Features requested for a package and features enabled by them.
+struct ResolvedPackage {
+    selection: FeatureSelection,
+    features: EnabledFeatures,
+}
+
+/// Enables features requested by a given selection in the root package, as well
+/// as features requested for its dependencies.
+///
+/// A package can be requested by multiple dependents, in which case the union
+/// of the requested features is enabled.
+fn resolve_features(
+    graph: &DependencyGraph,
+    root: NodeIndex,
+    selection: &FeatureSelection,
+    test: bool,
+) -> Result<FxHashMap<NodeIndex, ResolvedPackage>, String> {
+    let mut resolved = FxHashMap::<NodeIndex, ResolvedPackage>::default();
+    let mut stack = vec![(root, selection.clone())];
+
+    while let Some((node, selection)) = stack.pop() {
+        let manifest = &graph.package(node).manifest;
+
+        if let Some(package) = resolved.get_mut(&node) {
+            if !package.selection.merge(&selection) {
+                continue;
+            }
+
+            package.features = manifest.enabled_features(&package.selection)?;
+        } else {
+            resolved.insert(
+                node,
+                ResolvedPackage {
+                    features: manifest.enabled_features(&selection)?,
+                    selection,
+                },
+            );
+        }
+
+        for (dependency, target) in graph.dependencies(node) {
+            if is_dependency_enabled(graph, &resolved, node, dependency, test && node == root) {
+                stack.push((target, dependency.manifest.feature_selection()));
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Returns `true` if a given dependency of a package is used: dev dependencies
+/// are only used when the package is tested, and optional dependencies only when
+/// a feature enables them.
+fn is_dependency_enabled(
+    graph: &DependencyGraph,
+    resolved: &FxHashMap<NodeIndex, ResolvedPackage>,
+    node: NodeIndex,
+    dependency: &Dependency,
+    test: bool,
+) -> bool {
+    match dependency.kind {
+        DependencyKind::Dev => test,
+        DependencyKind::Normal => resolved[&node]
+            .features
+            .is_dependency_enabled(&graph.package(node).manifest, &dependency.name),
+    }
+}
+
+/// Reads the manifest of the package located in a given directory and replaces
+/// dependencies with `workspace = true` with the ones from the workspace, that
+/// the package is a member of.
+pub fn load_manifest(root: &Path) -> Result<TomlManifest, String> {
+    let mut manifest = read_manifest(root)?;
+
+    if manifest
+        .dependencies
+        .iter()
+        .chain(manifest.dev_dependencies.iter())
+        .flatten()
+        .any(|(_, dependency)| dependency.is_inherited_from_workspace())
+    {
+        let Some(workspace) = Workspace::find(root)? else {
+            return Err(format!(
+                "{} inherits dependencies from a workspace, but the package is not a workspace member",
+                PackagePathResolver::new(root).manifest().display()
+            ));
+        };
+
+        workspace.inherit_dependencies(root, &mut manifest)?;
+    }
+
+    Ok(manifest)
+}
+
+/// Canonical paths of manifests, which diagnostics were already emitted. The same
+/// manifest can be read multiple times, e.g. when looking up a workspace.
+static REPORTED_MANIFESTS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Reads and parses the manifest of the package located in a given directory.
+///
+/// Errors and warnings found in the manifest are emitted once per manifest.
+pub fn read_manifest(root: &Path) -> Result<TomlManifest, String> {
+    let manifest_path = PackagePathResolver::new(root).manifest();
+
+    let source = fs::read_to_string(&manifest_path)
+        .map_err(|err| format!("cannot read {}: {err}", manifest_path.display()))?;
+
+    let parsed = parse_manifest(PathId::from(&manifest_path), source);
+
+    let canonical_path = canonicalize(&manifest_path)?;
+    let mut reported_manifests = REPORTED_MANIFESTS.lock();
+
+    if !reported_manifests.contains(&canonical_path) {
+        reported_manifests.push(canonical_path);
+        DiagnosticsEmitter::new().emit_global_diagnostics(&parsed.diagnostics);
+    }
+
+    parsed
+        .into_result()
+        .map_err(|_| format!("cannot parse {}", manifest_path.display()))
+}
+
+/// Returns the canonical form of a given path.
+pub fn canonicalize(path: &Path) -> Result<PathBuf, String> {
+    path.canonicalize()
+        .map_err(|err| format!("cannot find {}: {err}", path.display()))
+}
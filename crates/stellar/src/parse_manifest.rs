@@ -2,6 +2,8 @@
 
 use std::fs;
 
+use stellar_diagnostics::DiagnosticsEmitter;
+use stellar_interner::PathId;
 use stellar_manifest::parse_manifest;
 
 use crate::log::log_error;
@@ -11,15 +13,19 @@ pub fn command(filepath: &str) {
         Err(..) => {
             log_error(format!("cannot read the file {filepath}"));
         }
-        Ok(source) => match parse_manifest(source) {
-            Err(err) => {
-                log_error(format!(
-                    "cannot parse the manifest file due to the error: {err}"
-                ));
-            }
-            Ok(manifest) => {
-                println!("{manifest:?}");
+        Ok(source) => {
+            let parsed = parse_manifest(PathId::from(filepath), source);
+
+            DiagnosticsEmitter::new().emit_global_diagnostics(&parsed.diagnostics);
+
+            match parsed.into_result() {
+                Err(..) => {
+                    log_error("cannot parse the manifest file due to the previous errors");
+                }
+                Ok(manifest) => {
+                    println!("{manifest:?}");
+                }
             }
-        },
+        }
     }
 }
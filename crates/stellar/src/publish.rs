@@ -0,0 +1,293 @@
+//! Packs the current package into an archive and publishes it into a directory
+//! registry.
+//!
+//! Archives are deterministic: files are sorted, and timestamps, permissions
+//! and owners are not recorded, so packing the same sources twice produces
+//! the same checksum.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sha2::{Digest, Sha256};
+use stellar_diagnostics::DiagnosticsEmitter;
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_manifest::{
+    registry::{archive_path, index_path, IndexFile, IndexVersion},
+    TomlManifest, TomlPackage,
+};
+
+use crate::{
+    config::{Config, RegistryConfig},
+    lockfile::{lock_scope, package_files},
+    log::{log_error, log_info},
+    package::{analyze_package, read_manifest, PackageArgs},
+    registry::registry,
+    workspace::build_directory,
+};
+
+/// Command line arguments of `stellar publish`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct PublishArgs {
+    /// The directory of the registry. Defaults to the registry configured in
+    /// `.stellar/config.toml`.
+    #[arg(long)]
+    registry: Option<PathBuf>,
+}
+
+/// A packed package.
+struct Archive {
+    package: TomlPackage,
+    manifest: TomlManifest,
+    path: PathBuf,
+    checksum: String,
+}
+
+pub fn package_command() {
+    if let Err(err) = pack(Path::new(".")) {
+        log_error(err);
+        process::exit(1);
+    }
+}
+
+pub fn publish_command(args: &PublishArgs) {
+    if let Err(err) = publish(Path::new("."), args) {
+        log_error(err);
+        process::exit(1);
+    }
+}
+
+/// Packs the package located in a given directory into
+/// `build/package/<name>-<version>.tar.gz` and checks, that the unpacked
+/// archive compiles on its own.
+fn pack(root: &Path) -> Result<Archive, String> {
+    let manifest = read_manifest(root)?;
+    let package = validate_manifest(&manifest)?;
+    let prefix = format!("{}-{}", package.name, package.version);
+
+    let mut files = package_files(root)?;
+    let readme = PackagePathResolver::new(root).readme();
+
+    if readme.is_file() {
+        files.push(("README.md".to_owned(), readme));
+    }
+
+    files.sort();
+
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::best()));
+
+    for (name, path) in &files {
+        let contents =
+            fs::read(path).map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+
+        builder
+            .append_data(&mut header, format!("{prefix}/{name}"), contents.as_slice())
+            .map_err(|err| format!("cannot pack {}: {err}", path.display()))?;
+    }
+
+    let contents = builder
+        .into_inner()
+        .and_then(GzEncoder::finish)
+        .map_err(|err| format!("cannot pack `{}`: {err}", package.name))?;
+    let checksum = hex::encode(Sha256::digest(&contents));
+
+    let directory = build_directory(root)?.join("package");
+    let path = directory.join(format!("{prefix}.tar.gz"));
+
+    fs::create_dir_all(&directory)
+        .and_then(|()| fs::write(&path, &contents))
+        .map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+
+    log_info(
+        "Packaged",
+        format!("{prefix} ({} file(s), checksum {checksum})", files.len()),
+    );
+
+    verify(root, &directory, &prefix, &contents)?;
+
+    Ok(Archive {
+        package,
+        manifest,
+        path,
+        checksum,
+    })
+}
+
+/// Returns the `[package]` section of a given manifest, if the manifest can be
+/// published.
+fn validate_manifest(manifest: &TomlManifest) -> Result<TomlPackage, String> {
+    let Some(package) = &manifest.package else {
+        return Err("a workspace root, that is not a package, cannot be packaged".to_owned());
+    };
+
+    let missing = [
+        ("license", package.license.is_none()),
+        ("description", package.description.is_none()),
+    ]
+    .into_iter()
+    .filter(|(_, missing)| *missing)
+    .map(|(field, _)| format!("`{field}`"))
+    .collect::<Vec<_>>();
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "`{}` cannot be packaged, because `[package]` is missing {}",
+            package.name,
+            missing.join(" and ")
+        ));
+    }
+
+    for (name, dependency) in manifest
+        .dependencies
+        .iter()
+        .chain(manifest.dev_dependencies.iter())
+        .flatten()
+    {
+        if dependency.path.is_some()
+            || dependency.git.is_some()
+            || dependency.is_inherited_from_workspace()
+        {
+            return Err(format!(
+                "`{}` cannot be packaged, because `{name}` is not a registry dependency, specify its `version` instead",
+                package.name
+            ));
+        }
+    }
+
+    Ok(package.clone())
+}
+
+/// Unpacks the archive into the build directory and analyzes it, so that
+/// missing files and undeclared dependencies are found before publishing.
+fn verify(root: &Path, directory: &Path, prefix: &str, contents: &[u8]) -> Result<(), String> {
+    let unpacked = directory.join(prefix);
+    drop(fs::remove_dir_all(&unpacked));
+
+    tar::Archive::new(GzDecoder::new(contents))
+        .unpack(directory)
+        .map_err(|err| format!("cannot unpack {}: {err}", unpacked.display()))?;
+
+    // Registry dependencies are resolved against the registry of the package,
+    // rather than the one of the unpacked archive.
+    let (lock_root, _) = lock_scope(root)?;
+
+    if let Some(registry) = registry(&lock_root)? {
+        let config = Config {
+            registry: Some(RegistryConfig {
+                path: registry
+                    .path()
+                    .canonicalize()
+                    .map_err(|err| format!("cannot find {}: {err}", registry.path().display()))?,
+            }),
+            vendor: None,
+            licenses: None,
+        };
+        let config_path = PackagePathResolver::new(&unpacked).config();
+
+        fs::create_dir_all(config_path.parent().unwrap_or(&unpacked))
+            .and_then(|()| fs::write(&config_path, toml::to_string(&config).unwrap_or_default()))
+            .map_err(|err| format!("cannot write {}: {err}", config_path.display()))?;
+    }
+
+    log_info("Verifying", prefix);
+
+    let analyzed = analyze_package(&unpacked, &PackageArgs::default(), false)?;
+    DiagnosticsEmitter::new().emit_global_diagnostics(analyzed.state.diagnostics());
+
+    if analyzed.state.diagnostics().is_fatal() {
+        return Err(format!(
+            "`{prefix}` doesn't compile once packaged due to the previous errors"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Packs the package located in a given directory and adds it to the registry.
+fn publish(root: &Path, args: &PublishArgs) -> Result<(), String> {
+    let registry = if let Some(registry) = &args.registry {
+        registry.clone()
+    } else {
+        let (lock_root, _) = lock_scope(root)?;
+
+        Config::load(&lock_root)?
+            .registry
+            .map(|registry| registry.path)
+            .ok_or_else(|| {
+                "specify `--registry` or configure a registry in .stellar/config.toml".to_owned()
+            })?
+    };
+
+    let archive = pack(root)?;
+    let Archive {
+        package,
+        manifest,
+        path,
+        checksum,
+    } = &archive;
+
+    let index_path = index_path(&registry, &package.name);
+    let mut index_file = if index_path.is_file() {
+        let source = fs::read_to_string(&index_path)
+            .map_err(|err| format!("cannot read {}: {err}", index_path.display()))?;
+
+        IndexFile::parse(&source)
+            .map_err(|err| format!("cannot parse {}: {err}", index_path.display()))?
+    } else {
+        let mut index_file = IndexFile::new();
+        index_file.author.clone_from(&package.author);
+        index_file
+    };
+
+    if index_file.author != package.author {
+        return Err(format!(
+            "`{}` is published by {} in the registry, but the package is by {}",
+            package.name,
+            index_file.author.as_deref().unwrap_or("an unknown author"),
+            package.author.as_deref().unwrap_or("an unknown author"),
+        ));
+    }
+
+    if index_file.version(&package.version).is_some() {
+        return Err(format!(
+            "`{} {}` is already published, bump the version of the package",
+            package.name, package.version
+        ));
+    }
+
+    let destination = archive_path(&registry, &package.name, &package.version);
+
+    fs::create_dir_all(destination.parent().unwrap_or(&registry))
+        .and_then(|()| fs::copy(path, &destination))
+        .map_err(|err| format!("cannot write {}: {err}", destination.display()))?;
+
+    index_file
+        .add_version(IndexVersion::new(package.version.clone(), checksum).with_manifest(manifest));
+
+    fs::create_dir_all(index_path.parent().unwrap_or(&registry))
+        .and_then(|()| fs::write(&index_path, index_file.to_string()))
+        .map_err(|err| format!("cannot write {}: {err}", index_path.display()))?;
+
+    log_info(
+        "Published",
+        format!(
+            "{} {} to {}",
+            package.name,
+            package.version,
+            registry.display()
+        ),
+    );
+
+    Ok(())
+}
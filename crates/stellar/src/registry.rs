@@ -0,0 +1,272 @@
+//! Resolves versions of dependencies from the package registry and unpacks
+//! their archives into the build directory.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+use stellar_depgraph::{
+    resolver::{resolve, PackageIndex, Requirement, Resolution},
+    DependencyGraph, PackageKind,
+};
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_fx_hash::FxHashMap;
+use stellar_manifest::{
+    lockfile::{Lockfile, PackageSource},
+    registry::{archive_path, index_path, IndexFile},
+    Version, VersionReq,
+};
+
+use crate::{config::Config, vendor::VendorDirectory};
+
+/// A package registry stored in a local directory.
+///
+/// See [`stellar_manifest::registry`] for the layout of the directory.
+pub struct DirectoryRegistry {
+    path: PathBuf,
+    index_files: FxHashMap<String, Option<IndexFile>>,
+}
+
+impl DirectoryRegistry {
+    /// Returns a registry located in a given directory.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            index_files: FxHashMap::default(),
+        }
+    }
+
+    /// Returns the directory of the registry.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl PackageIndex for DirectoryRegistry {
+    fn index_file(&mut self, name: &str) -> Result<Option<IndexFile>, String> {
+        if let Some(index_file) = self.index_files.get(name) {
+            return Ok(index_file.clone());
+        }
+
+        let path = index_path(&self.path, name);
+        let index_file = if path.is_file() {
+            let source = fs::read_to_string(&path)
+                .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+
+            Some(
+                IndexFile::parse(&source)
+                    .map_err(|err| format!("cannot parse {}: {err}", path.display()))?,
+            )
+        } else {
+            None
+        };
+
+        self.index_files.insert(name.to_owned(), index_file.clone());
+
+        Ok(index_file)
+    }
+}
+
+/// Picks versions of registry packages, that packages in a given dependency graph
+/// depend on, and unpacks them into the build directory located in `lock_root`.
+///
+/// Versions recorded in the lockfile are preferred. When `vendored` is `true`
+/// and dependencies were vendored, vendored copies are used instead of the
+/// registry. Returns roots of packages by their names, or `None` if nothing
+/// depends on registry packages.
+pub fn fetch_registry_packages(
+    lock_root: &Path,
+    graph: &DependencyGraph,
+    vendored: bool,
+) -> Result<Option<FxHashMap<String, PathBuf>>, String> {
+    let mut requirements = Vec::new();
+    let mut authors = Vec::new();
+
+    for node in graph.packages() {
+        let package = graph.package(node);
+        let manifest = &package.manifest;
+        let dev_dependencies = if package.kind == PackageKind::Root {
+            manifest.dev_dependencies.as_ref()
+        } else {
+            None
+        };
+
+        for (name, dependency) in manifest
+            .dependencies
+            .iter()
+            .chain(dev_dependencies)
+            .flatten()
+            .filter(|(_, dependency)| dependency.path.is_none() && dependency.git.is_none())
+        {
+            requirements.push(Requirement::new(
+                &package.name,
+                name,
+                dependency.version.clone().unwrap_or(VersionReq::STAR),
+            ));
+
+            if let Some(author) = &dependency.author {
+                authors.push((package.name.as_str(), name.as_str(), author.as_str()));
+            }
+        }
+    }
+
+    let Some(requirement) = requirements.first() else {
+        return Ok(None);
+    };
+
+    let root = match graph.roots() {
+        [root] => graph.package(*root).name.clone(),
+        _ => "the workspace".to_owned(),
+    };
+    let lockfile = read_lockfile(lock_root);
+    let preferences = lockfile
+        .iter()
+        .flat_map(|lockfile| &lockfile.packages)
+        .filter(|package| matches!(package.source, Some(PackageSource::Registry(_))))
+        .map(|package| (package.name.clone(), package.version.clone()))
+        .collect();
+    let config = Config::load(lock_root)?;
+
+    if let Some(vendor) = config.vendor.filter(|_| vendored) {
+        let mut vendor = VendorDirectory::load(&vendor.path)?;
+        let resolution =
+            resolve_versions(root, &requirements, &authors, &preferences, &mut vendor)?;
+
+        return vendor
+            .verified_roots(&resolution, lockfile.as_ref())
+            .map(Some);
+    }
+
+    let Some(mut registry) = config
+        .registry
+        .map(|registry| DirectoryRegistry::new(registry.path))
+    else {
+        return Err(format!(
+            "`{}` depends on `{}` from the package registry, but no registry is configured in {}",
+            requirement.dependent,
+            requirement.name,
+            PackagePathResolver::new(lock_root).config().display()
+        ));
+    };
+
+    let resolution = resolve_versions(root, &requirements, &authors, &preferences, &mut registry)?;
+    let destination = PackagePathResolver::new(lock_root)
+        .build_directory()
+        .join("registry");
+    let mut roots = FxHashMap::default();
+
+    for (name, version) in resolution.iter() {
+        let checksum = registry
+            .index_file(name)?
+            .and_then(|index_file| {
+                index_file
+                    .version(version)
+                    .map(|published| published.checksum.clone())
+            })
+            .unwrap_or_default();
+
+        roots.insert(
+            name.to_owned(),
+            unpack(registry.path(), name, version, &checksum, &destination)?,
+        );
+    }
+
+    Ok(Some(roots))
+}
+
+/// Resolves versions of registry packages and checks, that packages come from
+/// the authors specified in `author` fields of dependencies.
+fn resolve_versions(
+    root: String,
+    requirements: &[Requirement],
+    authors: &[(&str, &str, &str)],
+    preferences: &BTreeMap<String, Version>,
+    index: &mut impl PackageIndex,
+) -> Result<Resolution, String> {
+    let resolution =
+        resolve(root, requirements, preferences, index).map_err(|err| err.to_string())?;
+
+    for (dependent, name, author) in authors {
+        let index_author = index
+            .index_file(name)?
+            .and_then(|index_file| index_file.author);
+
+        if index_author.as_deref() != Some(*author) {
+            return Err(format!(
+                "`{dependent}` depends on `{name}` by {author}, but the registry has `{name}` by {}",
+                index_author.as_deref().unwrap_or("an unknown author")
+            ));
+        }
+    }
+
+    Ok(resolution)
+}
+
+/// Returns the registry configured for the package or workspace located in
+/// a given directory.
+pub fn registry(lock_root: &Path) -> Result<Option<DirectoryRegistry>, String> {
+    Ok(Config::load(lock_root)?
+        .registry
+        .map(|registry| DirectoryRegistry::new(registry.path)))
+}
+
+/// Reads the lockfile located in a given directory. A lockfile, that cannot be
+/// read, is ignored here and reported when it's synced.
+pub fn read_lockfile(lock_root: &Path) -> Option<Lockfile> {
+    fs::read_to_string(PackagePathResolver::new(lock_root).lockfile())
+        .ok()
+        .and_then(|source| Lockfile::parse(&source).ok())
+}
+
+/// Verifies the checksum of the archive of a given package version and unpacks it
+/// into a given directory, unless it's already there. Returns the root of the
+/// unpacked package.
+///
+/// Unpacked packages are keyed by the checksum, so that an archive, which was
+/// replaced in the registry, is verified and unpacked again.
+fn unpack(
+    registry: &Path,
+    name: &str,
+    version: &Version,
+    checksum: &str,
+    destination: &Path,
+) -> Result<PathBuf, String> {
+    let root = destination.join(format!(
+        "{name}-{version}-{}",
+        checksum.get(..16).unwrap_or(checksum)
+    ));
+
+    if PackagePathResolver::new(&root).manifest().is_file() {
+        return Ok(root);
+    }
+
+    let archive = archive_path(registry, name, version);
+    let contents =
+        fs::read(&archive).map_err(|err| format!("cannot read {}: {err}", archive.display()))?;
+
+    if hex::encode(Sha256::digest(&contents)) != checksum {
+        return Err(format!(
+            "checksum of {} doesn't match the registry index",
+            archive.display()
+        ));
+    }
+
+    // Archives contain a single `<name>-<version>` directory. It's unpacked into
+    // a temporary directory first, so that an interrupted build doesn't leave
+    // a partially unpacked package behind.
+    let temporary = destination.join(format!(".{name}-{version}"));
+    drop(fs::remove_dir_all(&temporary));
+
+    tar::Archive::new(GzDecoder::new(contents.as_slice()))
+        .unpack(&temporary)
+        .map_err(|err| format!("cannot unpack {}: {err}", archive.display()))?;
+    fs::rename(temporary.join(format!("{name}-{version}")), &root)
+        .map_err(|err| format!("cannot unpack {}: {err}", archive.display()))?;
+    drop(fs::remove_dir_all(&temporary));
+
+    Ok(root)
+}
@@ -0,0 +1,122 @@
+//! Prints the resolved dependency graph:
+//!
+//! ```txt
+//! app v0.1.0 (path+.)
+//! ├── http v1.0.0 (registry+../registry)
+//! │   └── json v0.1.0 (registry+../registry)
+//! │       └── itoa v1.0.0 (registry+../registry)
+//! └── json v0.1.0 (registry+../registry) (*)
+//! [dev-dependencies]
+//! └── testing v0.1.0 (path+testing)
+//! ```
+//!
+//! Packages, whose dependencies were already printed, are marked with `(*)` and
+//! their dependencies are not repeated, see [`TreePrinter`].
+
+use std::{path::Path, process};
+
+use stellar_depgraph::{tree::TreePrinter, DependencyGraph, NodeIndex, PackageKind};
+use stellar_manifest::lockfile::PackageSource;
+
+use crate::{
+    lockfile::{lock_scope, relative_path},
+    log::log_error,
+    package::dependency_graph,
+    registry::registry,
+};
+
+/// Command line arguments of `stellar tree`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct TreeArgs {
+    /// Prints packages, that depend on a given package, instead of dependencies.
+    #[arg(long, value_name = "PACKAGE")]
+    invert: Option<String>,
+
+    /// The maximum depth of the tree, e.g. `1` only prints direct dependencies.
+    #[arg(long)]
+    depth: Option<usize>,
+
+    /// The output format.
+    #[arg(long, value_enum, default_value_t = TreeFormat::Text)]
+    format: TreeFormat,
+}
+
+/// The output format of `stellar tree`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TreeFormat {
+    /// An indented tree.
+    Text,
+    /// A Graphviz graph.
+    Dot,
+}
+
+pub fn command(args: &TreeArgs) {
+    match tree(args) {
+        Err(err) => {
+            log_error(err);
+            process::exit(1);
+        }
+        Ok(output) => print!("{output}"),
+    }
+}
+
+fn tree(args: &TreeArgs) -> Result<String, String> {
+    let (lock_root, members) = lock_scope(Path::new("."))?;
+    let graph = dependency_graph(&lock_root, &members)?;
+    let roots = match &args.invert {
+        Some(name) => {
+            let roots = graph
+                .packages()
+                .filter(|node| graph.package(*node).name == *name)
+                .collect::<Vec<_>>();
+
+            if roots.is_empty() {
+                return Err(format!("package `{name}` is not in the dependency graph"));
+            }
+
+            roots
+        }
+        None => graph.roots().to_vec(),
+    };
+
+    let registry = registry(&lock_root)?.map(|registry| registry.path().to_owned());
+    let describe = |node| describe(&graph, &lock_root, registry.as_deref(), node);
+    let mut printer = TreePrinter::new(&graph, describe).with_depth(args.depth);
+
+    if args.invert.is_some() {
+        printer = printer.inverted();
+    }
+
+    Ok(match args.format {
+        TreeFormat::Text => printer.text(&roots),
+        TreeFormat::Dot => printer.dot(&roots),
+    })
+}
+
+/// Returns the name, the version and the source of a given package, e.g.
+/// `json v0.1.0 (path+../json)`.
+fn describe(
+    graph: &DependencyGraph,
+    lock_root: &Path,
+    registry: Option<&Path>,
+    node: NodeIndex,
+) -> String {
+    let package = graph.package(node);
+    let source = match (package.kind, registry, &package.git) {
+        (PackageKind::Registry, Some(registry), _) => {
+            PackageSource::Registry(relative_path(lock_root, registry))
+        }
+        (PackageKind::Git, _, Some(git)) => PackageSource::Git(git.clone()),
+        _ => {
+            let path = relative_path(lock_root, &package.canonical_path);
+
+            PackageSource::Path(if path.is_empty() {
+                ".".to_owned()
+            } else {
+                path
+            })
+        }
+    };
+
+    format!("{} v{} ({source})", package.name, package.version)
+}
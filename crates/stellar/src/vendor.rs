@@ -0,0 +1,291 @@
+//! Copies registry and git dependencies into the `vendor` directory, so that
+//! packages can be built without access to the registry and git repositories.
+//!
+//! ```txt
+//! vendor/<name>-<version>/        a registry package
+//! vendor/git/<name>-<commit>/     a git package at the commit recorded in the lockfile
+//! ```
+//!
+//! `stellar vendor` adds the `[vendor]` section to `.stellar/config.toml`, after
+//! which registry dependencies are resolved against the vendored copies, and git
+//! dependencies are taken from the vendored checkouts of locked commits. Every
+//! build compares checksums of vendored packages with the lockfile, so that
+//! modified copies are not used by accident.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use stellar_depgraph::{
+    resolver::{PackageIndex, Resolution},
+    PackageKind,
+};
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_fx_hash::FxHashMap;
+use stellar_manifest::{
+    lockfile::{GitSource, Lockfile, PackageSource},
+    registry::{IndexFile, IndexVersion},
+    GitReference, Version,
+};
+
+use crate::{
+    lockfile::{lock_scope, package_checksum, package_files, update_lockfile},
+    log::{log_error, log_info},
+    package::{read_manifest, unvendored_dependency_graph},
+};
+
+pub fn command() {
+    if let Err(err) = vendor(Path::new(".")) {
+        log_error(err);
+        process::exit(1);
+    }
+}
+
+fn vendor(root: &Path) -> Result<(), String> {
+    let (lock_root, members) = lock_scope(root)?;
+    let graph = unvendored_dependency_graph(&lock_root, &members)?;
+
+    update_lockfile(&lock_root, &graph, false)?;
+
+    let vendor_directory = lock_root.join("vendor");
+
+    if vendor_directory.exists() {
+        fs::remove_dir_all(&vendor_directory)
+            .map_err(|err| format!("cannot remove {}: {err}", vendor_directory.display()))?;
+    }
+
+    let mut vendored = 0;
+
+    for node in graph.packages() {
+        let package = graph.package(node);
+        let destination = match (package.kind, &package.git) {
+            (PackageKind::Registry, _) => {
+                vendor_directory.join(format!("{}-{}", package.name, package.version))
+            }
+            (PackageKind::Git, Some(source)) => {
+                git_checkout_directory(&vendor_directory, &package.name, &source.commit)
+            }
+            _ => continue,
+        };
+
+        copy_package(&package.canonical_path, &destination)?;
+        vendored += 1;
+    }
+
+    write_vendor_config(&lock_root)?;
+
+    log_info(
+        "Vendored",
+        format!("{vendored} package(s) into {}", vendor_directory.display()),
+    );
+
+    Ok(())
+}
+
+/// Copies the manifest, source files and the readme of the package located in
+/// a given directory into `destination`.
+fn copy_package(root: &Path, destination: &Path) -> Result<(), String> {
+    let mut files = package_files(root)?;
+    let readme = PackagePathResolver::new(root).readme();
+
+    if readme.is_file() {
+        files.push(("README.md".to_owned(), readme));
+    }
+
+    for (name, path) in files {
+        let target = destination.join(name);
+
+        fs::create_dir_all(target.parent().unwrap_or(destination))
+            .and_then(|()| fs::copy(&path, &target))
+            .map_err(|err| format!("cannot copy {}: {err}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the directory of the vendored copy of a git package at a given commit.
+fn git_checkout_directory(vendor_directory: &Path, name: &str, commit: &str) -> PathBuf {
+    vendor_directory
+        .join("git")
+        .join(format!("{name}-{commit}"))
+}
+
+/// Returns the root of the vendored copy of a git package with a given name,
+/// repository URL and reference, together with its source, after checking, that
+/// it matches the checksum in the lockfile.
+///
+/// Only the commit recorded in the lockfile is vendored, so the package must be
+/// locked.
+pub fn vendored_git_package(
+    vendor_directory: &Path,
+    name: &str,
+    url: &str,
+    reference: &GitReference,
+    lockfile: Option<&Lockfile>,
+) -> Result<(PathBuf, GitSource), String> {
+    let locked = lockfile
+        .iter()
+        .flat_map(|lockfile| &lockfile.packages)
+        .filter(|package| package.name == name)
+        .find_map(|package| match &package.source {
+            Some(PackageSource::Git(source))
+                if source.url == url && source.reference == *reference =>
+            {
+                Some((source, package.checksum.as_ref()?))
+            }
+            _ => None,
+        });
+
+    let Some((source, checksum)) = locked else {
+        return Err(format!(
+            "`{name}` from {url} is not locked, run `stellar vendor` again"
+        ));
+    };
+
+    let root = git_checkout_directory(vendor_directory, name, &source.commit);
+
+    if !PackagePathResolver::new(&root).manifest().is_file() {
+        return Err(format!(
+            "`{name}` at commit {} is not vendored, run `stellar vendor` again",
+            source.commit
+        ));
+    }
+
+    if package_checksum(&root)? != *checksum {
+        return Err(format!(
+            "checksum of the vendored `{name}` at commit {} doesn't match package.lock, {} was modified",
+            source.commit,
+            root.display()
+        ));
+    }
+
+    Ok((root, source.clone()))
+}
+
+/// Adds the `[vendor]` section to the configuration file, keeping other sections.
+fn write_vendor_config(lock_root: &Path) -> Result<(), String> {
+    let path = PackagePathResolver::new(lock_root).config();
+    let mut config = if path.is_file() {
+        let source = fs::read_to_string(&path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+
+        source
+            .parse::<toml::Table>()
+            .map_err(|err| format!("cannot parse {}: {}", path.display(), err.message()))?
+    } else {
+        toml::Table::new()
+    };
+
+    config.insert(
+        "vendor".to_owned(),
+        toml::Value::Table(toml::Table::from_iter([(
+            "path".to_owned(),
+            toml::Value::String("vendor".to_owned()),
+        )])),
+    );
+
+    fs::create_dir_all(path.parent().unwrap_or(lock_root))
+        .and_then(|()| fs::write(&path, config.to_string()))
+        .map_err(|err| format!("cannot write {}: {err}", path.display()))
+}
+
+/// Vendored copies of registry packages. Serves as the package index, when
+/// resolving versions.
+pub struct VendorDirectory {
+    index_files: FxHashMap<String, IndexFile>,
+
+    /// Roots of vendored packages by their names and versions.
+    roots: FxHashMap<(String, Version), PathBuf>,
+}
+
+impl VendorDirectory {
+    /// Reads manifests of packages in a given vendor directory.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut vendor = Self {
+            index_files: FxHashMap::default(),
+            roots: FxHashMap::default(),
+        };
+
+        let entries =
+            fs::read_dir(path).map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+
+        for entry in entries {
+            let root = entry.map_err(|err| err.to_string())?.path();
+
+            if !PackagePathResolver::new(&root).manifest().is_file() {
+                continue;
+            }
+
+            let manifest = read_manifest(&root)?;
+            let Some(package) = &manifest.package else {
+                continue;
+            };
+
+            let index_file = vendor
+                .index_files
+                .entry(package.name.clone())
+                .or_insert_with(|| IndexFile {
+                    author: package.author.clone(),
+                    versions: Vec::new(),
+                });
+
+            index_file.add_version(
+                IndexVersion::new(package.version.clone(), "").with_manifest(&manifest),
+            );
+            vendor
+                .roots
+                .insert((package.name.clone(), package.version.clone()), root);
+        }
+
+        Ok(vendor)
+    }
+
+    /// Returns roots of vendored packages with resolved versions by their names,
+    /// after checking, that they match checksums in the lockfile.
+    pub fn verified_roots(
+        &self,
+        resolution: &Resolution,
+        lockfile: Option<&Lockfile>,
+    ) -> Result<FxHashMap<String, PathBuf>, String> {
+        let mut roots = FxHashMap::default();
+
+        for (name, version) in resolution.iter() {
+            let Some(root) = self.roots.get(&(name.to_owned(), version.clone())) else {
+                continue;
+            };
+
+            let locked = lockfile.and_then(|lockfile| {
+                lockfile.packages.iter().find(|package| {
+                    package.name == name
+                        && package.version == *version
+                        && matches!(package.source, Some(PackageSource::Registry(_)))
+                })
+            });
+
+            let Some(checksum) = locked.and_then(|locked| locked.checksum.as_ref()) else {
+                return Err(format!(
+                    "`{name} {version}` is vendored, but not locked, run `stellar vendor` again"
+                ));
+            };
+
+            if package_checksum(root)? != *checksum {
+                return Err(format!(
+                    "checksum of the vendored `{name} {version}` doesn't match package.lock, {} was modified",
+                    root.display()
+                ));
+            }
+
+            roots.insert(name.to_owned(), root.clone());
+        }
+
+        Ok(roots)
+    }
+}
+
+impl PackageIndex for VendorDirectory {
+    fn index_file(&mut self, name: &str) -> Result<Option<IndexFile>, String> {
+        Ok(self.index_files.get(name).cloned())
+    }
+}
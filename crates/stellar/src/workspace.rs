@@ -0,0 +1,177 @@
+//! Discovers workspaces and their member packages.
+
+use std::path::{Path, PathBuf};
+
+use stellar_depgraph::{DependencyKind, PackageKind};
+use stellar_filesystem::path_resolver::PackagePathResolver;
+use stellar_manifest::{TomlManifest, TomlWorkspace};
+
+use crate::{
+    git::is_url,
+    package::{canonicalize, dependency_graph, read_manifest},
+};
+
+/// A workspace, i.e. a group of packages, that share dependencies and the build directory.
+pub struct Workspace {
+    /// The canonical path of the workspace root.
+    pub root: PathBuf,
+
+    /// The `[workspace]` section of the root manifest.
+    pub manifest: TomlWorkspace,
+
+    /// Canonical roots of member packages sorted alphabetically. If the workspace
+    /// root is a package, it's a member as well.
+    pub members: Vec<PathBuf>,
+}
+
+impl Workspace {
+    /// Loads the workspace located in a given directory. Returns `None`, if the manifest
+    /// in the directory doesn't have the `[workspace]` section.
+    pub fn load(root: &Path) -> Result<Option<Self>, String> {
+        let manifest = read_manifest(root)?;
+
+        Self::from_manifest(root, manifest)
+    }
+
+    /// Finds the workspace, that a package located in a given directory is a member of.
+    pub fn find(package_root: &Path) -> Result<Option<Self>, String> {
+        let package_root = canonicalize(package_root)?;
+
+        for root in package_root.ancestors() {
+            if !PackagePathResolver::new(root).manifest().is_file() {
+                continue;
+            }
+
+            if let Some(workspace) = Self::load(root)? {
+                if workspace.members.contains(&package_root) {
+                    return Ok(Some(workspace));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn from_manifest(root: &Path, manifest: TomlManifest) -> Result<Option<Self>, String> {
+        let Some(workspace) = manifest.workspace else {
+            return Ok(None);
+        };
+
+        let root = canonicalize(root)?;
+        let mut members = Vec::new();
+
+        if manifest.package.is_some() {
+            members.push(root.clone());
+        }
+
+        for pattern in &workspace.members {
+            let paths = glob::glob(&root.join(pattern).to_string_lossy())
+                .map_err(|err| format!("invalid workspace member pattern `{pattern}`: {err}"))?;
+
+            for path in paths {
+                let path = path.map_err(|err| err.to_string())?;
+
+                if PackagePathResolver::new(&path).manifest().is_file() {
+                    members.push(canonicalize(&path)?);
+                }
+            }
+        }
+
+        members.sort();
+        members.dedup();
+
+        Ok(Some(Self {
+            root,
+            manifest: workspace,
+            members,
+        }))
+    }
+
+    /// Returns the build directory shared by all members.
+    pub fn build_directory(&self) -> PathBuf {
+        PackagePathResolver::new(&self.root).build_directory()
+    }
+
+    /// Replaces dependencies and dev dependencies with `workspace = true` in the manifest
+    /// of a member located in a given directory with the ones from `[workspace.dependencies]`.
+    ///
+    /// Paths of inherited dependencies (including paths of git repositories) are
+    /// relative to the workspace root, so they are made absolute. The member can still make an inherited dependency optional
+    /// and enable additional features of it.
+    pub fn inherit_dependencies(
+        &self,
+        member_root: &Path,
+        manifest: &mut TomlManifest,
+    ) -> Result<(), String> {
+        for (name, dependency) in manifest
+            .dependencies
+            .iter_mut()
+            .chain(manifest.dev_dependencies.iter_mut())
+            .flatten()
+        {
+            if !dependency.is_inherited_from_workspace() {
+                continue;
+            }
+
+            let Some(inherited) = self
+                .manifest
+                .dependencies
+                .as_ref()
+                .and_then(|dependencies| dependencies.get(name))
+            else {
+                return Err(format!(
+                    "dependency `{name}` in {} is inherited from the workspace, \
+                     but it's not found in `[workspace.dependencies]` of {}",
+                    PackagePathResolver::new(member_root).manifest().display(),
+                    PackagePathResolver::new(&self.root).manifest().display()
+                ));
+            };
+
+            let mut inherited = inherited.clone();
+
+            if let Some(path) = &inherited.path {
+                inherited.path = Some(self.root.join(path).to_string_lossy().into_owned());
+            }
+
+            if let Some(git) = inherited.git.as_ref().filter(|git| !is_url(git)) {
+                inherited.git = Some(self.root.join(git).to_string_lossy().into_owned());
+            }
+
+            inherited.optional = dependency.optional;
+
+            if let Some(features) = dependency.features.take() {
+                inherited
+                    .features
+                    .get_or_insert_with(Vec::new)
+                    .extend(features);
+            }
+
+            *dependency = inherited;
+        }
+
+        Ok(())
+    }
+
+    /// Returns roots of members sorted so that every member comes after
+    /// the members it depends on.
+    pub fn members_in_dependency_order(&self) -> Result<Vec<PathBuf>, String> {
+        let graph = dependency_graph(&self.root, &self.members)?;
+
+        Ok(graph
+            .build_order(|_, dependency| dependency.kind == DependencyKind::Normal)
+            .into_iter()
+            .map(|node| graph.package(node))
+            .filter(|package| package.kind == PackageKind::Root)
+            .map(|package| package.canonical_path.clone())
+            .collect())
+    }
+}
+
+/// Returns the build directory of the package located in a given directory. Members
+/// of a workspace share the build directory located in the workspace root.
+pub fn build_directory(package_root: &Path) -> Result<PathBuf, String> {
+    Ok(match Workspace::find(package_root)? {
+        Some(workspace) => workspace.build_directory(),
+        None => PackagePathResolver::new(package_root).build_directory(),
+    })
+}
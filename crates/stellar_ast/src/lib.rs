@@ -150,6 +150,7 @@
 )]
 
 use std::fmt::Display;
+use std::ops::Range;
 use std::str::FromStr;
 
 use derive_more::Display;
@@ -268,6 +269,72 @@ pub struct ImportPath {
     pub as_: Option<IdentifierAST>,
 }
 
+/// A docstring, e.g. `/// Returns the length of the string.`.
+///
+/// Consists of consecutive doc comments: `///` for items and `//!` for modules.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Docstring {
+    /// Contents of the doc comments without `///` and `//!` prefixes, joined with newlines.
+    pub text: String,
+
+    /// Locations of every line's contents in the source file.
+    pub lines: Vec<Location>,
+}
+
+impl Docstring {
+    /// Creates a new docstring from contents of the doc comments and their locations.
+    #[must_use]
+    pub fn new(lines: Vec<(&str, Location)>) -> Self {
+        let mut text = String::new();
+        let mut locations = vec![];
+
+        for (idx, (line, location)) in lines.into_iter().enumerate() {
+            if idx != 0 {
+                text.push('\n');
+            }
+
+            text.push_str(line);
+            locations.push(location);
+        }
+
+        Self {
+            text,
+            lines: locations,
+        }
+    }
+
+    /// Returns the docstring text.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the location in the source file of a given byte range of the docstring
+    /// text, or `None` if the range is out of bounds or spans over multiple lines.
+    #[must_use]
+    pub fn location_of(&self, range: Range<usize>) -> Option<Location> {
+        let mut line_start = 0;
+
+        for location in &self.lines {
+            let line_end = line_start + (location.end.0 - location.start.0);
+
+            if range.start >= line_start && range.end <= line_end {
+                return Some(Location {
+                    filepath: location.filepath,
+                    start: location.start + (range.start - line_start),
+                    end: location.start + (range.end - line_start),
+                });
+            }
+
+            line_start = line_end + 1;
+        }
+
+        None
+    }
+}
+
 /// A type constructor, e.g. `Option[T]`.
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -518,7 +585,7 @@ pub struct TypeAlias {
     pub value: Type,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A where clause predicate, e.g. `T: ToString`.
@@ -1070,7 +1137,7 @@ pub struct Interface {
     pub inherits: Option<Vec<TypeConstructor>>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// An enum module item.
@@ -1088,7 +1155,7 @@ pub struct Enum {
     pub implements: Option<Vec<TypeConstructor>>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A struct module item.
@@ -1106,7 +1173,7 @@ pub struct Struct {
     pub implements: Option<Vec<TypeConstructor>>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A tuple-like struct module item.
@@ -1124,7 +1191,7 @@ pub struct TupleLikeStruct {
     pub implements: Option<Vec<TypeConstructor>>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A module item.
@@ -1339,7 +1406,7 @@ pub enum EnumItem {
         name: IdentifierAST,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-        docstring: Option<String>,
+        docstring: Option<Docstring>,
     },
     /// A tuple-like enum item, e.g. `None` in `enum Option<T> { Some(T), None }`.
     #[cfg_attr(feature = "serde", serde(rename = "tuple_like_item"))]
@@ -1348,7 +1415,7 @@ pub enum EnumItem {
         fields: Vec<TupleField>,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-        docstring: Option<String>,
+        docstring: Option<Docstring>,
     },
     /// A struct item, e.g. `A { b: T }` in `enum B { A { b: T } }`.
     #[cfg_attr(feature = "serde", serde(rename = "struct_item"))]
@@ -1357,7 +1424,7 @@ pub enum EnumItem {
         fields: Vec<StructField>,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-        docstring: Option<String>,
+        docstring: Option<Docstring>,
     },
 }
 
@@ -1382,7 +1449,7 @@ pub struct StructField {
     pub ty: Type,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A function.
@@ -1410,7 +1477,7 @@ pub struct FunctionSignature {
     pub where_predicates: Vec<WherePredicate>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A function parameter, e.g. `self`, `self: Self`, `a: uint32`.
@@ -1457,7 +1524,7 @@ pub struct Module {
     pub items: Vec<ModuleItem>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A visibility qualifier - `pub` or nothing (private visibility).
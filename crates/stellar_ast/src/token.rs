@@ -94,8 +94,68 @@ pub enum NumberKind {
     Float,
 }
 
+/// A version of the language, that a package is written in. New editions can
+/// reserve new keywords, so identifiers in existing packages are not broken
+/// until they migrate to a new edition.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edition {
+    /// The first edition of the language.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "2023"))]
+    Edition2023,
+
+    /// The 2024 edition, that reserves `mut`, `const` and `async`.
+    #[cfg_attr(feature = "serde", serde(rename = "2024"))]
+    Edition2024,
+}
+
+impl Edition {
+    /// The latest edition.
+    pub const LATEST: Self = Self::Edition2024;
+
+    /// Returns every edition from the oldest to the latest.
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        &[Self::Edition2023, Self::Edition2024]
+    }
+
+    /// Returns the edition, that comes after this one.
+    #[must_use]
+    pub const fn next(self) -> Option<Self> {
+        match self {
+            Self::Edition2023 => Some(Self::Edition2024),
+            Self::Edition2024 => None,
+        }
+    }
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Edition2023 => "2023",
+            Self::Edition2024 => "2024",
+        })
+    }
+}
+
+impl std::str::FromStr for Edition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|edition| edition.to_string() == s)
+            .ok_or_else(|| format!("unknown edition `{s}`"))
+    }
+}
+
 macro_rules! define_keywords {
-    {$($keyword:ident),*} => {
+    {
+        $($keyword:ident),*;
+        $($edition:ident => $($edition_keyword:ident),*);*
+    } => {
         paste! {
             /// This enum represents a set of keywords used in the Stellar programming language.
             /// Each variant of the enum corresponds to a specific keyword.
@@ -105,6 +165,25 @@ macro_rules! define_keywords {
                     #[doc = "Keyword `" $keyword "`."]
                     [<$keyword:camel>],
                 )*
+                $($(
+                    #[doc = "Keyword `" $edition_keyword "` (reserved since [`Edition::" $edition "`])."]
+                    [<$edition_keyword:camel>],
+                )*)*
+            }
+
+            impl Keyword {
+                /// Returns the first edition, in which the keyword is reserved.
+                #[must_use]
+                pub const fn edition(self) -> Edition {
+                    match self {
+                        $(
+                            Self::[<$keyword:camel>] => Edition::Edition2023,
+                        )*
+                        $($(
+                            Self::[<$edition_keyword:camel>] => Edition::$edition,
+                        )*)*
+                    }
+                }
             }
 
             use std::fmt::Display;
@@ -115,18 +194,40 @@ macro_rules! define_keywords {
                         $(
                             Self::[<$keyword:camel>] => write!(f, "`{}`", stringify!($keyword)),
                         )*
+                        $($(
+                            Self::[<$edition_keyword:camel>] => {
+                                write!(f, "`{}`", stringify!($edition_keyword))
+                            }
+                        )*)*
                     }
                 }
             }
 
-            /// Convert a string into a keyword.
-            pub fn resolve_keyword(string: impl AsRef<str>) -> Option<Keyword> {
+            /// Convert a string into a keyword, if it's reserved in a given edition.
+            pub fn resolve_keyword(string: impl AsRef<str>, edition: Edition) -> Option<Keyword> {
                 match string.as_ref() {
                     $(
                         stringify!($keyword) => Some(Keyword::[<$keyword:camel>]),
                     )*
+                    $($(
+                        stringify!($edition_keyword) => Some(Keyword::[<$edition_keyword:camel>]),
+                    )*)*
                     _ => None,
                 }
+                .filter(|keyword| keyword.edition() <= edition)
+            }
+
+            /// Returns the keyword, that a given identifier becomes in the edition
+            /// after a given one, together with that edition.
+            pub fn resolve_next_edition_keyword(
+                string: impl AsRef<str>,
+                edition: Edition,
+            ) -> Option<(Keyword, Edition)> {
+                let next = edition.next()?;
+
+                resolve_keyword(string, next)
+                    .filter(|keyword| keyword.edition() == next)
+                    .map(|keyword| (keyword, next))
             }
         }
     };
@@ -172,7 +273,9 @@ macro_rules! define_punctuators {
 define_keywords! {
     as, defer, else, enum, for, fun, if, pub, return,
     struct, type, let, where, while, match, import, break,
-    continue, dyn, loop, interface, implements
+    continue, dyn, loop, interface, implements;
+
+    Edition2024 => mut, const, async
 }
 
 define_punctuators! {
@@ -3,12 +3,12 @@
 //! AST Lowering is the process of converting AST into HIR.
 //!
 //! It:
-//! * removes parenthesized expressions.
-//! * removes parenthesized types.
-//! * removes grouped patterns.
 //! * converts `loop {}` into `while true {}`.
 //! * converts `interface A[T]: B[T] + C` into `interface A[T] where Self: B[T] + C`.
 //!
+//! Parenthesized expressions and types, as well as grouped patterns, are kept,
+//! so that lints can report unnecessary parentheses after lowering.
+//!
 //! See the [`stellar_hir`] crate for more details.
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
@@ -18,9 +18,8 @@
 #[cfg(feature = "debug")]
 use std::time::Instant;
 
-use diagnostics::{UnnecessaryGroupedPattern, UnnecessaryParenthesizedExpression};
 use stellar_ast::IdentifierAST;
-use stellar_database::{ModuleId, State};
+use stellar_database::ModuleId;
 use stellar_filesystem::location::Location;
 use stellar_fx_hash::FxHashMap;
 use stellar_interner::builtin_identifiers::BIG_SELF;
@@ -28,11 +27,9 @@ use stellar_parser::ParseResult;
 #[cfg(feature = "debug")]
 use tracing::trace;
 
-mod diagnostics;
-
-pub struct LowerToHir<'s> {
-    state: &'s mut State,
-}
+/// Converts AST of modules into HIR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowerToHir;
 
 /// A lowered module.
 #[derive(Debug)]
@@ -85,11 +82,9 @@ impl From<LoweredModule> for stellar_hir::Module {
     }
 }
 
-impl<'s> LowerToHir<'s> {
-    pub fn run_all(
-        state: &'s mut State,
-        modules: Vec<ParseResult>,
-    ) -> FxHashMap<ModuleId, stellar_hir::Module> {
+impl LowerToHir {
+    /// Lowers given parsed modules.
+    pub fn run_all(modules: Vec<ParseResult>) -> FxHashMap<ModuleId, stellar_hir::Module> {
         modules
             .into_iter()
             .map(|module| {
@@ -97,12 +92,12 @@ impl<'s> LowerToHir<'s> {
                 let now = Instant::now();
 
                 let (module, ast) = (module.module(), module.into_ast());
-                let hir = LowerToHir { state }.run(ast);
+                let hir = LowerToHir.run(ast);
 
                 #[cfg(feature = "debug")]
                 trace!(
                     "lower_ast(module = '{}') <{} us>",
-                    module.filepath(state.db()),
+                    hir.filepath,
                     now.elapsed().as_micros()
                 );
 
@@ -356,15 +351,10 @@ impl<'s> LowerToHir<'s> {
 
     fn lower_pattern(&mut self, ast: stellar_ast::Pattern) -> stellar_hir::Pattern {
         match ast {
-            stellar_ast::Pattern::Grouped { inner, .. } => {
-                if let stellar_ast::Pattern::Grouped { location, .. } = *inner {
-                    self.state
-                        .diagnostics_mut()
-                        .add_diagnostic(UnnecessaryGroupedPattern::new(location));
-                }
-
-                self.lower_pattern(*inner)
-            }
+            stellar_ast::Pattern::Grouped { location, inner } => stellar_hir::Pattern::Grouped {
+                location,
+                inner: Box::new(self.lower_pattern(*inner)),
+            },
             stellar_ast::Pattern::NegativeNumericLiteral(literal) => {
                 stellar_hir::Pattern::NegativeNumericLiteral(literal)
             }
@@ -505,22 +495,14 @@ impl<'s> LowerToHir<'s> {
                 location,
                 expression,
                 block,
-            } => {
-                if let stellar_ast::Expression::Parenthesized { location, .. } = *expression {
-                    self.state
-                        .diagnostics_mut()
-                        .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
-                }
-
-                stellar_hir::Expression::Match {
-                    location,
-                    expression: Box::new(self.lower_expression(*expression)),
-                    block: block
-                        .into_iter()
-                        .map(|item| self.lower_match_expression_item(item))
-                        .collect(),
-                }
-            }
+            } => stellar_hir::Expression::Match {
+                location,
+                expression: Box::new(self.lower_expression(*expression)),
+                block: block
+                    .into_iter()
+                    .map(|item| self.lower_match_expression_item(item))
+                    .collect(),
+            },
             stellar_ast::Expression::Struct {
                 location,
                 left,
@@ -537,19 +519,11 @@ impl<'s> LowerToHir<'s> {
                 location,
                 condition,
                 statements_block: body,
-            } => {
-                if let stellar_ast::Expression::Parenthesized { location, .. } = *condition {
-                    self.state
-                        .diagnostics_mut()
-                        .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
-                }
-
-                stellar_hir::Expression::While {
-                    location,
-                    condition: Box::new(self.lower_expression(*condition)),
-                    statements_block: self.lower_statements_block(body),
-                }
-            }
+            } => stellar_hir::Expression::While {
+                location,
+                condition: Box::new(self.lower_expression(*condition)),
+                statements_block: self.lower_statements_block(body),
+            },
             stellar_ast::Expression::Prefix {
                 location,
                 inner,
@@ -577,14 +551,11 @@ impl<'s> LowerToHir<'s> {
                 if_blocks: self.lower_if_blocks(if_blocks),
                 r#else: r#else.map(|else_block| self.lower_statements_block(else_block)),
             },
-            stellar_ast::Expression::Parenthesized { inner, .. } => {
-                if let stellar_ast::Expression::Parenthesized { location, .. } = *inner {
-                    self.state
-                        .diagnostics_mut()
-                        .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
+            stellar_ast::Expression::Parenthesized { location, inner } => {
+                stellar_hir::Expression::Parenthesized {
+                    location,
+                    inner: Box::new(self.lower_expression(*inner)),
                 }
-
-                self.lower_expression(*inner)
             }
             stellar_ast::Expression::Binary {
                 location,
@@ -656,12 +627,6 @@ impl<'s> LowerToHir<'s> {
         &mut self,
         ast: stellar_ast::MatchExpressionItem,
     ) -> stellar_hir::MatchExpressionItem {
-        if let stellar_ast::Expression::Parenthesized { location, .. } = ast.right {
-            self.state
-                .diagnostics_mut()
-                .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
-        }
-
         stellar_hir::MatchExpressionItem {
             left: self.lower_pattern(ast.left),
             right: self.lower_expression(ast.right),
@@ -702,12 +667,6 @@ impl<'s> LowerToHir<'s> {
         &mut self,
         if_block: (stellar_ast::Expression, Vec<stellar_ast::Statement>),
     ) -> (stellar_hir::Expression, Vec<stellar_hir::Statement>) {
-        if let stellar_ast::Expression::Parenthesized { location, .. } = if_block.0 {
-            self.state
-                .diagnostics_mut()
-                .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
-        }
-
         (
             self.lower_expression(if_block.0),
             self.lower_statements_block(if_block.1),
@@ -887,14 +846,11 @@ impl<'s> LowerToHir<'s> {
             stellar_ast::Type::Constructor(constructor) => {
                 stellar_hir::Type::Constructor(self.lower_type_constructor(constructor))
             }
-            stellar_ast::Type::Parenthesized { inner, .. } => {
-                if let stellar_ast::Type::Parenthesized { location, .. } = *inner {
-                    self.state
-                        .diagnostics_mut()
-                        .add_diagnostic(UnnecessaryParenthesizedExpression::new(location));
+            stellar_ast::Type::Parenthesized { location, inner } => {
+                stellar_hir::Type::Parenthesized {
+                    location,
+                    inner: Box::new(self.lower_type(*inner)),
                 }
-
-                self.lower_type(*inner)
             }
             stellar_ast::Type::Underscore { location } => self.lower_underscore_type(location),
             stellar_ast::Type::InterfaceObject { location, bounds } => {
@@ -0,0 +1,204 @@
+//! Configuration of the compiler, that is determined by the build profile.
+//!
+//! See [`Config`] and [`Profile`] for more details.
+
+use std::{fmt, str::FromStr};
+
+/// A build profile, that selects default values of the configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// The profile used by default: the code is not optimized and contains debug
+    /// information and runtime checks.
+    #[default]
+    Dev,
+
+    /// The profile selected with `--release`: the code is optimized and
+    /// doesn't contain debug information, overflow checks and assertions.
+    Release,
+}
+
+impl Profile {
+    /// Returns all build profiles.
+    #[inline]
+    #[must_use]
+    pub const fn all() -> [Self; 2] {
+        [Self::Dev, Self::Release]
+    }
+
+    /// Returns the name of the profile, e.g. `dev`.
+    #[inline]
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Release => "release",
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|profile| profile.name() == s)
+            .ok_or_else(|| format!("unknown profile `{s}`, expected `dev` or `release`"))
+    }
+}
+
+/// Configuration of the compiler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    profile: Profile,
+    optimization_level: u8,
+    debug_info: bool,
+    overflow_checks: bool,
+    strip_assertions: bool,
+    warnings_as_errors: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    /// Returns the default configuration of the `dev` profile.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::for_profile(Profile::Dev)
+    }
+
+    /// Returns the default configuration of a given profile.
+    #[must_use]
+    pub const fn for_profile(profile: Profile) -> Self {
+        match profile {
+            Profile::Dev => Self {
+                profile,
+                optimization_level: 0,
+                debug_info: true,
+                overflow_checks: true,
+                strip_assertions: false,
+                warnings_as_errors: false,
+            },
+            Profile::Release => Self {
+                profile,
+                optimization_level: 3,
+                debug_info: false,
+                overflow_checks: false,
+                strip_assertions: true,
+                warnings_as_errors: false,
+            },
+        }
+    }
+
+    /// Builds a new configuration with a given optimization level, from `0`
+    /// (no optimizations) to `3`.
+    #[inline]
+    #[must_use]
+    pub const fn with_optimization_level(mut self, optimization_level: u8) -> Self {
+        self.optimization_level = optimization_level;
+        self
+    }
+
+    /// Builds a new configuration, that emits or doesn't emit debug information.
+    #[inline]
+    #[must_use]
+    pub const fn with_debug_info(mut self, debug_info: bool) -> Self {
+        self.debug_info = debug_info;
+        self
+    }
+
+    /// Builds a new configuration, that checks or doesn't check integer overflows
+    /// at runtime.
+    #[inline]
+    #[must_use]
+    pub const fn with_overflow_checks(mut self, overflow_checks: bool) -> Self {
+        self.overflow_checks = overflow_checks;
+        self
+    }
+
+    /// Builds a new configuration, that removes or keeps assertions.
+    #[inline]
+    #[must_use]
+    pub const fn with_strip_assertions(mut self, strip_assertions: bool) -> Self {
+        self.strip_assertions = strip_assertions;
+        self
+    }
+
+    /// Builds a new configuration, that treats or doesn't treat warnings as errors.
+    #[inline]
+    #[must_use]
+    pub const fn with_warnings_as_errors(mut self, warnings_as_errors: bool) -> Self {
+        self.warnings_as_errors = warnings_as_errors;
+        self
+    }
+
+    /// Returns the build profile.
+    #[inline]
+    #[must_use]
+    pub const fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Returns the optimization level.
+    #[inline]
+    #[must_use]
+    pub const fn optimization_level(&self) -> u8 {
+        self.optimization_level
+    }
+
+    /// Returns `true` if debug information is emitted.
+    #[inline]
+    #[must_use]
+    pub const fn debug_info(&self) -> bool {
+        self.debug_info
+    }
+
+    /// Returns `true` if integer overflows are checked at runtime.
+    #[inline]
+    #[must_use]
+    pub const fn overflow_checks(&self) -> bool {
+        self.overflow_checks
+    }
+
+    /// Returns `true` if assertions are removed.
+    #[inline]
+    #[must_use]
+    pub const fn strip_assertions(&self) -> bool {
+        self.strip_assertions
+    }
+
+    /// Returns `true` if warnings are treated as errors.
+    #[inline]
+    #[must_use]
+    pub const fn warnings_as_errors(&self) -> bool {
+        self.warnings_as_errors
+    }
+
+    /// Returns a short description of the configuration, e.g.
+    /// `unoptimized + debuginfo`.
+    #[must_use]
+    pub fn description(&self) -> String {
+        let mut description = if self.optimization_level == 0 {
+            "unoptimized".to_owned()
+        } else {
+            "optimized".to_owned()
+        };
+
+        if self.debug_info {
+            description.push_str(" + debuginfo");
+        }
+
+        description
+    }
+}
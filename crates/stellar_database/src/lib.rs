@@ -3,7 +3,11 @@
     html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
 )]
 
-use std::{iter, ops::Add};
+use std::{
+    fmt::{self, Display},
+    iter,
+    ops::Add,
+};
 
 use filetime::FileTime;
 use paste::paste;
@@ -11,15 +15,17 @@ use paste::paste;
 use serde::{Deserialize, Serialize};
 use stellar_ast::{IdentifierAST, ModuleItemKind, Visibility};
 use stellar_diagnostics::Diagnostics;
-use stellar_filesystem::location::{Location, DUMMY_LOCATION};
+use stellar_filesystem::location::{ByteOffset, Location, DUMMY_LOCATION};
 use stellar_fx_hash::FxHashMap;
 use stellar_interner::{IdentifierId, PathId};
 
+pub mod config;
 #[macro_use]
 mod id_type;
 pub mod symbol;
 pub mod ty;
 
+pub use config::{Config, Profile};
 pub use symbol::Symbol;
 use ty::{Type, TypeConstructor};
 
@@ -48,6 +54,20 @@ impl Path {
     }
 }
 
+impl Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, segment) in self.segments.iter().enumerate() {
+            if idx != 0 {
+                f.write_str(".")?;
+            }
+
+            write!(f, "{segment}")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl From<IdentifierId> for Path {
     fn from(id: IdentifierId) -> Self {
         Self::new(vec![id])
@@ -92,7 +112,7 @@ impl Add<IdentifierAST> for Path {
 }
 
 /// A data that Stellar compiler has about an enum.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EnumData {
     pub signature: SignatureId,
@@ -156,7 +176,7 @@ impl EnumId {
 }
 
 /// A data that Stellar compiler has about a struct.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StructData {
     pub signature: SignatureId,
@@ -201,7 +221,7 @@ impl StructId {
 }
 
 /// A data that Stellar compiler has about a function.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TupleLikeStructData {
     pub signature: SignatureId,
@@ -237,7 +257,7 @@ impl TupleLikeStructId {
 }
 
 /// A data that Stellar compiler has about a field.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FieldData {
     pub visibility: Visibility,
@@ -272,7 +292,7 @@ impl FieldData {
 }
 
 /// A data that Stellar compiler has about a predicate.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PredicateData {
     pub ty: Type,
@@ -457,7 +477,7 @@ impl GenericParameterData {
 }
 
 /// A data that Stellar compiler has about an enum item.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EnumItemData {
     pub enum_: EnumId,
@@ -512,7 +532,7 @@ impl EnumItemId {
 }
 
 /// A data that Stellar compiler has about a particular type signature.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SignatureData {
     pub visibility: Visibility,
@@ -618,6 +638,12 @@ impl SignatureId {
         self.get_data_mut(db).predicates.push(predicate);
     }
 
+    #[inline]
+    #[must_use]
+    pub fn implements(self, db: &Database) -> &[TypeConstructor] {
+        &self.get_data(db).implements
+    }
+
     #[inline]
     pub fn add_implemented_interface(self, db: &mut Database, interface: TypeConstructor) {
         self.get_data_mut(db).implements.push(interface);
@@ -640,7 +666,7 @@ impl SignatureId {
 }
 
 /// A data that Stellar compiler has about a function.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FunctionData {
     pub signature: SignatureId,
@@ -672,7 +698,7 @@ impl FunctionId {
 }
 
 /// A data that Stellar compiler has about an interface.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InterfaceData {
     pub signature: SignatureId,
@@ -708,7 +734,7 @@ impl InterfaceId {
 }
 
 /// A data that Stellar compiler has about a module.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TypeAliasData {
     pub signature: SignatureId,
@@ -754,8 +780,20 @@ impl TypeAliasId {
     }
 }
 
+/// A resolved intra-doc link, e.g. [`Option`] in a docstring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DocLink {
+    /// Location of the link's path inside the doc comment.
+    pub location: Location,
+    /// The path as written in the docstring, e.g. `std.option.Option`.
+    pub path: String,
+    /// The symbol the link refers to.
+    pub target: Symbol,
+}
+
 /// A data that Stellar compiler has about a module.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ModuleData {
     pub path: Path,
@@ -763,6 +801,22 @@ pub struct ModuleData {
     pub module_item_symbols: FxHashMap<IdentifierId, Symbol>,
     pub submodules: FxHashMap<IdentifierId, ModuleId>,
     pub resolved_imports: FxHashMap<IdentifierId, Symbol>,
+    pub doc_links: Vec<DocLink>,
+    pub import_insertion_point: ImportInsertionPoint,
+}
+
+/// The place in a module, that new imports are inserted at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImportInsertionPoint {
+    /// The start of the file, if the module has neither imports nor a docstring.
+    #[default]
+    Start,
+
+    /// The end of the module docstring, if the module has no imports.
+    AfterDocstring(ByteOffset),
+
+    /// The end of the last import.
+    AfterImport(ByteOffset),
 }
 
 impl ModuleData {
@@ -783,6 +837,8 @@ impl ModuleData {
             submodules: FxHashMap::default(),
             resolved_imports: FxHashMap::default(),
             module_item_symbols: FxHashMap::default(),
+            doc_links: vec![],
+            import_insertion_point: ImportInsertionPoint::Start,
         }
     }
 }
@@ -923,6 +979,42 @@ impl ModuleId {
     pub fn add_resolved_import(self, db: &mut Database, name: IdentifierId, symbol: Symbol) {
         self.get_data_mut(db).resolved_imports.insert(name, symbol);
     }
+
+    /// Returns intra-doc links resolved in the module's docstrings.
+    #[inline]
+    #[must_use]
+    pub fn doc_links(self, db: &Database) -> &[DocLink] {
+        &self.get_data(db).doc_links
+    }
+
+    /// Returns the intra-doc link, that contains a given location, if it exists.
+    #[must_use]
+    pub fn doc_link_at(self, db: &Database, location: Location) -> Option<&DocLink> {
+        self.doc_links(db).iter().find(|link| {
+            link.location.filepath == location.filepath
+                && link.location.start <= location.start
+                && location.end <= link.location.end
+        })
+    }
+
+    /// Adds a resolved intra-doc link to the module.
+    #[inline]
+    pub fn add_doc_link(self, db: &mut Database, link: DocLink) {
+        self.get_data_mut(db).doc_links.push(link);
+    }
+
+    /// Returns the place in the module, that new imports are inserted at.
+    #[inline]
+    #[must_use]
+    pub fn import_insertion_point(self, db: &Database) -> ImportInsertionPoint {
+        self.get_data(db).import_insertion_point
+    }
+
+    /// Sets the place in the module, that new imports are inserted at.
+    #[inline]
+    pub fn set_import_insertion_point(self, db: &mut Database, point: ImportInsertionPoint) {
+        self.get_data_mut(db).import_insertion_point = point;
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -952,6 +1044,12 @@ impl PackageId {
             .and_then(|package| package.parent)
     }
 
+    /// Sets the package, that the package is a local dependency of.
+    #[inline]
+    pub fn set_parent(self, db: &mut Database, parent: PackageId) {
+        db.packages[self.0 - 1].parent = Some(parent);
+    }
+
     #[inline]
     #[must_use]
     pub fn dependencies(self, db: &Database) -> &FxHashMap<IdentifierId, PackageId> {
@@ -969,6 +1067,14 @@ impl PackageId {
             .map(|package| &package.dependencies)
     }
 
+    /// Adds a dependency to the package.
+    #[inline]
+    pub fn add_dependency(self, db: &mut Database, name: IdentifierId, dependency: PackageId) {
+        db.packages[self.0 - 1]
+            .dependencies
+            .insert(name, dependency);
+    }
+
     #[inline]
     #[must_use]
     pub fn root_module(self, db: &Database) -> ModuleId {
@@ -986,10 +1092,16 @@ impl PackageId {
     pub fn set_root_module(self, db: &mut Database, module: ModuleId) {
         db.packages[self.0 - 1].root_module = module;
     }
+
+    /// Returns an iterator over all modules of the package.
+    #[inline]
+    pub fn modules(self, db: &Database) -> impl Iterator<Item = ModuleId> {
+        (1..=db.packages[self.0 - 1].module_.len()).map(move |idx| ModuleId(self, idx))
+    }
 }
 
 /// The information Stellar compiler has about a particular package.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PackageData {
     #[allow(dead_code)]
@@ -1114,7 +1226,7 @@ impl From<Vec<u8>> for PackageData {
 }
 
 /// Storage for Stellar compiler entities.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Database {
     packages: Vec<PackageData>,
 }
@@ -1148,6 +1260,12 @@ impl Database {
         &mut self.packages[id.0 - 1]
     }
 
+    /// Returns an iterator over IDs of all packages in the database.
+    #[inline]
+    pub fn packages(&self) -> impl Iterator<Item = PackageId> {
+        (1..=self.packages.len()).map(PackageId)
+    }
+
     /// Returns a mutable reference to package data by its ID.
     #[inline]
     #[must_use]
@@ -1157,24 +1275,13 @@ impl Database {
 }
 
 /// Contains database and diagnostics.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct State {
     db: Database,
     diagnostics: Diagnostics,
     config: Config,
 }
 
-#[derive(Default)]
-pub struct Config {}
-
-impl Config {
-    #[inline]
-    #[must_use]
-    pub fn new() -> Self {
-        Self::default()
-    }
-}
-
 impl State {
     /// Creates a new empty state.
     #[inline]
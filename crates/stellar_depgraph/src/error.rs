@@ -0,0 +1,131 @@
+//! Errors, that can occur when building the dependency graph.
+
+use std::{fmt, fs};
+
+use petgraph::graph::EdgeIndex;
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Label},
+    BuildDiagnostic,
+};
+use stellar_filesystem::{location::Location, path_resolver::PackagePathResolver};
+use stellar_interner::PathId;
+use stellar_manifest::dependency_location;
+
+use crate::DependencyGraph;
+
+/// An error, that occurred when building the dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyGraphError {
+    /// A package cannot be loaded, e.g. its manifest is not valid.
+    Package(String),
+
+    /// Packages depend on each other.
+    Cycle(DependencyCycleError),
+}
+
+impl fmt::Display for DependencyGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Package(message) => f.write_str(message),
+            Self::Cycle(cycle) => cycle.fmt(f),
+        }
+    }
+}
+
+/// Diagnostic related to packages, that depend on each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCycleError {
+    /// Names of packages in the cycle. The first package is repeated at the end,
+    /// e.g. `["a", "b", "c", "a"]`.
+    traceback: Vec<String>,
+
+    /// Locations of dependency entries in manifests, one for every step of
+    /// the traceback.
+    locations: Vec<Location>,
+}
+
+impl DependencyCycleError {
+    pub(crate) fn new(graph: &DependencyGraph, cycle: &[EdgeIndex]) -> Self {
+        let mut traceback = Vec::new();
+        let mut locations = Vec::new();
+
+        for edge in cycle {
+            let Some((source, _)) = graph.graph.edge_endpoints(*edge) else {
+                continue;
+            };
+
+            let package = &graph.graph[source];
+            let dependency = &graph.graph[*edge];
+            let manifest_path = PackagePathResolver::new(&package.path).manifest();
+
+            traceback.push(package.name.clone());
+            locations.push(dependency_location(
+                PathId::from(&manifest_path),
+                &fs::read_to_string(&manifest_path).unwrap_or_default(),
+                dependency.kind.table(),
+                &dependency.name,
+            ));
+        }
+
+        if let Some(first) = traceback.first() {
+            traceback.push(first.clone());
+        }
+
+        Self {
+            traceback,
+            locations,
+        }
+    }
+
+    /// Returns names of packages in the cycle. The first package is repeated at
+    /// the end, e.g. `["a", "b", "c", "a"]`.
+    #[inline]
+    #[must_use]
+    pub fn traceback(&self) -> &[String] {
+        &self.traceback
+    }
+
+    /// Returns locations of dependency entries, that form the cycle.
+    #[inline]
+    #[must_use]
+    pub fn locations(&self) -> &[Location] {
+        &self.locations
+    }
+}
+
+impl fmt::Display for DependencyCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cyclic package dependency: {}",
+            self.traceback.join(" -> ")
+        )
+    }
+}
+
+impl BuildDiagnostic for DependencyCycleError {
+    fn build(self) -> Diagnostic {
+        let labels = self
+            .locations
+            .iter()
+            .zip(self.traceback.windows(2))
+            .enumerate()
+            .map(|(idx, (location, step))| {
+                let label = if idx == 0 {
+                    Label::primary(*location)
+                } else {
+                    Label::secondary(*location)
+                };
+
+                label.with_message(format!("`{}` depends on `{}` here", step[0], step[1]))
+            });
+
+        Diagnostic::error()
+            .with_code("E014")
+            .with_message(self.to_string())
+            .with_labels(labels)
+            .with_notes([
+                "note: packages cannot depend on each other, move shared code into a separate package",
+            ])
+    }
+}
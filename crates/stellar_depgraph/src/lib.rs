@@ -1,7 +1,554 @@
-#![allow(warnings)]
+//! # Dependency graph
+//!
+//! Builds the graph of packages, that a package (or members of a workspace)
+//! depends on, by following `path` dependencies and dependencies, that come from
+//! a package registry or a git repository:
+//!
+//! ```txt
+//! server 0.1.0
+//! |_ http 1.0.0 (registry)
+//! |  |_ url 0.2.0 (path dependency of `http`)
+//! |_ json 0.1.0 (path dependency of `server`)
+//! ```
+//!
+//! Dependency cycles are not allowed and are reported with the full traceback,
+//! e.g. `a -> b -> c -> a`, pointing to the dependency entries in manifests.
+//!
+//! The graph is used to compute the order, in which packages are built (every
+//! package comes after its dependencies), and to allocate packages in the
+//! database together with their dependencies and parents.
+//!
+//! Reading manifests and fetching registry and git packages is delegated to
+//! [`PackageProvider`], so that the graph doesn't depend on how packages are
+//! stored. The graph can be printed with [`tree::TreePrinter`].
 
-use stellar_manifest::{TomlManifest, TomlPackage};
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
+    html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
+)]
+#![cfg_attr(not(test), forbid(clippy::unwrap_used))]
+#![warn(clippy::dbg_macro)]
+#![warn(
+    // rustc lint groups https://doc.rust-lang.org/rustc/lints/groups.html
+    future_incompatible,
+    let_underscore,
+    nonstandard_style,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    rust_2021_compatibility,
+    unused,
+    // rustc allowed-by-default lints https://doc.rust-lang.org/rustc/lints/listing/allowed-by-default.html
+    macro_use_extern_crate,
+    meta_variable_misuse,
+    missing_abi,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unsafe_op_in_unsafe_fn,
+    unused_crate_dependencies,
+    unused_import_braces,
+    unused_lifetimes,
+    unused_qualifications,
+    variant_size_differences,
+    // rustdoc lints https://doc.rust-lang.org/rustdoc/lints.html
+    rustdoc::broken_intra_doc_links,
+    rustdoc::private_intra_doc_links,
+    rustdoc::missing_crate_level_docs,
+    rustdoc::private_doc_tests,
+    rustdoc::invalid_codeblock_attributes,
+    rustdoc::invalid_rust_codeblocks,
+    rustdoc::bare_urls,
+    // clippy categories https://doc.rust-lang.org/clippy/
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::too_many_lines,
+    clippy::option_if_let_else,
+    clippy::redundant_pub_crate,
+    clippy::unnested_or_patterns
+)]
 
-pub struct DependencyCycleError {
-    traceback: Vec<TomlPackage>,
+mod error;
+pub mod resolver;
+pub mod tree;
+
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+pub use error::{DependencyCycleError, DependencyGraphError};
+pub use petgraph::graph::NodeIndex;
+use petgraph::{
+    graph::{DiGraph, EdgeIndex},
+    visit::{DfsPostOrder, EdgeFiltered, EdgeRef},
+    Direction,
+};
+use stellar_database::{Database, PackageData, PackageId};
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_interner::{IdentifierId, PathId};
+use stellar_manifest::{
+    lockfile::GitSource, DependencyTable, TomlDependency, TomlManifest, Version,
+};
+
+/// Reads manifests and fetches packages, that the dependency graph consists of.
+pub trait PackageProvider {
+    /// Reads the manifest of the package located in a given directory.
+    ///
+    /// # Errors
+    /// Returns an error if the manifest cannot be read or is not valid.
+    fn load_manifest(&mut self, root: &Path) -> Result<TomlManifest, String>;
+
+    /// Returns the directory of a registry package, that satisfies a given
+    /// dependency, or `None` if the dependency must be skipped.
+    ///
+    /// # Errors
+    /// Returns an error if there is no such package or it cannot be fetched.
+    fn fetch_registry_package(
+        &mut self,
+        name: &str,
+        dependency: &TomlDependency,
+    ) -> Result<Option<PathBuf>, String>;
+
+    /// Checks out the git repository of a given dependency and returns the directory
+    /// of the package inside of the checkout together with the checked out commit.
+    /// `dependent` is the directory of the package, that has the dependency, and
+    /// is used to resolve relative repository paths.
+    ///
+    /// # Errors
+    /// Returns an error if the repository cannot be fetched or doesn't contain
+    /// the package.
+    fn fetch_git_package(
+        &mut self,
+        name: &str,
+        dependency: &TomlDependency,
+        dependent: &Path,
+    ) -> Result<(PathBuf, GitSource), String>;
+}
+
+/// Where a package in the dependency graph comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackageKind {
+    /// The package is being built, e.g. a workspace member.
+    Root,
+    /// The package is a `path` dependency of another package.
+    Path,
+    /// The package comes from a package registry.
+    Registry,
+    /// The package comes from a git repository.
+    Git,
+}
+
+/// A package in the dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageNode {
+    /// The name of the package.
+    pub name: String,
+
+    /// The version of the package.
+    pub version: Version,
+
+    /// The path of the package directory as it was first reached, e.g. `./../json`.
+    pub path: PathBuf,
+
+    /// The canonical path of the package directory.
+    pub canonical_path: PathBuf,
+
+    /// The manifest of the package.
+    pub manifest: TomlManifest,
+
+    /// Where the package comes from.
+    pub kind: PackageKind,
+
+    /// For a `path` dependency, the closest root, registry or git package, that
+    /// (transitively) depends on it. See [`PackageId::parent`].
+    pub parent: Option<NodeIndex>,
+
+    /// For a git package, the repository and the checked out commit.
+    pub git: Option<GitSource>,
+}
+
+/// A kind of a dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// A dependency from the `[dependencies]` table.
+    Normal,
+    /// A dependency from the `[dev-dependencies]` table.
+    Dev,
+}
+
+impl DependencyKind {
+    /// Returns the manifest table, that contains dependencies of the kind.
+    #[inline]
+    #[must_use]
+    pub const fn table(self) -> DependencyTable {
+        match self {
+            Self::Normal => DependencyTable::Dependencies,
+            Self::Dev => DependencyTable::DevDependencies,
+        }
+    }
+}
+
+/// An edge of the dependency graph, that goes from the dependent package to
+/// the dependency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    /// The name of the dependency in the manifest of the dependent package.
+    pub name: String,
+
+    /// The kind of the dependency.
+    pub kind: DependencyKind,
+
+    /// The dependency entry in the manifest of the dependent package.
+    pub manifest: TomlDependency,
+}
+
+/// The graph of packages and dependencies between them.
+///
+/// See [crate level documentation] for more information.
+///
+/// [crate level documentation]: crate
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    graph: DiGraph<PackageNode, Dependency>,
+    roots: Vec<NodeIndex>,
+}
+
+impl DependencyGraph {
+    /// Builds the dependency graph of packages located in given directories.
+    ///
+    /// Every dependency is followed, including optional ones, so that the graph
+    /// doesn't depend on enabled features. Dev dependencies are only followed for
+    /// the root packages.
+    ///
+    /// # Errors
+    /// Returns an error if a package cannot be loaded or if there is a dependency
+    /// cycle.
+    pub fn build(
+        roots: &[PathBuf],
+        provider: &mut impl PackageProvider,
+    ) -> Result<Self, DependencyGraphError> {
+        let mut graph = Self {
+            graph: DiGraph::new(),
+            roots: Vec::new(),
+        };
+        let mut nodes = FxHashMap::default();
+        let mut queue = VecDeque::new();
+
+        for root in roots {
+            let node = graph.add_package(&mut nodes, provider, root, PackageKind::Root)?;
+
+            if !graph.roots.contains(&node) {
+                graph.roots.push(node);
+                queue.push_back(node);
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let package = &graph.graph[node];
+            let dev_dependencies = if package.kind == PackageKind::Root {
+                package.manifest.dev_dependencies.iter().flatten().collect()
+            } else {
+                Vec::new()
+            };
+            let dependencies = package
+                .manifest
+                .dependencies
+                .iter()
+                .flatten()
+                .map(|(name, dependency)| (DependencyKind::Normal, name, dependency))
+                .chain(
+                    dev_dependencies
+                        .into_iter()
+                        .map(|(name, dependency)| (DependencyKind::Dev, name, dependency)),
+                )
+                .map(|(kind, name, dependency)| Dependency {
+                    name: name.clone(),
+                    kind,
+                    manifest: dependency.clone(),
+                })
+                .collect::<Vec<_>>();
+            let package_path = package.path.clone();
+            let package_kind = package.kind;
+
+            for dependency in dependencies {
+                let (path, kind, git) = if let Some(path) = &dependency.manifest.path {
+                    (package_path.join(path), PackageKind::Path, None)
+                } else if dependency.manifest.git.is_some() {
+                    let (path, source) = provider
+                        .fetch_git_package(&dependency.name, &dependency.manifest, &package_path)
+                        .map_err(DependencyGraphError::Package)?;
+
+                    (path, PackageKind::Git, Some(source))
+                } else if let Some(path) = provider
+                    .fetch_registry_package(&dependency.name, &dependency.manifest)
+                    .map_err(DependencyGraphError::Package)?
+                {
+                    (path, PackageKind::Registry, None)
+                } else {
+                    continue;
+                };
+
+                let count = graph.graph.node_count();
+                let target = graph.add_package(&mut nodes, provider, &path, kind)?;
+
+                if graph.graph.node_count() > count {
+                    graph.graph[target].git = git;
+                    queue.push_back(target);
+                }
+
+                if kind == PackageKind::Path && graph.graph[target].parent.is_none() {
+                    graph.graph[target].parent = match package_kind {
+                        PackageKind::Root | PackageKind::Registry | PackageKind::Git => Some(node),
+                        PackageKind::Path => graph.graph[node].parent,
+                    };
+                }
+
+                graph.graph.add_edge(node, target, dependency);
+            }
+        }
+
+        if let Some(cycle) = graph.find_cycle() {
+            return Err(DependencyGraphError::Cycle(DependencyCycleError::new(
+                &graph, &cycle,
+            )));
+        }
+
+        Ok(graph)
+    }
+
+    /// Returns the node of the package located in a given directory, adding it
+    /// to the graph if it's not there yet.
+    fn add_package(
+        &mut self,
+        nodes: &mut FxHashMap<PathBuf, NodeIndex>,
+        provider: &mut impl PackageProvider,
+        path: &Path,
+        kind: PackageKind,
+    ) -> Result<NodeIndex, DependencyGraphError> {
+        let canonical_path = path.canonicalize().map_err(|err| {
+            DependencyGraphError::Package(format!("cannot find {}: {err}", path.display()))
+        })?;
+
+        if let Some(node) = nodes.get(&canonical_path) {
+            return Ok(*node);
+        }
+
+        let manifest = provider
+            .load_manifest(path)
+            .map_err(DependencyGraphError::Package)?;
+        let Some(package) = &manifest.package else {
+            return Err(DependencyGraphError::Package(format!(
+                "{} describes a workspace, not a package",
+                path.display()
+            )));
+        };
+
+        let node = self.graph.add_node(PackageNode {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            path: path.to_owned(),
+            canonical_path: canonical_path.clone(),
+            manifest,
+            kind,
+            parent: None,
+            git: None,
+        });
+
+        nodes.insert(canonical_path, node);
+
+        Ok(node)
+    }
+
+    /// Returns edges forming a dependency cycle, if there is one.
+    fn find_cycle(&self) -> Option<Vec<EdgeIndex>> {
+        let mut finished = FxHashSet::default();
+
+        self.roots.iter().find_map(|root| {
+            self.find_cycle_from(*root, &mut finished, &mut vec![*root], &mut Vec::new())
+        })
+    }
+
+    /// Looks for a dependency cycle with a depth-first search. `trace` contains
+    /// nodes on the path from a root to the current node, and `edges` contains
+    /// edges between them.
+    fn find_cycle_from(
+        &self,
+        node: NodeIndex,
+        finished: &mut FxHashSet<NodeIndex>,
+        trace: &mut Vec<NodeIndex>,
+        edges: &mut Vec<EdgeIndex>,
+    ) -> Option<Vec<EdgeIndex>> {
+        for (edge, target) in self.dependency_edges(node) {
+            if finished.contains(&target) {
+                continue;
+            }
+
+            if let Some(start) = trace.iter().position(|visited| *visited == target) {
+                let mut cycle = edges[start..].to_vec();
+                cycle.push(edge);
+
+                return Some(cycle);
+            }
+
+            trace.push(target);
+            edges.push(edge);
+
+            if let Some(cycle) = self.find_cycle_from(target, finished, trace, edges) {
+                return Some(cycle);
+            }
+
+            trace.pop();
+            edges.pop();
+        }
+
+        finished.insert(node);
+
+        None
+    }
+
+    /// Returns outgoing edges of a given node in the order, in which they were added.
+    fn dependency_edges(&self, node: NodeIndex) -> Vec<(EdgeIndex, NodeIndex)> {
+        let mut edges = self
+            .graph
+            .edges(node)
+            .map(|edge| (edge.id(), edge.target()))
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Returns nodes of the root packages.
+    #[inline]
+    #[must_use]
+    pub fn roots(&self) -> &[NodeIndex] {
+        &self.roots
+    }
+
+    /// Returns a package by its node.
+    #[inline]
+    #[must_use]
+    pub fn package(&self, node: NodeIndex) -> &PackageNode {
+        &self.graph[node]
+    }
+
+    /// Returns an iterator over nodes of all packages in the graph.
+    #[inline]
+    pub fn packages(&self) -> impl Iterator<Item = NodeIndex> {
+        self.graph.node_indices()
+    }
+
+    /// Returns the node of the package located in a given directory.
+    #[must_use]
+    pub fn find(&self, path: &Path) -> Option<NodeIndex> {
+        let canonical_path = path.canonicalize().ok()?;
+
+        self.graph
+            .node_indices()
+            .find(|node| self.graph[*node].canonical_path == canonical_path)
+    }
+
+    /// Returns dependencies of a given package together with their nodes: normal
+    /// dependencies sorted by name, followed by development dependencies sorted by name.
+    #[must_use]
+    pub fn dependencies(&self, node: NodeIndex) -> Vec<(&Dependency, NodeIndex)> {
+        self.dependency_edges(node)
+            .into_iter()
+            .map(|(edge, target)| (&self.graph[edge], target))
+            .collect()
+    }
+
+    /// Returns packages, that depend on a given package, together with the
+    /// dependencies and nodes of the dependents, sorted by dependent names.
+    #[must_use]
+    pub fn dependents(&self, node: NodeIndex) -> Vec<(&Dependency, NodeIndex)> {
+        let mut dependents = self
+            .graph
+            .edges_directed(node, Direction::Incoming)
+            .map(|edge| (edge.weight(), edge.source()))
+            .collect::<Vec<_>>();
+        dependents.sort_by(|(_, a), (_, b)| self.graph[*a].name.cmp(&self.graph[*b].name));
+        dependents
+    }
+
+    /// Returns packages reachable from the roots through dependencies accepted by
+    /// a given filter, sorted so that every package comes after its dependencies.
+    ///
+    /// The filter receives the dependent package and the dependency, e.g. to skip
+    /// optional dependencies, that are not enabled.
+    pub fn build_order(&self, filter: impl Fn(NodeIndex, &Dependency) -> bool) -> Vec<NodeIndex> {
+        let graph = EdgeFiltered::from_fn(&self.graph, |edge| filter(edge.source(), edge.weight()));
+        let mut dfs = DfsPostOrder::empty(&graph);
+        let mut order = Vec::new();
+
+        for root in &self.roots {
+            dfs.move_to(*root);
+
+            while let Some(node) = dfs.next(&graph) {
+                order.push(node);
+            }
+        }
+
+        order
+    }
+
+    /// Allocates packages returned by [`DependencyGraph::build_order`] in the
+    /// database and records their dependencies and parents. Returns the packages
+    /// in the build order.
+    pub fn alloc_packages(
+        &self,
+        db: &mut Database,
+        filter: impl Fn(NodeIndex, &Dependency) -> bool,
+    ) -> Vec<(NodeIndex, PackageId)> {
+        let order = self.build_order(&filter);
+        let mut packages = FxHashMap::default();
+
+        for node in &order {
+            let package = &self.graph[*node];
+            let id = PackageData::alloc(
+                db,
+                IdentifierId::from(package.name.as_str()),
+                PathId::from(&package.path),
+            );
+
+            for (dependency, target) in self.dependencies(*node) {
+                if filter(*node, dependency) {
+                    id.add_dependency(
+                        db,
+                        IdentifierId::from(dependency.name.as_str()),
+                        packages[&target],
+                    );
+                }
+            }
+
+            packages.insert(*node, id);
+        }
+
+        for node in &order {
+            if let Some(parent) = self.graph[*node]
+                .parent
+                .and_then(|parent| packages.get(&parent))
+            {
+                packages[node].set_parent(db, *parent);
+            }
+        }
+
+        order
+            .into_iter()
+            .map(|node| (node, packages[&node]))
+            .collect()
+    }
 }
@@ -0,0 +1,865 @@
+//! Picks versions of registry packages, that satisfy requirements of the whole
+//! dependency graph.
+//!
+//! The solver is based on [PubGrub]. Every fact it learns is recorded as an
+//! incompatibility, i.e. a set of conditions, that cannot be true at the same
+//! time (e.g. "`http 1.0.0` is selected, but `json ^0.2` is not"). When a conflict
+//! is found, the solver derives a new incompatibility, that explains it, and jumps
+//! back to the decision, that caused it, so the same dead end is never tried twice.
+//!
+//! When there is no solution, the chain of derivations is reported as the reason:
+//!
+//! ```txt
+//! Because every version of http depends on json ^0.2 and app depends on http ^1.0, app depends on json ^0.2.
+//! Because app depends on json ^0.2 and app depends on json ^0.1, version solving failed.
+//! ```
+//!
+//! Every package name can only be resolved to a single version. Yanked versions
+//! are only used, if they are preferred, e.g. because they are locked.
+//!
+//! [PubGrub]: https://github.com/dart-lang/pub/blob/master/doc/solver.md
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use stellar_fx_hash::{FxHashMap, FxHashSet};
+use stellar_manifest::{registry::IndexFile, Version, VersionReq};
+
+/// Provides index files of registry packages to the solver.
+pub trait PackageIndex {
+    /// Returns the index file of a package with a given name, or `None` if there
+    /// is no such package in the registry.
+    ///
+    /// # Errors
+    /// Returns an error if the index file cannot be read or is not valid.
+    fn index_file(&mut self, name: &str) -> Result<Option<IndexFile>, String>;
+}
+
+/// A version requirement of a registry dependency of a package, that is not
+/// a registry package itself, e.g. of a workspace member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    /// The name of the dependent package.
+    pub dependent: String,
+
+    /// The name of the dependency.
+    pub name: String,
+
+    /// The version requirement of the dependency.
+    pub version: VersionReq,
+}
+
+impl Requirement {
+    /// Returns a new requirement.
+    #[inline]
+    #[must_use]
+    pub fn new(dependent: impl Into<String>, name: impl Into<String>, version: VersionReq) -> Self {
+        Self {
+            dependent: dependent.into(),
+            name: name.into(),
+            version,
+        }
+    }
+}
+
+/// Versions picked by the solver.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Resolution {
+    versions: BTreeMap<String, Version>,
+}
+
+impl Resolution {
+    /// Returns the version picked for a package with a given name.
+    #[inline]
+    #[must_use]
+    pub fn version(&self, name: &str) -> Option<&Version> {
+        self.versions.get(name)
+    }
+
+    /// Returns an iterator over names and versions of picked packages sorted
+    /// by name.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Version)> {
+        self.versions
+            .iter()
+            .map(|(name, version)| (name.as_str(), version))
+    }
+}
+
+/// An error, that occurred when resolving versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionError {
+    /// An index file cannot be read.
+    Index(String),
+
+    /// There are no versions, that satisfy all requirements. Contains
+    /// the explanation of the conflict.
+    Conflict(String),
+}
+
+impl fmt::Display for ResolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(message) => f.write_str(message),
+            Self::Conflict(explanation) => {
+                write!(
+                    f,
+                    "failed to select versions of dependencies:\n{explanation}"
+                )
+            }
+        }
+    }
+}
+
+/// Picks versions of registry packages, that satisfy given requirements and
+/// dependencies of the picked versions themselves.
+///
+/// `root` is the name used for packages, that requirements come from, in the
+/// explanation of a conflict, e.g. `app` or `the workspace`. When possible,
+/// versions from `preferences` are picked, otherwise the newest ones are.
+///
+/// # Errors
+/// Returns an error if an index file cannot be read or there is no solution.
+pub fn resolve(
+    root: impl Into<String>,
+    requirements: &[Requirement],
+    preferences: &BTreeMap<String, Version>,
+    index: &mut impl PackageIndex,
+) -> Result<Resolution, ResolutionError> {
+    let mut solver = Solver {
+        index,
+        preferences,
+        packages: vec![Package {
+            name: root.into(),
+            versions: vec![Version::new(0, 0, 0)],
+            dependencies: vec![requirements
+                .iter()
+                .map(|requirement| {
+                    (
+                        Some(requirement.dependent.clone()),
+                        requirement.name.clone(),
+                        requirement.version.clone(),
+                    )
+                })
+                .collect()],
+            found: true,
+        }],
+        ids: FxHashMap::default(),
+        labels: FxHashMap::default(),
+        incompatibilities: Vec::new(),
+        assignments: Vec::new(),
+    };
+
+    solver.solve()
+}
+
+/// Stands for "the package is not selected" in version sets.
+const NONE: usize = usize::MAX;
+
+/// Stands for versions, that are not in the registry, in version sets. It can
+/// never be selected. Requirements, that don't match any available versions,
+/// are represented with it instead of an empty set, so that their negations,
+/// e.g. "not `json ^1.0`", are not satisfied right away, and the solver finds
+/// out, that there are no versions to choose from.
+const OTHER: usize = usize::MAX - 1;
+
+/// Indices of versions of a package, possibly including [`OTHER`] and [`NONE`].
+type VersionSet = BTreeSet<usize>;
+
+/// The root package, that requirements come from.
+const ROOT: usize = 0;
+
+/// A condition on the version of a package: the selected version (or [`NONE`])
+/// must be in the set. A term is positive, if it requires the package to be
+/// selected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Term {
+    package: usize,
+    set: VersionSet,
+}
+
+impl Term {
+    fn is_positive(&self) -> bool {
+        !self.set.contains(&NONE)
+    }
+}
+
+#[derive(Debug)]
+struct Package {
+    name: String,
+
+    /// Available versions in ascending order.
+    versions: Vec<Version>,
+
+    /// Dependencies of every version. The first element of a dependency is
+    /// the name of the dependent package, that overrides the name of the root.
+    dependencies: Vec<Vec<(Option<String>, String, VersionReq)>>,
+
+    /// Whether the package is in the registry.
+    found: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Cause {
+    /// The root package must be selected.
+    Root,
+
+    /// A package version depends on a package.
+    Dependency {
+        dependent: String,
+        dependency: String,
+    },
+
+    /// No versions match the requirement.
+    NoVersions { dependency: String },
+
+    /// The package is not in the registry.
+    NotFound,
+
+    /// Derived from two other incompatibilities.
+    Derived(usize, usize),
+}
+
+/// Terms, that cannot be all satisfied at the same time.
+#[derive(Debug)]
+struct Incompatibility {
+    terms: Vec<Term>,
+    cause: Cause,
+}
+
+#[derive(Debug)]
+struct Assignment {
+    term: Term,
+    decision_level: usize,
+
+    /// The incompatibility, that the assignment was derived from, or `None`
+    /// if it's a decision.
+    cause: Option<usize>,
+}
+
+enum Relation {
+    Satisfied,
+    Contradicted,
+    AlmostSatisfied(Term),
+    Inconclusive,
+}
+
+struct Solver<'s, I> {
+    index: &'s mut I,
+    preferences: &'s BTreeMap<String, Version>,
+    packages: Vec<Package>,
+    ids: FxHashMap<String, usize>,
+
+    /// Requirements, that produced version sets, used to describe terms.
+    labels: FxHashMap<(usize, VersionSet), String>,
+
+    incompatibilities: Vec<Incompatibility>,
+
+    /// The partial solution.
+    assignments: Vec<Assignment>,
+}
+
+impl<I: PackageIndex> Solver<'_, I> {
+    fn solve(&mut self) -> Result<Resolution, ResolutionError> {
+        self.incompatibilities.push(Incompatibility {
+            terms: vec![Term {
+                package: ROOT,
+                set: VersionSet::from([NONE]),
+            }],
+            cause: Cause::Root,
+        });
+
+        let mut next = ROOT;
+
+        loop {
+            if let Err(incompatibility) = self.propagate(next) {
+                return Err(ResolutionError::Conflict(self.explain(incompatibility)));
+            }
+
+            match self.decide()? {
+                Some(package) => next = package,
+                None => break,
+            }
+        }
+
+        Ok(Resolution {
+            versions: self
+                .assignments
+                .iter()
+                .filter(|assignment| assignment.cause.is_none() && assignment.term.package != ROOT)
+                .filter_map(|assignment| {
+                    let package = &self.packages[assignment.term.package];
+
+                    assignment
+                        .term
+                        .set
+                        .first()
+                        .map(|version| (package.name.clone(), package.versions[*version].clone()))
+                })
+                .collect(),
+        })
+    }
+
+    /// Derives assignments from incompatibilities, that are almost satisfied,
+    /// starting from the ones, that mention a given package. Returns the terminal
+    /// incompatibility, if a conflict cannot be resolved.
+    fn propagate(&mut self, package: usize) -> Result<(), usize> {
+        let mut changed = vec![package];
+
+        while let Some(package) = changed.pop() {
+            for idx in (0..self.incompatibilities.len()).rev() {
+                if !self.incompatibilities[idx]
+                    .terms
+                    .iter()
+                    .any(|term| term.package == package)
+                {
+                    continue;
+                }
+
+                match self.relation(idx) {
+                    Relation::Satisfied => {
+                        let root_cause = self.resolve_conflict(idx)?;
+
+                        if let Relation::AlmostSatisfied(term) = self.relation(root_cause) {
+                            changed.clear();
+                            changed.push(term.package);
+                            self.derive(&term, root_cause);
+                        }
+
+                        break;
+                    }
+                    Relation::AlmostSatisfied(term) => {
+                        if !changed.contains(&term.package) {
+                            changed.push(term.package);
+                        }
+
+                        self.derive(&term, idx);
+                    }
+                    Relation::Contradicted | Relation::Inconclusive => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Picks a version of a package, that must be selected, but isn't yet.
+    /// Returns `None` if every such package is selected.
+    fn decide(&mut self) -> Result<Option<usize>, ResolutionError> {
+        let mut next: Option<(usize, VersionSet)> = None;
+
+        for package in 0..self.packages.len() {
+            if self.is_decided(package) {
+                continue;
+            }
+
+            let set = self.accumulated(package, self.assignments.len());
+
+            if set.contains(&NONE) {
+                continue;
+            }
+
+            // Packages with fewer candidates are more likely to cause conflicts,
+            // so they are decided first.
+            if next
+                .as_ref()
+                .is_none_or(|(_, candidates)| set.len() < candidates.len())
+            {
+                next = Some((package, set));
+            }
+        }
+
+        let Some((package, set)) = next else {
+            return Ok(None);
+        };
+
+        let candidates = set
+            .iter()
+            .copied()
+            .filter(|version| *version != OTHER)
+            .collect::<Vec<_>>();
+        let preferred = self
+            .preferences
+            .get(&self.packages[package].name)
+            .and_then(|preferred| {
+                candidates
+                    .iter()
+                    .find(|version| self.packages[package].versions[**version] == *preferred)
+            });
+
+        let Some(version) = preferred.or_else(|| candidates.last()).copied() else {
+            let cause = if self.packages[package].found {
+                Cause::NoVersions {
+                    dependency: self.requirement_of(package),
+                }
+            } else {
+                Cause::NotFound
+            };
+
+            self.incompatibilities.push(Incompatibility {
+                terms: vec![Term { package, set }],
+                cause,
+            });
+
+            return Ok(Some(package));
+        };
+
+        let dependent = if package == ROOT {
+            self.packages[ROOT].name.clone()
+        } else {
+            format!(
+                "{} {}",
+                self.packages[package].name, self.packages[package].versions[version]
+            )
+        };
+
+        for (dependent_override, name, requirement) in
+            self.packages[package].dependencies[version].clone()
+        {
+            let dependency = self.package_id(&name)?;
+            let mut set = self.matching(dependency, &requirement);
+
+            if set.is_empty() {
+                set.insert(OTHER);
+            }
+
+            self.labels
+                .entry((dependency, set.clone()))
+                .or_insert_with(|| requirement.to_string());
+
+            let negated = self.negate(&Term {
+                package: dependency,
+                set,
+            });
+
+            self.incompatibilities.push(Incompatibility {
+                terms: vec![
+                    Term {
+                        package,
+                        set: VersionSet::from([version]),
+                    },
+                    negated,
+                ],
+                cause: Cause::Dependency {
+                    dependent: dependent_override.unwrap_or_else(|| dependent.clone()),
+                    dependency: format!("{name} {requirement}"),
+                },
+            });
+        }
+
+        self.assignments.push(Assignment {
+            term: Term {
+                package,
+                set: VersionSet::from([version]),
+            },
+            decision_level: self.decision_level() + 1,
+            cause: None,
+        });
+
+        Ok(Some(package))
+    }
+
+    /// Finds the root cause of a conflict, i.e. an incompatibility, that is
+    /// satisfied by the partial solution, and backtracks to the point, where it's
+    /// almost satisfied. Returns the terminal incompatibility, if the conflict
+    /// cannot be resolved.
+    fn resolve_conflict(&mut self, mut incompatibility: usize) -> Result<usize, usize> {
+        loop {
+            if self.is_terminal(incompatibility) {
+                return Err(incompatibility);
+            }
+
+            let (satisfier, previous_level) = self.find_satisfier(incompatibility);
+            let assignment = &self.assignments[satisfier];
+
+            let cause = match assignment.cause {
+                Some(cause) if assignment.decision_level == previous_level => cause,
+                _ => {
+                    self.backtrack(previous_level);
+
+                    return Ok(incompatibility);
+                }
+            };
+
+            let package = assignment.term.package;
+            let satisfier_term = assignment.term.clone();
+            let mut terms = Vec::new();
+
+            for term in self.incompatibilities[incompatibility]
+                .terms
+                .iter()
+                .chain(&self.incompatibilities[cause].terms)
+                .filter(|term| term.package != package)
+            {
+                self.merge_term(&mut terms, term.clone());
+            }
+
+            if let Some(term) = self.incompatibilities[incompatibility]
+                .terms
+                .iter()
+                .find(|term| term.package == package)
+            {
+                if !satisfier_term.set.is_subset(&term.set) {
+                    let mut set = term.set.clone();
+                    set.extend(self.negate(&satisfier_term).set);
+
+                    self.merge_term(&mut terms, Term { package, set });
+                }
+            }
+
+            self.incompatibilities.push(Incompatibility {
+                terms,
+                cause: Cause::Derived(incompatibility, cause),
+            });
+            incompatibility = self.incompatibilities.len() - 1;
+        }
+    }
+
+    /// Returns the earliest assignment, after which a given incompatibility is
+    /// satisfied, and the decision level, at which the incompatibility would be
+    /// satisfied without it (but with the assignment itself).
+    fn find_satisfier(&self, incompatibility: usize) -> (usize, usize) {
+        let terms = &self.incompatibilities[incompatibility].terms;
+        let satisfier = (0..self.assignments.len())
+            .find(|idx| {
+                terms
+                    .iter()
+                    .all(|term| self.accumulated(term.package, idx + 1).is_subset(&term.set))
+            })
+            .unwrap_or(self.assignments.len() - 1);
+
+        let previous_level = (0..satisfier)
+            .find(|idx| {
+                terms.iter().all(|term| {
+                    let mut set = self.accumulated(term.package, idx + 1);
+
+                    if self.assignments[satisfier].term.package == term.package {
+                        set.retain(|version| {
+                            self.assignments[satisfier].term.set.contains(version)
+                        });
+                    }
+
+                    set.is_subset(&term.set)
+                })
+            })
+            .map_or(1, |idx| self.assignments[idx].decision_level.max(1));
+
+        (satisfier, previous_level)
+    }
+
+    fn relation(&self, incompatibility: usize) -> Relation {
+        let mut unsatisfied = None;
+
+        for term in &self.incompatibilities[incompatibility].terms {
+            let set = self.accumulated(term.package, self.assignments.len());
+
+            if set.is_subset(&term.set) {
+                continue;
+            }
+
+            if set.is_disjoint(&term.set) {
+                return Relation::Contradicted;
+            }
+
+            if unsatisfied.is_some() {
+                return Relation::Inconclusive;
+            }
+
+            unsatisfied = Some(term.clone());
+        }
+
+        unsatisfied.map_or(Relation::Satisfied, Relation::AlmostSatisfied)
+    }
+
+    /// Adds the negation of a given term to the partial solution.
+    fn derive(&mut self, term: &Term, cause: usize) {
+        self.assignments.push(Assignment {
+            term: self.negate(term),
+            decision_level: self.decision_level(),
+            cause: Some(cause),
+        });
+    }
+
+    fn backtrack(&mut self, decision_level: usize) {
+        self.assignments
+            .retain(|assignment| assignment.decision_level <= decision_level);
+    }
+
+    fn decision_level(&self) -> usize {
+        self.assignments
+            .iter()
+            .filter(|assignment| assignment.cause.is_none())
+            .count()
+    }
+
+    fn is_decided(&self, package: usize) -> bool {
+        self.assignments
+            .iter()
+            .any(|assignment| assignment.cause.is_none() && assignment.term.package == package)
+    }
+
+    fn is_terminal(&self, incompatibility: usize) -> bool {
+        match self.incompatibilities[incompatibility].terms.as_slice() {
+            [] => true,
+            [term] => term.package == ROOT && term.is_positive(),
+            _ => false,
+        }
+    }
+
+    /// Returns the intersection of terms of a package in the first `count`
+    /// assignments.
+    fn accumulated(&self, package: usize, count: usize) -> VersionSet {
+        let mut set = self.universe(package);
+
+        for assignment in &self.assignments[..count] {
+            if assignment.term.package == package {
+                set.retain(|version| assignment.term.set.contains(version));
+            }
+        }
+
+        set
+    }
+
+    fn universe(&self, package: usize) -> VersionSet {
+        (0..self.packages[package].versions.len())
+            .chain([OTHER, NONE])
+            .collect()
+    }
+
+    fn negate(&self, term: &Term) -> Term {
+        Term {
+            package: term.package,
+            set: self
+                .universe(term.package)
+                .difference(&term.set)
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// Adds a term to a list of terms of an incompatibility, intersecting it with
+    /// the term for the same package. Terms, that are always satisfied, are dropped.
+    fn merge_term(&self, terms: &mut Vec<Term>, term: Term) {
+        if let Some(existing) = terms
+            .iter_mut()
+            .find(|existing| existing.package == term.package)
+        {
+            existing.set.retain(|version| term.set.contains(version));
+        } else {
+            terms.push(term);
+        }
+
+        terms.retain(|term| term.set != self.universe(term.package));
+    }
+
+    /// Returns indices of versions of a package, that match a given requirement.
+    fn matching(&self, package: usize, requirement: &VersionReq) -> VersionSet {
+        self.packages[package]
+            .versions
+            .iter()
+            .enumerate()
+            .filter(|(_, version)| requirement.matches(version))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Returns the requirement, that made a package required, e.g. `json ^0.3`.
+    fn requirement_of(&self, package: usize) -> String {
+        self.assignments
+            .iter()
+            .rev()
+            .filter(|assignment| assignment.term.package == package)
+            .find_map(|assignment| {
+                match assignment
+                    .cause
+                    .map(|cause| &self.incompatibilities[cause].cause)
+                {
+                    Some(Cause::Dependency { dependency, .. }) => Some(dependency.clone()),
+                    _ => None,
+                }
+            })
+            .unwrap_or_else(|| self.packages[package].name.clone())
+    }
+
+    fn package_id(&mut self, name: &str) -> Result<usize, ResolutionError> {
+        if let Some(id) = self.ids.get(name) {
+            return Ok(*id);
+        }
+
+        let index_file = self
+            .index
+            .index_file(name)
+            .map_err(ResolutionError::Index)?;
+        let preferred = self.preferences.get(name);
+        let versions = index_file
+            .iter()
+            .flat_map(|index_file| &index_file.versions)
+            .filter(|version| !version.yanked || Some(&version.version) == preferred)
+            .collect::<Vec<_>>();
+
+        self.packages.push(Package {
+            name: name.to_owned(),
+            versions: versions
+                .iter()
+                .map(|version| version.version.clone())
+                .collect(),
+            dependencies: versions
+                .iter()
+                .map(|version| {
+                    version
+                        .dependencies
+                        .iter()
+                        .map(|(name, requirement)| (None, name.clone(), requirement.clone()))
+                        .collect()
+                })
+                .collect(),
+            found: index_file.is_some(),
+        });
+
+        let id = self.packages.len() - 1;
+        self.ids.insert(name.to_owned(), id);
+
+        Ok(id)
+    }
+
+    /// Explains why a given terminal incompatibility was derived, one derivation
+    /// per line.
+    fn explain(&self, incompatibility: usize) -> String {
+        let mut lines = Vec::new();
+
+        self.explain_derivation(incompatibility, &mut lines, &mut FxHashSet::default());
+
+        lines.join("\n")
+    }
+
+    fn explain_derivation(
+        &self,
+        incompatibility: usize,
+        lines: &mut Vec<String>,
+        explained: &mut FxHashSet<usize>,
+    ) {
+        let Cause::Derived(left, right) = self.incompatibilities[incompatibility].cause else {
+            lines.push(format!("{}.", self.describe(incompatibility)));
+            return;
+        };
+
+        for cause in [left, right] {
+            if matches!(self.incompatibilities[cause].cause, Cause::Derived(..))
+                && explained.insert(cause)
+            {
+                self.explain_derivation(cause, lines, explained);
+            }
+        }
+
+        lines.push(format!(
+            "Because {} and {}, {}.",
+            self.describe(left),
+            self.describe(right),
+            self.describe(incompatibility)
+        ));
+    }
+
+    /// Returns a human-readable description of an incompatibility.
+    fn describe(&self, incompatibility: usize) -> String {
+        let incompatibility = &self.incompatibilities[incompatibility];
+
+        match &incompatibility.cause {
+            Cause::Root => format!("{} is required", self.packages[ROOT].name),
+            Cause::Dependency {
+                dependent,
+                dependency,
+            } => format!("{dependent} depends on {dependency}"),
+            Cause::NoVersions { dependency } => {
+                format!("no versions of {dependency} are available")
+            }
+            Cause::NotFound => format!(
+                "{} is not in the registry",
+                incompatibility
+                    .terms
+                    .first()
+                    .map_or("the package", |term| &self.packages[term.package].name)
+            ),
+            Cause::Derived(..) => match incompatibility.terms.as_slice() {
+                [] => "version solving failed".to_owned(),
+                [term] if term.package == ROOT && term.is_positive() => {
+                    "version solving failed".to_owned()
+                }
+                [term] if term.is_positive() => {
+                    format!("{} cannot be used", self.describe_term(term))
+                }
+                [term] => format!("{} is required", self.describe_term(&self.negate(term))),
+                [positive, negative] | [negative, positive]
+                    if positive.is_positive() && !negative.is_positive() =>
+                {
+                    format!(
+                        "{} depends on {}",
+                        self.describe_term(positive),
+                        self.describe_term(&self.negate(negative))
+                    )
+                }
+                [first, second] if first.is_positive() && second.is_positive() => format!(
+                    "{} is incompatible with {}",
+                    self.describe_term(first),
+                    self.describe_term(second)
+                ),
+                terms => format!(
+                    "{} are incompatible",
+                    terms
+                        .iter()
+                        .map(|term| self.describe_term(term))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
+        }
+    }
+
+    /// Returns a human-readable description of a term, e.g. `json ^0.2`,
+    /// `not http 1.0.0` or `every version of http`.
+    fn describe_term(&self, term: &Term) -> String {
+        if term.package == ROOT {
+            return self.packages[ROOT].name.clone();
+        }
+
+        if !term.is_positive() {
+            return format!("not {}", self.describe_term(&self.negate(term)));
+        }
+
+        let package = &self.packages[term.package];
+        let name = &package.name;
+
+        if let Some(label) = self.labels.get(&(term.package, term.set.clone())) {
+            return format!("{name} {label}");
+        }
+
+        let indices = term
+            .set
+            .iter()
+            .copied()
+            .filter(|version| *version != OTHER)
+            .collect::<Vec<_>>();
+        let versions = indices
+            .iter()
+            .map(|version| &package.versions[*version])
+            .collect::<Vec<_>>();
+
+        match versions.as_slice() {
+            [] => format!("no versions of {name}"),
+            _ if versions.len() == package.versions.len() => format!("every version of {name}"),
+            [version] => format!("{name} {version}"),
+            [first, .., last] if indices[indices.len() - 1] - indices[0] + 1 == indices.len() => {
+                format!("{name} >={first}, <={last}")
+            }
+            versions => format!(
+                "{name} {}",
+                versions
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Prints the dependency graph as an indented tree:
+//!
+//! ```txt
+//! app v0.1.0 (path+.)
+//! ├── http v1.0.0 (registry+../registry)
+//! │   └── json v0.1.0 (registry+../registry)
+//! │       └── itoa v1.0.0 (registry+../registry)
+//! └── json v0.1.0 (registry+../registry) (*)
+//! [dev-dependencies]
+//! └── testing v0.1.0 (path+testing)
+//! ```
+//!
+//! or as a Graphviz graph. Packages, whose dependencies were already printed,
+//! are marked with `(*)` and their dependencies are not repeated.
+
+use std::{collections::VecDeque, fmt::Write};
+
+use stellar_fx_hash::FxHashSet;
+
+use crate::{DependencyGraph, DependencyKind, NodeIndex};
+
+/// Prints the dependency graph, describing every package with a given function,
+/// e.g. as `json v0.1.0 (path+../json)`.
+#[derive(Debug, Clone)]
+pub struct TreePrinter<'g, D> {
+    graph: &'g DependencyGraph,
+    describe: D,
+
+    /// Whether edges go from dependencies to dependents.
+    inverted: bool,
+
+    /// The maximum depth of the tree.
+    depth: Option<usize>,
+}
+
+impl<'g, D> TreePrinter<'g, D>
+where
+    D: Fn(NodeIndex) -> String,
+{
+    /// Returns a new printer of dependencies of packages in a given graph.
+    #[inline]
+    #[must_use]
+    pub const fn new(graph: &'g DependencyGraph, describe: D) -> Self {
+        Self {
+            graph,
+            describe,
+            inverted: false,
+            depth: None,
+        }
+    }
+
+    /// Builds a new printer, that prints packages depending on the roots
+    /// instead of dependencies.
+    #[inline]
+    #[must_use]
+    pub const fn inverted(mut self) -> Self {
+        self.inverted = true;
+        self
+    }
+
+    /// Builds a new printer with a given maximum depth of the tree, e.g. `1` only
+    /// prints direct dependencies of the roots.
+    #[inline]
+    #[must_use]
+    pub const fn with_depth(mut self, depth: Option<usize>) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Returns the trees of given root packages separated by empty lines.
+    #[must_use]
+    pub fn text(&self, roots: &[NodeIndex]) -> String {
+        let mut output = String::new();
+        let mut expanded = FxHashSet::default();
+
+        for (idx, root) in roots.iter().enumerate() {
+            if idx != 0 {
+                output.push('\n');
+            }
+
+            self.write_package(&mut output, *root, "", 0, &mut expanded);
+        }
+
+        output
+    }
+
+    /// Writes a package and, unless its children were already written or the
+    /// maximum depth is reached, its children.
+    fn write_package(
+        &self,
+        output: &mut String,
+        node: NodeIndex,
+        prefix: &str,
+        depth: usize,
+        expanded: &mut FxHashSet<NodeIndex>,
+    ) {
+        let edges = if self.is_too_deep(depth) {
+            Vec::new()
+        } else {
+            self.edges(node)
+        };
+
+        output.push_str(&(self.describe)(node));
+
+        // A package reached at the maximum depth is not marked as expanded, so
+        // that a later occurrence closer to the root still prints its children.
+        if edges.is_empty() {
+            output.push('\n');
+            return;
+        }
+
+        if !expanded.insert(node) {
+            output.push_str(" (*)\n");
+            return;
+        }
+
+        output.push('\n');
+
+        for kind in [DependencyKind::Normal, DependencyKind::Dev] {
+            let children = edges
+                .iter()
+                .filter(|(edge_kind, _)| *edge_kind == kind)
+                .collect::<Vec<_>>();
+
+            if kind == DependencyKind::Dev && !children.is_empty() {
+                let _ = writeln!(output, "{prefix}[dev-dependencies]");
+            }
+
+            for (idx, (_, child)) in children.iter().enumerate() {
+                let last = idx == children.len() - 1;
+
+                output.push_str(prefix);
+                output.push_str(if last { "└── " } else { "├── " });
+
+                self.write_package(
+                    output,
+                    *child,
+                    &format!("{prefix}{}", if last { "    " } else { "│   " }),
+                    depth + 1,
+                    expanded,
+                );
+            }
+        }
+    }
+
+    /// Returns a Graphviz graph of given root packages. Dev dependencies are
+    /// drawn with dashed edges.
+    #[must_use]
+    pub fn dot(&self, roots: &[NodeIndex]) -> String {
+        let mut output = "digraph dependencies {\n".to_owned();
+        let mut visited = FxHashSet::default();
+
+        // Packages are visited in the breadth-first order, so that every package
+        // is first reached at its smallest depth and its edges are only skipped,
+        // if every path to it exceeds the maximum depth.
+        let mut queue = roots.iter().map(|root| (*root, 0)).collect::<VecDeque<_>>();
+
+        while let Some((node, depth)) = queue.pop_front() {
+            if !visited.insert(node) {
+                continue;
+            }
+
+            let _ = writeln!(
+                output,
+                "    n{} [label = \"{}\"];",
+                node.index(),
+                (self.describe)(node)
+            );
+
+            if self.is_too_deep(depth) {
+                continue;
+            }
+
+            for (kind, other) in self.edges(node) {
+                let (from, to) = if self.inverted {
+                    (other, node)
+                } else {
+                    (node, other)
+                };
+
+                let _ = writeln!(
+                    output,
+                    "    n{} -> n{}{};",
+                    from.index(),
+                    to.index(),
+                    if kind == DependencyKind::Dev {
+                        " [style = dashed]"
+                    } else {
+                        ""
+                    }
+                );
+
+                queue.push_back((other, depth + 1));
+            }
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Returns `true` if children of packages at a given depth are not printed.
+    fn is_too_deep(&self, depth: usize) -> bool {
+        self.depth.is_some_and(|max_depth| depth >= max_depth)
+    }
+
+    /// Returns children of a given package in the tree: dependencies, or
+    /// dependents if the tree is inverted.
+    fn edges(&self, node: NodeIndex) -> Vec<(DependencyKind, NodeIndex)> {
+        let edges = if self.inverted {
+            self.graph.dependents(node)
+        } else {
+            self.graph.dependencies(node)
+        };
+
+        edges
+            .into_iter()
+            .map(|(dependency, node)| (dependency.kind, node))
+            .collect()
+    }
+}
@@ -0,0 +1,60 @@
+//! Fixtures shared by dependency graph tests.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use stellar_depgraph::PackageProvider;
+use stellar_interner::PathId;
+use stellar_manifest::{lockfile::GitSource, parse_manifest, TomlDependency, TomlManifest};
+
+/// Loads packages from the filesystem, without registry and git dependencies.
+pub struct Provider;
+
+impl PackageProvider for Provider {
+    fn load_manifest(&mut self, root: &Path) -> Result<TomlManifest, String> {
+        let path = root.join("package.toml");
+        let source = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+
+        parse_manifest(PathId::from(&path), source)
+            .into_result()
+            .map_err(|_| format!("cannot parse {}", path.display()))
+    }
+
+    fn fetch_registry_package(
+        &mut self,
+        _: &str,
+        _: &TomlDependency,
+    ) -> Result<Option<PathBuf>, String> {
+        Ok(None)
+    }
+
+    fn fetch_git_package(
+        &mut self,
+        name: &str,
+        _: &TomlDependency,
+        _: &Path,
+    ) -> Result<(PathBuf, GitSource), String> {
+        Err(format!("cannot fetch `{name}`"))
+    }
+}
+
+/// Creates packages with given names and dependency sections in a new temporary
+/// directory.
+pub fn packages(test: &str, packages: &[(&str, &str)]) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("stellar_depgraph_{test}_{}", process::id()));
+    let _ = fs::remove_dir_all(&directory);
+
+    for (name, dependencies) in packages {
+        fs::create_dir_all(directory.join(name)).unwrap();
+        fs::write(
+            directory.join(name).join("package.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n{dependencies}"),
+        )
+        .unwrap();
+    }
+
+    directory
+}
@@ -0,0 +1,98 @@
+use std::fs;
+
+use common::{packages, Provider};
+use stellar_database::Database;
+use stellar_depgraph::{DependencyGraph, DependencyGraphError, PackageKind};
+use stellar_interner::IdentifierId;
+
+mod common;
+
+#[test]
+fn build_order() {
+    let directory = packages(
+        "build_order",
+        &[
+            (
+                "app",
+                "[dependencies]\njson = { path = \"../json\" }\nhttp = { path = \"../http\" }\nregistry = { version = \"1.0\" }\n",
+            ),
+            ("http", "[dependencies]\njson = { path = \"../json\" }\n"),
+            ("json", ""),
+        ],
+    );
+
+    let graph = DependencyGraph::build(&[directory.join("app")], &mut Provider).unwrap();
+    let order = graph.build_order(|_, _| true);
+
+    assert_eq!(
+        order
+            .iter()
+            .map(|node| graph.package(*node).name.as_str())
+            .collect::<Vec<_>>(),
+        ["json", "http", "app"]
+    );
+
+    let root = graph.roots()[0];
+
+    assert_eq!(graph.package(root).kind, PackageKind::Root);
+    assert!(order
+        .iter()
+        .filter(|node| **node != root)
+        .all(|node| graph.package(*node).parent == Some(root)));
+
+    assert_eq!(
+        graph
+            .dependents(order[0])
+            .iter()
+            .map(|(_, node)| graph.package(*node).name.as_str())
+            .collect::<Vec<_>>(),
+        ["app", "http"]
+    );
+
+    let mut db = Database::new();
+    let packages = graph.alloc_packages(&mut db, |_, _| true);
+    let (_, app) = packages[2];
+    let (_, json) = packages[0];
+
+    assert_eq!(app.dependencies(&db).len(), 2);
+    assert_eq!(
+        app.dependencies(&db).get(&IdentifierId::from("json")),
+        Some(&json)
+    );
+    assert_eq!(json.parent(&db), Some(app));
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn cycle() {
+    let directory = packages(
+        "cycle",
+        &[
+            ("a", "[dependencies]\nb = { path = \"../b\" }\n"),
+            ("b", "[dependencies]\nc = { path = \"../c\" }\n"),
+            (
+                "c",
+                "[dev-dependencies]\n\n[dependencies]\na = { path = \"../a\" }\n",
+            ),
+        ],
+    );
+
+    let Err(DependencyGraphError::Cycle(cycle)) =
+        DependencyGraph::build(&[directory.join("a")], &mut Provider)
+    else {
+        panic!("expected a dependency cycle");
+    };
+
+    assert_eq!(cycle.traceback(), ["a", "b", "c", "a"]);
+    assert_eq!(
+        cycle
+            .locations()
+            .iter()
+            .map(|location| location.start.0..location.end.0)
+            .collect::<Vec<_>>(),
+        [55..56, 55..56, 75..76]
+    );
+
+    fs::remove_dir_all(directory).unwrap();
+}
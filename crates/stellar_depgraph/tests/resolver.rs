@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use stellar_depgraph::resolver::{resolve, PackageIndex, Requirement, ResolutionError};
+use stellar_manifest::{
+    registry::{IndexFile, IndexVersion},
+    Version, VersionReq,
+};
+
+struct Index(BTreeMap<&'static str, IndexFile>);
+
+impl PackageIndex for Index {
+    fn index_file(&mut self, name: &str) -> Result<Option<IndexFile>, String> {
+        Ok(self.0.get(name).cloned())
+    }
+}
+
+fn version(version: &str) -> Version {
+    Version::parse(version).unwrap()
+}
+
+fn requirement(requirement: &str) -> VersionReq {
+    VersionReq::parse(requirement).unwrap()
+}
+
+fn published(v: &str, dependencies: &[(&str, &str)]) -> IndexVersion {
+    IndexVersion::new(version(v), "").with_dependencies(
+        dependencies
+            .iter()
+            .map(|(name, req)| (*name, requirement(req))),
+    )
+}
+
+fn index() -> Index {
+    Index(BTreeMap::from([
+        (
+            "http",
+            IndexFile::new()
+                .with_version(published("1.0.0", &[("json", "^0.1")]))
+                .with_version(published("1.1.0", &[("json", "^0.2")]))
+                .with_version(published("1.2.0", &[("json", "^0.2")]).yanked()),
+        ),
+        (
+            "json",
+            IndexFile::new()
+                .with_version(published("0.1.0", &[]))
+                .with_version(published("0.1.1", &[]))
+                .with_version(published("0.2.0", &[])),
+        ),
+    ]))
+}
+
+#[test]
+fn backtracking() {
+    let resolution = resolve(
+        "app",
+        &[
+            Requirement::new("app", "http", requirement("^1.0")),
+            Requirement::new("app", "json", requirement("^0.1")),
+        ],
+        &BTreeMap::new(),
+        &mut index(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolution
+            .iter()
+            .map(|(name, version)| format!("{name} {version}"))
+            .collect::<Vec<_>>(),
+        ["http 1.0.0", "json 0.1.1"]
+    );
+}
+
+#[test]
+fn preferences() {
+    let preferences = BTreeMap::from([
+        ("http".to_owned(), version("1.2.0")),
+        ("json".to_owned(), version("0.1.0")),
+    ]);
+    let resolution = resolve(
+        "app",
+        &[Requirement::new("app", "http", requirement("^1.0"))],
+        &preferences,
+        &mut index(),
+    )
+    .unwrap();
+
+    assert_eq!(resolution.version("http"), Some(&version("1.2.0")));
+    assert_eq!(resolution.version("json"), Some(&version("0.2.0")));
+}
+
+#[test]
+fn conflict() {
+    let error = resolve(
+        "app",
+        &[
+            Requirement::new("app", "http", requirement("^1.1")),
+            Requirement::new("app", "json", requirement("^0.1")),
+        ],
+        &BTreeMap::new(),
+        &mut index(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error,
+        ResolutionError::Conflict(
+            "Because http 1.1.0 depends on json ^0.2 and app depends on http ^1.1, \
+             app depends on json ^0.2.\n\
+             Because app depends on json ^0.2 and app depends on json ^0.1, \
+             version solving failed."
+                .to_owned()
+        )
+    );
+}
+
+#[test]
+fn not_found() {
+    let error = resolve(
+        "app",
+        &[Requirement::new("app", "xml", requirement("^1.0"))],
+        &BTreeMap::new(),
+        &mut index(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error,
+        ResolutionError::Conflict(
+            "Because xml is not in the registry and app depends on xml ^1.0, \
+             version solving failed."
+                .to_owned()
+        )
+    );
+}
@@ -0,0 +1,114 @@
+use common::{packages, Provider};
+use stellar_depgraph::{tree::TreePrinter, DependencyGraph, NodeIndex};
+
+mod common;
+
+/// Builds the graph of `app`, that depends on `http` and `json`, and a dev
+/// dependency `testing`, where `http` depends on `json` and `json` depends on
+/// `itoa`.
+fn graph(test: &str) -> DependencyGraph {
+    let directory = packages(
+        test,
+        &[
+            (
+                "app",
+                "[dependencies]\nhttp = { path = \"../http\" }\njson = { path = \"../json\" }\n\n[dev-dependencies]\ntesting = { path = \"../testing\" }\n",
+            ),
+            ("http", "[dependencies]\njson = { path = \"../json\" }\n"),
+            ("json", "[dependencies]\nitoa = { path = \"../itoa\" }\n"),
+            ("itoa", ""),
+            ("testing", ""),
+        ],
+    );
+
+    DependencyGraph::build(&[directory.join("app")], &mut Provider).unwrap()
+}
+
+fn name(graph: &DependencyGraph) -> impl Fn(NodeIndex) -> String + '_ {
+    |node| graph.package(node).name.clone()
+}
+
+#[test]
+fn duplicates() {
+    let graph = graph("tree_duplicates");
+
+    assert_eq!(
+        TreePrinter::new(&graph, name(&graph)).text(graph.roots()),
+        "app
+├── http
+│   └── json
+│       └── itoa
+└── json (*)
+[dev-dependencies]
+└── testing
+"
+    );
+}
+
+#[test]
+fn depth() {
+    let graph = graph("tree_depth");
+
+    // `json` is first reached at the maximum depth, so its dependencies are
+    // printed, when it's reached again as a direct dependency.
+    assert_eq!(
+        TreePrinter::new(&graph, name(&graph))
+            .with_depth(Some(2))
+            .text(graph.roots()),
+        "app
+├── http
+│   └── json
+└── json
+    └── itoa
+[dev-dependencies]
+└── testing
+"
+    );
+}
+
+#[test]
+fn inverted() {
+    let graph = graph("tree_inverted");
+    let itoa = graph
+        .packages()
+        .find(|node| graph.package(*node).name == "itoa")
+        .unwrap();
+
+    assert_eq!(
+        TreePrinter::new(&graph, name(&graph))
+            .inverted()
+            .text(&[itoa]),
+        "itoa
+└── json
+    ├── app
+    └── http
+        └── app
+"
+    );
+}
+
+#[test]
+fn dot() {
+    let graph = graph("tree_dot");
+    let output = TreePrinter::new(&graph, name(&graph))
+        .with_depth(Some(2))
+        .dot(graph.roots());
+    let id = |name: &str| {
+        graph
+            .packages()
+            .find(|node| graph.package(*node).name == name)
+            .unwrap()
+            .index()
+    };
+
+    assert!(output.starts_with("digraph dependencies {\n"));
+    assert!(output.ends_with("}\n"));
+    assert!(output.contains(&format!("    n{} [label = \"json\"];\n", id("json"))));
+    assert!(output.contains(&format!("    n{} -> n{};\n", id("json"), id("itoa"))));
+    assert!(output.contains(&format!(
+        "    n{} -> n{} [style = dashed];\n",
+        id("app"),
+        id("testing")
+    )));
+    assert_eq!(output.matches("[label").count(), 5);
+}
@@ -0,0 +1,90 @@
+//! Editor-facing representation of diagnostic suggestions.
+//!
+//! Every [`Suggestion`] attached to a [`Diagnostic`] can be presented by an editor
+//! as a quick fix. The types in this module mirror the shape of code actions in the
+//! [Language Server Protocol], so that a language server can forward them as is.
+//!
+//! [Language Server Protocol]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_codeAction
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use stellar_filesystem::location::Location;
+
+use crate::{
+    diagnostic::{Diagnostic, LabelStyle, Suggestion, TextEdit},
+    Diagnostics,
+};
+
+/// The kind of a code action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CodeActionKind {
+    /// A fix for a particular diagnostic.
+    #[cfg_attr(feature = "serde", serde(rename = "quickfix"))]
+    QuickFix,
+}
+
+/// An action, that an editor can offer to the user, for example in a light bulb menu.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CodeAction {
+    /// A short, human-readable title of the action.
+    pub title: String,
+    /// The kind of the action.
+    pub kind: CodeActionKind,
+    /// The code of the diagnostic, that the action fixes.
+    pub diagnostic_code: Option<String>,
+    /// The text edits, that the action applies.
+    pub edits: Vec<TextEdit>,
+}
+
+impl CodeAction {
+    /// Creates a quick fix code action from a diagnostic suggestion.
+    #[inline]
+    #[must_use]
+    pub fn from_suggestion(diagnostic: &Diagnostic, suggestion: &Suggestion) -> Self {
+        Self {
+            title: suggestion.message.clone(),
+            kind: CodeActionKind::QuickFix,
+            diagnostic_code: diagnostic.code.clone(),
+            edits: suggestion.edits.clone(),
+        }
+    }
+}
+
+impl Diagnostic {
+    /// Returns code actions for all machine-applicable suggestions of the diagnostic.
+    #[must_use]
+    pub fn code_actions(&self) -> Vec<CodeAction> {
+        self.suggestions
+            .iter()
+            .filter(|suggestion| !suggestion.edits.is_empty())
+            .map(|suggestion| CodeAction::from_suggestion(self, suggestion))
+            .collect()
+    }
+
+    /// Returns `true` if one of the primary labels of the diagnostic overlaps
+    /// with a given location.
+    #[must_use]
+    pub fn is_reported_at(&self, location: Location) -> bool {
+        self.labels.iter().any(|label| {
+            label.style == LabelStyle::Primary
+                && label.location.filepath == location.filepath
+                && label.location.start <= location.end
+                && location.start <= label.location.end
+        })
+    }
+}
+
+impl Diagnostics {
+    /// Returns code actions, that are available at a given location, e.g.
+    /// under the cursor of an editor.
+    #[must_use]
+    pub fn code_actions_at(&self, location: Location) -> Vec<CodeAction> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_reported_at(location))
+            .flat_map(Diagnostic::code_actions)
+            .collect()
+    }
+}
@@ -119,6 +119,9 @@ pub struct Diagnostic {
     /// Notes that are associated with the primary cause of the diagnostic.
     /// These can include line breaks for improved formatting.
     pub notes: Vec<String>,
+    /// Machine-applicable fixes for the diagnostic, that can be shown
+    /// in the terminal as `help` notes, or applied by an editor.
+    pub suggestions: Vec<Suggestion>,
 }
 
 impl Diagnostic {
@@ -132,6 +135,7 @@ impl Diagnostic {
             message: String::new(),
             labels: Vec::new(),
             notes: Vec::new(),
+            suggestions: Vec::new(),
         }
     }
 
@@ -221,6 +225,22 @@ impl Diagnostic {
         self
     }
 
+    /// Add a suggestion to the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    /// Add some suggestions to the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn with_suggestions(mut self, suggestions: impl IntoIterator<Item = Suggestion>) -> Self {
+        self.suggestions.extend(suggestions);
+        self
+    }
+
     /// Returns the files involved in the diagnostic.
     #[inline]
     #[must_use]
@@ -230,4 +250,84 @@ impl Diagnostic {
             .map(|label| label.location.filepath)
             .collect()
     }
+
+    /// Returns the location of the first primary label of the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn primary_location(&self) -> Option<Location> {
+        self.labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+            .map(|label| label.location)
+    }
+}
+
+/// A text edit, that replaces a source text at a given location.
+///
+/// If the location is empty (`start == end`), the edit is an insertion.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextEdit {
+    /// The location of the source text to replace.
+    pub location: Location,
+    /// The text to replace the source text with.
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Create a new text edit.
+    #[inline]
+    #[must_use]
+    pub fn new(location: Location, replacement: impl ToString) -> Self {
+        Self {
+            location,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    /// Create a new text edit, that inserts a text before a given location.
+    #[inline]
+    #[must_use]
+    pub fn insert_before(location: Location, text: impl ToString) -> Self {
+        Self::new(
+            Location {
+                end: location.start,
+                ..location
+            },
+            text,
+        )
+    }
+}
+
+/// A fix for a diagnostic, that consists of a human-readable message
+/// and a list of text edits, that need to be applied together.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Suggestion {
+    /// The message, that describes the suggestion, e.g. ``consider importing `std.option.Option` ``.
+    ///
+    /// It should not include line breaks.
+    pub message: String,
+    /// The text edits, that implement the suggestion.
+    pub edits: Vec<TextEdit>,
+}
+
+impl Suggestion {
+    /// Create a new suggestion without any edits.
+    #[inline]
+    #[must_use]
+    pub fn new(message: impl ToString) -> Self {
+        Self {
+            message: message.to_string(),
+            edits: Vec::new(),
+        }
+    }
+
+    /// Add a text edit to the suggestion.
+    #[inline]
+    #[must_use]
+    pub fn with_edit(mut self, edit: TextEdit) -> Self {
+        self.edits.push(edit);
+        self
+    }
 }
@@ -28,11 +28,18 @@
 /// }
 /// ```
 ///
+/// Diagnostics can also have optional `notes { ... }`, `optional_notes { ... }` and
+/// `suggestions { ... }` blocks after the labels. `optional_notes` accepts a comma separated
+/// list of [`Option`]s, and only the notes that are present are added. `suggestions` accepts
+/// an expression that evaluates into an iterator of [`Suggestion`]s.
+///
 /// This macro invokations does few things:
 ///
 /// * Creates a `FailedToResolveModule` struct with given fields.
 /// * Automatically creates a constructor (`new` method) for it.
 /// * Automatically implements `BuildDiagnostic` trait for a given struct.
+///
+/// [`Suggestion`]: crate::diagnostic::Suggestion
 #[macro_export]
 macro_rules! define_diagnostics {
     {
@@ -89,10 +96,22 @@ macro_rules! define_diagnostics {
         )*
     };
     (@notes $diagnostic:ident,) => {};
-    (@notes $diagnostic:ident, notes { $($note:expr)* }) => {
+    (@notes $diagnostic:ident, notes { $($note:expr)* } $($rest:tt)*) => {
         let $diagnostic = $diagnostic.with_notes(vec![
             $($note),*
         ]);
+
+        $crate::define_diagnostics!(@notes $diagnostic, $($rest)*);
+    };
+    (@notes $diagnostic:ident, optional_notes { $($note:expr),* } $($rest:tt)*) => {
+        let $diagnostic = $diagnostic.with_notes(
+            [$($note),*].into_iter().flatten()
+        );
+
+        $crate::define_diagnostics!(@notes $diagnostic, $($rest)*);
+    };
+    (@notes $diagnostic:ident, suggestions { $suggestions:expr }) => {
+        let $diagnostic = $diagnostic.with_suggestions($suggestions);
     };
     (@primary_label $diagnostic:ident,) => {};
     (@primary_label $diagnostic:ident, $location:expr) => {
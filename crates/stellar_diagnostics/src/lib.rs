@@ -61,21 +61,24 @@
     clippy::similar_names
 )]
 
+pub mod code_action;
 pub mod diagnostic;
 pub mod files;
+pub mod lint;
 #[macro_use]
 mod diagnostic_macro;
 pub mod term;
 
 use core::fmt;
-use std::fmt::Display;
+use std::{fmt::Display, path::PathBuf};
 
-use stellar_filesystem::in_memory_file_storage::InMemoryFileStorage;
+use stellar_filesystem::{in_memory_file_storage::InMemoryFileStorage, location::Location};
 use stellar_fx_hash::FxHashSet;
-use stellar_interner::PathId;
+use stellar_interner::{PathId, DUMMY_PATH_ID};
 
 use crate::{
     diagnostic::{Diagnostic, Severity},
+    lint::{find_lint_by_code, LintLevel, LintLevels, LintSuppression},
     term::{
         termcolor::{ColorChoice, StandardStream},
         Config,
@@ -110,6 +113,17 @@ pub struct Diagnostics {
 
     /// Diagnostics.
     pub diagnostics: Vec<Diagnostic>,
+
+    /// Levels of lints, that are applied to added diagnostics, unless they are
+    /// reported in one of the packages with their own levels.
+    pub lint_levels: LintLevels,
+
+    /// Levels of lints in packages, by directories of the packages. If packages
+    /// are nested, levels of the innermost package are applied.
+    pub package_lint_levels: Vec<(PathBuf, LintLevels)>,
+
+    /// Lints allowed in parts of the source code.
+    pub suppressions: Vec<LintSuppression>,
 }
 
 impl Default for Diagnostics {
@@ -126,19 +140,130 @@ impl Diagnostics {
         Self {
             files_involved: FxHashSet::default(),
             diagnostics: vec![],
+            lint_levels: LintLevels::new(),
+            package_lint_levels: vec![],
+            suppressions: vec![],
         }
     }
 
+    /// Builds new diagnostics with given lint levels.
+    #[inline]
+    #[must_use]
+    pub fn with_lint_levels(mut self, lint_levels: LintLevels) -> Self {
+        self.lint_levels = lint_levels;
+        self
+    }
+
+    /// Sets levels of lints, that are applied to diagnostics added afterwards.
+    #[inline]
+    pub fn set_lint_levels(&mut self, lint_levels: LintLevels) {
+        self.lint_levels = lint_levels;
+    }
+
+    /// Sets levels of lints, that are applied to diagnostics reported in files
+    /// of the package located in a given directory.
+    #[inline]
+    pub fn set_package_lint_levels(
+        &mut self,
+        directory: impl Into<PathBuf>,
+        lint_levels: LintLevels,
+    ) {
+        self.package_lint_levels
+            .push((directory.into(), lint_levels));
+    }
+
+    /// Returns levels of lints, that are applied to diagnostics reported at
+    /// a given location.
+    #[must_use]
+    pub fn lint_levels_at(&self, location: Option<Location>) -> &LintLevels {
+        location
+            .filter(|location| location.filepath != DUMMY_PATH_ID)
+            .and_then(|location| {
+                let filepath = location.filepath.as_path();
+
+                self.package_lint_levels
+                    .iter()
+                    .filter(|(directory, _)| filepath.starts_with(directory))
+                    .max_by_key(|(directory, _)| directory.components().count())
+            })
+            .map_or(&self.lint_levels, |(_, lint_levels)| lint_levels)
+    }
+
     /// Adds a diagnostic associated with some files.
+    ///
+    /// If the diagnostic is reported by a lint, its severity is determined by
+    /// the level of the lint, and it's skipped if the lint is allowed, either
+    /// globally or in the scope, where the diagnostic is reported.
     #[inline]
     pub fn add_diagnostic(&mut self, diagnostic: impl BuildDiagnostic) {
         let diagnostic = diagnostic.build();
 
+        if self.suppress(&diagnostic) {
+            return;
+        }
+
+        let Some(diagnostic) = self
+            .lint_levels_at(diagnostic.primary_location())
+            .apply(diagnostic)
+        else {
+            return;
+        };
+
         self.files_involved.extend(diagnostic.files_involved());
         self.diagnostics.push(diagnostic);
     }
 
-    /// Returns `true` if diagnostics are fatal.
+    /// Allows a lint in a part of the source code. Diagnostics of the lint, that
+    /// were already reported in this part, are removed.
+    pub fn add_suppression(&mut self, suppression: LintSuppression) {
+        self.suppressions.push(suppression);
+
+        let mut diagnostics = std::mem::take(&mut self.diagnostics);
+        diagnostics.retain(|diagnostic| !self.suppress(diagnostic));
+        self.diagnostics = diagnostics;
+    }
+
+    /// Reports lint suppressions, that didn't silence any diagnostic. Must be
+    /// called after all lints are checked.
+    pub fn report_unused_suppressions(&mut self) {
+        let (used, unused): (Vec<_>, Vec<_>) = std::mem::take(&mut self.suppressions)
+            .into_iter()
+            .partition(|suppression| suppression.used);
+        self.suppressions = used;
+
+        for suppression in unused {
+            self.add_diagnostic(suppression.unused_diagnostic());
+        }
+    }
+
+    /// Returns `true` if a given diagnostic is reported by a lint, that is allowed
+    /// in the scope of some suppression, and marks the innermost one as used.
+    /// Forbidden lints are never suppressed, but the suppression is still marked
+    /// as used, because the diagnostic explains why it has no effect.
+    fn suppress(&mut self, diagnostic: &Diagnostic) -> bool {
+        let Some(lint) = diagnostic.code.as_deref().and_then(find_lint_by_code) else {
+            return false;
+        };
+        let Some(location) = diagnostic.primary_location() else {
+            return false;
+        };
+        let forbidden = self.lint_levels_at(Some(location)).level(lint) == LintLevel::Forbid;
+
+        let Some(suppression) = self
+            .suppressions
+            .iter_mut()
+            .filter(|suppression| suppression.suppresses(lint, location))
+            .min_by_key(|suppression| suppression.scope.end.0 - suppression.scope.start.0)
+        else {
+            return false;
+        };
+
+        suppression.used = true;
+        !forbidden
+    }
+
+    /// Returns `true` if diagnostics are fatal, i.e. there are errors, including
+    /// lints with the `deny` or `forbid` level.
     #[inline]
     #[must_use]
     pub fn is_fatal(&self) -> bool {
@@ -278,3 +403,10 @@ pub trait BuildDiagnostic {
     #[must_use]
     fn build(self) -> Diagnostic;
 }
+
+impl BuildDiagnostic for Diagnostic {
+    #[inline]
+    fn build(self) -> Diagnostic {
+        self
+    }
+}
@@ -0,0 +1,356 @@
+//! Lints - warnings, whose levels can be configured.
+//!
+//! Every lint has a name, e.g. `unnecessary_parenthesized_type`, and belongs to a
+//! group, e.g. `style`. Levels of lints are set by name or by group in the `[lints]`
+//! table of the manifest and with command line flags, see [`LintLevels`]. The
+//! `warnings` group contains every lint, that would otherwise be a warning.
+//!
+//! Lints can also be allowed for a single item, method or statement with a
+//! directive comment, see [`LintSuppression`].
+//!
+//! Lints reported by the compiler itself are listed in [`BUILTIN_LINTS`]. Other
+//! lints, e.g. the ones checked on HIR, are declared where they are implemented
+//! and added to the registry with [`register_lint`], before manifests and source
+//! files are parsed, so that their names are known.
+
+use std::{fmt, str::FromStr};
+
+use parking_lot::RwLock;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use stellar_filesystem::location::Location;
+
+use crate::diagnostic::{Diagnostic, Label, Severity};
+
+/// A level of a lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LintLevel {
+    /// The lint is not reported.
+    Allow,
+    /// The lint is reported as a warning.
+    Warn,
+    /// The lint is reported as an error.
+    Deny,
+    /// The lint is reported as an error and its level cannot be lowered.
+    Forbid,
+}
+
+impl LintLevel {
+    /// Returns the name of the level, e.g. `deny`.
+    #[inline]
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+            Self::Forbid => "forbid",
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            "forbid" => Ok(Self::Forbid),
+            _ => Err(format!(
+                "unknown lint level `{s}`, expected `allow`, `warn`, `deny` or `forbid`"
+            )),
+        }
+    }
+}
+
+/// Information about a lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lint {
+    /// The name of the lint, e.g. `unnecessary_parenthesized_type`.
+    pub name: &'static str,
+
+    /// The code of diagnostics reported by the lint, e.g. `W002`.
+    pub code: &'static str,
+
+    /// The group, that the lint belongs to, e.g. `style`.
+    pub group: &'static str,
+
+    /// The level of the lint, unless it's configured.
+    pub default_level: LintLevel,
+
+    /// A short description of what the lint reports.
+    pub description: &'static str,
+}
+
+/// The group, that contains every lint at the `warn` level.
+pub const WARNINGS_GROUP: &str = "warnings";
+
+/// Reports links in docstrings, that cannot be resolved.
+pub const UNRESOLVED_DOC_LINK: Lint = Lint {
+    name: "unresolved_doc_link",
+    code: "W003",
+    group: "docs",
+    default_level: LintLevel::Warn,
+    description: "links in docstrings, that cannot be resolved",
+};
+
+/// Reports identifiers, that are keywords in the next edition.
+pub const NEXT_EDITION_KEYWORD: Lint = Lint {
+    name: "next_edition_keyword",
+    code: "W005",
+    group: "compatibility",
+    default_level: LintLevel::Warn,
+    description: "identifiers, that are keywords in the next edition",
+};
+
+/// Reports `allow` directives, that don't suppress any diagnostic.
+pub const UNUSED_ALLOW: Lint = Lint {
+    name: "unused_allow",
+    code: "W007",
+    group: "unused",
+    default_level: LintLevel::Warn,
+    description: "`allow` directives, that don't suppress any diagnostic",
+};
+
+/// Lints, that are reported by the compiler itself rather than by lint passes.
+/// They are always registered.
+pub const BUILTIN_LINTS: &[&Lint] = &[&UNRESOLVED_DOC_LINK, &NEXT_EDITION_KEYWORD, &UNUSED_ALLOW];
+
+/// Lints added with [`register_lint`].
+static REGISTERED_LINTS: RwLock<Vec<&'static Lint>> = RwLock::new(Vec::new());
+
+/// Adds a lint to the registry, so that its level can be configured and it can
+/// be allowed with directives. Registering the same lint twice has no effect.
+///
+/// # Panics
+/// Panics if another lint with the same name or code is already registered.
+pub fn register_lint(lint: &'static Lint) {
+    let mut registered = REGISTERED_LINTS.write();
+
+    if let Some(known) = BUILTIN_LINTS
+        .iter()
+        .chain(registered.iter())
+        .find(|known| known.name == lint.name || known.code == lint.code)
+    {
+        assert_eq!(
+            *known, lint,
+            "lint `{}` conflicts with the registered lint `{}`",
+            lint.name, known.name
+        );
+        return;
+    }
+
+    registered.push(lint);
+}
+
+/// Returns all registered lints, including [`BUILTIN_LINTS`].
+#[must_use]
+pub fn lints() -> Vec<&'static Lint> {
+    BUILTIN_LINTS
+        .iter()
+        .copied()
+        .chain(REGISTERED_LINTS.read().iter().copied())
+        .collect()
+}
+
+/// Returns the registered lint with a given name.
+#[must_use]
+pub fn find_lint(name: &str) -> Option<&'static Lint> {
+    lints().into_iter().find(|lint| lint.name == name)
+}
+
+/// Returns the registered lint, that reports diagnostics with a given code.
+#[must_use]
+pub fn find_lint_by_code(code: &str) -> Option<&'static Lint> {
+    lints().into_iter().find(|lint| lint.code == code)
+}
+
+/// Returns names of all registered lints and their groups.
+pub fn lint_and_group_names() -> impl Iterator<Item = &'static str> + Clone {
+    let lints = lints();
+    let mut groups = lints.iter().map(|lint| lint.group).collect::<Vec<_>>();
+    groups.sort_unstable();
+    groups.dedup();
+
+    lints
+        .into_iter()
+        .map(|lint| lint.name)
+        .chain(groups)
+        .chain([WARNINGS_GROUP])
+}
+
+/// Returns `true` if a given name is a name of a registered lint or a lint group.
+#[must_use]
+pub fn is_lint_or_group(name: &str) -> bool {
+    lint_and_group_names().any(|known| known == name)
+}
+
+/// Levels of lints configured by name or by group.
+///
+/// Levels are applied in the order, in which they are set, so that a level set
+/// later overrides an earlier one, e.g. `style = "deny"` followed by
+/// `unnecessary_grouped_pattern = "allow"` denies every style lint except one.
+/// The level of a forbidden lint cannot be changed.
+///
+/// Levels can be capped, like with `--cap-lints` in cargo, so that lints of
+/// dependencies never fail the build, e.g. a lint set to `deny` is reported as
+/// a warning, if levels are capped at `warn`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintLevels {
+    levels: Vec<(String, LintLevel)>,
+    cap: Option<LintLevel>,
+}
+
+impl LintLevels {
+    /// Returns lint levels, that keep default levels of all lints.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            levels: Vec::new(),
+            cap: None,
+        }
+    }
+
+    /// Builds new lint levels, that are never higher than a given level.
+    #[inline]
+    #[must_use]
+    pub const fn with_cap(mut self, cap: LintLevel) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Builds new lint levels with a given level of a lint or a lint group.
+    #[inline]
+    #[must_use]
+    pub fn with_level(mut self, name: impl Into<String>, level: LintLevel) -> Self {
+        self.set_level(name, level);
+        self
+    }
+
+    /// Sets the level of a lint or a lint group.
+    #[inline]
+    pub fn set_level(&mut self, name: impl Into<String>, level: LintLevel) {
+        self.levels.push((name.into(), level));
+    }
+
+    /// Returns the effective level of a given lint, that is capped, if the levels
+    /// have a cap.
+    #[must_use]
+    pub fn level(&self, lint: &Lint) -> LintLevel {
+        let mut effective = lint.default_level;
+
+        for (name, level) in &self.levels {
+            if effective == LintLevel::Forbid {
+                break;
+            }
+
+            if name == lint.name
+                || name == lint.group
+                || (name == WARNINGS_GROUP && effective == LintLevel::Warn)
+            {
+                effective = *level;
+            }
+        }
+
+        self.cap.map_or(effective, |cap| effective.min(cap))
+    }
+
+    /// Applies the level of the lint, that reported a given diagnostic. Returns
+    /// `None`, if the lint is allowed. Diagnostics, that are not reported by
+    /// lints, are returned as is.
+    #[must_use]
+    pub fn apply(&self, mut diagnostic: Diagnostic) -> Option<Diagnostic> {
+        let Some(lint) = diagnostic.code.as_deref().and_then(find_lint_by_code) else {
+            return Some(diagnostic);
+        };
+
+        match self.level(lint) {
+            LintLevel::Allow => return None,
+            LintLevel::Warn => diagnostic.severity = Severity::Warning,
+            level @ (LintLevel::Deny | LintLevel::Forbid) => {
+                diagnostic.severity = Severity::Error;
+                diagnostic
+                    .notes
+                    .push(format!("note: `{}` is set to `{level}`", lint.name));
+            }
+        }
+
+        Some(diagnostic)
+    }
+}
+
+/// A lint or a lint group allowed in a part of the source code with a directive
+/// comment, e.g.:
+///
+/// ```txt
+/// // stellar: allow(unnecessary_parenthesized_type)
+/// fun parse(input: (String)) {}
+/// ```
+///
+/// Forbidden lints cannot be allowed this way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintSuppression {
+    /// The name of the allowed lint or lint group.
+    pub name: String,
+
+    /// The location of the name in the directive.
+    pub location: Location,
+
+    /// The location of the item, method or statement, in which the lint is allowed.
+    pub scope: Location,
+
+    /// Whether the suppression silenced at least one diagnostic.
+    pub used: bool,
+}
+
+impl LintSuppression {
+    /// Creates a new unused lint suppression.
+    #[inline]
+    #[must_use]
+    pub const fn new(name: String, location: Location, scope: Location) -> Self {
+        Self {
+            name,
+            location,
+            scope,
+            used: false,
+        }
+    }
+
+    /// Returns `true` if the suppression silences a given lint reported at
+    /// a given location.
+    #[must_use]
+    pub fn suppresses(&self, lint: &Lint, location: Location) -> bool {
+        let name = self.name.as_str();
+
+        ([lint.name, lint.group].contains(&name)
+            || (name == WARNINGS_GROUP && lint.default_level == LintLevel::Warn))
+            && self.scope.filepath == location.filepath
+            && self.scope.start <= location.start
+            && location.end <= self.scope.end
+    }
+
+    /// Returns a diagnostic, that reports the suppression as unused.
+    #[must_use]
+    pub fn unused_diagnostic(&self) -> Diagnostic {
+        Diagnostic::warning()
+            .with_code(UNUSED_ALLOW.code)
+            .with_message(format!(
+                "`{}` is allowed, but never reported here",
+                self.name
+            ))
+            .with_label(
+                Label::primary(self.location).with_message("help: remove it from the directive"),
+            )
+    }
+}
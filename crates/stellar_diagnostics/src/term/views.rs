@@ -4,7 +4,7 @@ use stellar_filesystem::in_memory_file_storage::InMemoryFileStorage;
 use stellar_filesystem::location::{ByteOffset, Location};
 use stellar_interner::PathId;
 
-use crate::diagnostic::{Diagnostic, LabelStyle};
+use crate::diagnostic::{Diagnostic, LabelStyle, Suggestion};
 use crate::files::{DiagnosticsRenderHelper, Error, ResolvedLocation};
 use crate::term::renderer::{Locus, MultiLabel, Renderer, SingleLabel};
 use crate::term::Config;
@@ -422,7 +422,10 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
 
             // Check to see if we should render a trailing border after the
             // final line of the snippet.
-            if labeled_files.peek().is_none() && self.diagnostic.notes.is_empty() {
+            if labeled_files.peek().is_none()
+                && self.diagnostic.notes.is_empty()
+                && self.diagnostic.suggestions.is_empty()
+            {
                 // We don't render a border if we are at the final newline
                 // without trailing notes, because it would end up looking too
                 // spaced-out in combination with the final new line.
@@ -446,6 +449,15 @@ impl<'d, 'c> RichDiagnostic<'d, 'c> {
         for note in &self.diagnostic.notes {
             renderer.render_snippet_note(outer_padding, note)?;
         }
+
+        // Suggestions
+        //
+        // ```text
+        // = help: consider importing `std.option.Option`: `import std.option.Option;`
+        // ```
+        for suggestion in &self.diagnostic.suggestions {
+            renderer.render_snippet_note(outer_padding, &render_suggestion(suggestion))?;
+        }
         renderer.render_empty()
     }
 }
@@ -517,8 +529,26 @@ impl<'d> ShortDiagnostic<'d> {
             for note in &self.diagnostic.notes {
                 renderer.render_snippet_note(0, note)?;
             }
+
+            for suggestion in &self.diagnostic.suggestions {
+                renderer.render_snippet_note(0, &render_suggestion(suggestion))?;
+            }
         }
 
         Ok(())
     }
 }
+
+/// Formats a suggestion as a `help` note.
+fn render_suggestion(suggestion: &Suggestion) -> String {
+    match suggestion.edits.as_slice() {
+        [edit] if !edit.replacement.trim().is_empty() => {
+            format!(
+                "help: {}: `{}`",
+                suggestion.message,
+                edit.replacement.trim()
+            )
+        }
+        _ => format!("help: {}", suggestion.message),
+    }
+}
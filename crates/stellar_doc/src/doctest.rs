@@ -0,0 +1,465 @@
+//! Checks code examples in docstrings (doctests).
+//!
+//! Every fenced code block marked with `stellar` is a doctest:
+//!
+//! ````txt
+//! /// ```stellar
+//! /// import std.option.Option;
+//! ///
+//! /// let a = Option.Some(1);
+//! /// ```
+//! ````
+//!
+//! The code block is wrapped into a synthetic module of a separate package, that
+//! depends on the documented one, so that the documented items can be imported.
+//! If the code block doesn't define any items, everything except imports is wrapped
+//! into `fun main() { ... }`. The module is then parsed, lowered and goes through
+//! name resolution, and locations of the reported diagnostics are mapped back into
+//! the doc comment.
+//!
+//! Bodies of functions are not type-checked yet, so a doctest only fails because of
+//! syntax errors and unresolved names and imports.
+//!
+//! Code blocks support the following flags, e.g. ```` ```stellar,should_fail ````:
+//! * `ignore` - the doctest is not checked.
+//! * `should_fail` - the doctest passes only if checking the code produces errors.
+
+use std::mem;
+
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{ModuleId, PackageData, PackageId, Path, State};
+use stellar_diagnostics::{diagnostic::Diagnostic, Diagnostics};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_hir::Docstring;
+use stellar_interner::{IdentifierId, PathId};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    collect_definitions::CollectDefinitions, resolve_imports::ResolveImports,
+};
+
+/// Keywords, that start module items. If one of the lines of a code block starts
+/// with such a keyword, the code block is not wrapped into `main`.
+const ITEM_KEYWORDS: [&str; 6] = ["fun ", "pub ", "struct ", "enum ", "interface ", "type "];
+
+/// A code example found in a docstring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Doctest {
+    /// The path of the documented module or item, e.g. `std.option.Option`.
+    pub path: Path,
+
+    /// The location of the opening fence of the code block.
+    pub location: Location,
+
+    /// The code of the example.
+    pub code: String,
+
+    /// Locations of every line of the code in the doc comment.
+    pub lines: Vec<Location>,
+
+    /// Whether the code block is marked with `ignore`.
+    pub ignore: bool,
+
+    /// Whether the code block is marked with `should_fail`.
+    pub should_fail: bool,
+}
+
+/// The result of checking a doctest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DoctestOutcome {
+    /// The code was checked successfully, or failed to check as expected.
+    Passed,
+
+    /// The doctest is marked with `ignore`.
+    Ignored,
+
+    /// Checking the code produced errors.
+    ///
+    /// Locations of the diagnostics are mapped into the doc comment.
+    Failed(Diagnostics),
+
+    /// The doctest is marked with `should_fail`, but checking the code produced no errors.
+    UnexpectedlyPassed,
+}
+
+impl DoctestOutcome {
+    /// Returns `true` if the doctest passed or was ignored.
+    #[inline]
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        matches!(self, Self::Passed | Self::Ignored)
+    }
+}
+
+/// Collects doctests from docstrings of all given modules, including docstrings of
+/// private items.
+///
+/// Doctests are sorted by the paths of the documented items.
+#[must_use]
+pub fn collect_doctests<'h>(
+    db: &stellar_database::Database,
+    modules: impl IntoIterator<Item = (&'h ModuleId, &'h stellar_hir::Module)>,
+) -> Vec<Doctest> {
+    let mut doctests = vec![];
+
+    for (module, hir) in modules {
+        for (path, docstring) in docstrings(module.path(db), hir) {
+            extract_doctests(&path, docstring, &mut doctests);
+        }
+    }
+
+    doctests.sort_by(|a, b| {
+        (a.path.to_string(), a.location.start).cmp(&(b.path.to_string(), b.location.start))
+    });
+
+    doctests
+}
+
+/// Checks a given doctest of a package.
+///
+/// Definitions of the package must already be collected and its imports resolved.
+/// The doctest is checked in a copy of the state, so that the synthetic package
+/// and diagnostics of one doctest never leak into the state or into other doctests.
+#[must_use]
+pub fn run_doctest(state: &State, package: PackageId, doctest: &Doctest) -> DoctestOutcome {
+    if doctest.ignore {
+        return DoctestOutcome::Ignored;
+    }
+
+    let mut state = state.clone();
+    *state.diagnostics_mut() =
+        Diagnostics::new().with_lint_levels(state.diagnostics().lint_levels.clone());
+    let state = &mut state;
+
+    let (source, line_map) = synthetic_source(&doctest.code);
+    let filepath = PathId::from(format!(
+        "{} (doctest at {})",
+        doctest.location.filepath, doctest.location.start
+    ));
+
+    let doctest_package =
+        PackageData::alloc(state.db_mut(), IdentifierId::from("doctest"), filepath);
+    let package_name = package.name(state.db());
+    doctest_package.add_dependency(state.db_mut(), package_name, package);
+
+    let module = parse_module(
+        state,
+        doctest_package,
+        Path::new(vec![IdentifierId::from("doctest")]),
+        filepath,
+        &source,
+    );
+    doctest_package.set_root_module(state.db_mut(), module.module());
+
+    let hir = LowerToHir::run_all(vec![module]);
+
+    CollectDefinitions::run_all(state, &hir);
+    ResolveImports::run_all(state, &hir);
+
+    let mut diagnostics = mem::take(state.diagnostics_mut());
+    map_diagnostics(&mut diagnostics, filepath, &source, &line_map, doctest);
+
+    match (diagnostics.is_fatal(), doctest.should_fail) {
+        (false, false) | (true, true) => DoctestOutcome::Passed,
+        (true, false) => DoctestOutcome::Failed(diagnostics),
+        (false, true) => DoctestOutcome::UnexpectedlyPassed,
+    }
+}
+
+/// Returns all docstrings of a module and its items together with the paths of the
+/// documented entities.
+fn docstrings<'h>(module_path: &Path, hir: &'h stellar_hir::Module) -> Vec<(Path, &'h Docstring)> {
+    let mut docstrings = vec![];
+    let mut add = |path: Path, docstring: Option<&'h Docstring>| {
+        if let Some(docstring) = docstring {
+            docstrings.push((path, docstring));
+        }
+    };
+
+    add(module_path.clone(), hir.docstring.as_ref());
+
+    for item in &hir.items {
+        let Some(name) = item.name() else {
+            continue;
+        };
+
+        let path = module_path.clone() + name;
+
+        match item {
+            stellar_hir::ModuleItem::Enum(enum_) => {
+                add(path.clone(), enum_.docstring.as_ref());
+
+                for item in &enum_.items {
+                    let item_path = path.clone() + item.name_id();
+
+                    match item {
+                        stellar_hir::EnumItem::Just { docstring, .. }
+                        | stellar_hir::EnumItem::TupleLike { docstring, .. } => {
+                            add(item_path, docstring.as_ref());
+                        }
+                        stellar_hir::EnumItem::Struct {
+                            docstring, fields, ..
+                        } => {
+                            add(item_path.clone(), docstring.as_ref());
+
+                            for field in fields {
+                                add(item_path.clone() + field.name.id, field.docstring.as_ref());
+                            }
+                        }
+                    }
+                }
+
+                for method in &enum_.methods {
+                    add(
+                        path.clone() + method.signature.name.id,
+                        method.signature.docstring.as_ref(),
+                    );
+                }
+            }
+            stellar_hir::ModuleItem::Function(function) => {
+                add(path, function.signature.docstring.as_ref());
+            }
+            stellar_hir::ModuleItem::Interface(interface) => {
+                add(path.clone(), interface.docstring.as_ref());
+
+                for method in &interface.methods {
+                    add(
+                        path.clone() + method.signature.name.id,
+                        method.signature.docstring.as_ref(),
+                    );
+                }
+            }
+            stellar_hir::ModuleItem::Struct(struct_) => {
+                add(path.clone(), struct_.docstring.as_ref());
+
+                for field in &struct_.fields {
+                    add(path.clone() + field.name.id, field.docstring.as_ref());
+                }
+
+                for method in &struct_.methods {
+                    add(
+                        path.clone() + method.signature.name.id,
+                        method.signature.docstring.as_ref(),
+                    );
+                }
+            }
+            stellar_hir::ModuleItem::TupleLikeStruct(struct_) => {
+                add(path.clone(), struct_.docstring.as_ref());
+
+                for method in &struct_.methods {
+                    add(
+                        path.clone() + method.signature.name.id,
+                        method.signature.docstring.as_ref(),
+                    );
+                }
+            }
+            stellar_hir::ModuleItem::TypeAlias(alias) => {
+                add(path, alias.docstring.as_ref());
+            }
+            stellar_hir::ModuleItem::Import { .. } => {}
+        }
+    }
+
+    docstrings
+}
+
+/// Extracts doctests from a given docstring.
+fn extract_doctests(path: &Path, docstring: &Docstring, doctests: &mut Vec<Doctest>) {
+    // `None` - outside of a code block, `Some(None)` - inside of a code block, that
+    // is not a doctest, `Some(Some(..))` - inside of a doctest.
+    let mut current: Option<Option<Doctest>> = None;
+    let mut offset = 0;
+
+    for line in docstring.as_str().split('\n') {
+        let range = offset..offset + line.len();
+        offset = range.end + 1;
+
+        // Lines of a docstring never span over multiple doc comments.
+        let Some(location) = docstring.location_of(range) else {
+            continue;
+        };
+
+        if let Some(info) = line.trim_start().strip_prefix("```") {
+            match current.take() {
+                None => {
+                    current = Some(parse_fence_info(info).map(|(ignore, should_fail)| Doctest {
+                        path: path.clone(),
+                        location,
+                        code: String::new(),
+                        lines: vec![],
+                        ignore,
+                        should_fail,
+                    }));
+                }
+                Some(doctest) => doctests.extend(doctest),
+            }
+
+            continue;
+        }
+
+        if let Some(Some(doctest)) = &mut current {
+            if !doctest.lines.is_empty() {
+                doctest.code.push('\n');
+            }
+
+            doctest.code.push_str(line);
+            doctest.lines.push(location);
+        }
+    }
+
+    // An unclosed code block lasts until the end of the docstring.
+    if let Some(doctest) = current {
+        doctests.extend(doctest);
+    }
+}
+
+/// Parses the info string of a code block fence, e.g. `stellar,ignore`, and returns
+/// `ignore` and `should_fail` flags, if the code block is a doctest.
+fn parse_fence_info(info: &str) -> Option<(bool, bool)> {
+    let flags = info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|flag| !flag.is_empty())
+        .collect::<Vec<_>>();
+
+    flags
+        .contains(&"stellar")
+        .then(|| (flags.contains(&"ignore"), flags.contains(&"should_fail")))
+}
+
+/// Returns the source code of the synthetic module for a given doctest code, and for
+/// every line of the source, the index of the corresponding line of the doctest code,
+/// if it exists.
+fn synthetic_source(code: &str) -> (String, Vec<Option<usize>>) {
+    let lines = code.split('\n').collect::<Vec<_>>();
+    let is_import = |line: &str| line.trim_start().starts_with("import ");
+
+    let defines_items = lines.iter().any(|line| {
+        let line = line.trim_start();
+
+        ITEM_KEYWORDS
+            .iter()
+            .any(|keyword| line.starts_with(keyword))
+    });
+
+    if defines_items {
+        return (code.to_owned(), (0..lines.len()).map(Some).collect());
+    }
+
+    let mut source = vec![];
+    let mut line_map = vec![];
+
+    for (idx, line) in lines.iter().enumerate() {
+        if is_import(line) {
+            source.push(*line);
+            line_map.push(Some(idx));
+        }
+    }
+
+    source.push("fun main() {");
+    line_map.push(None);
+
+    for (idx, line) in lines.iter().enumerate() {
+        if !is_import(line) {
+            source.push(*line);
+            line_map.push(Some(idx));
+        }
+    }
+
+    source.push("}");
+    line_map.push(None);
+
+    (source.join("\n"), line_map)
+}
+
+/// Maps locations of diagnostics in the synthetic module into the doc comment.
+fn map_diagnostics(
+    diagnostics: &mut Diagnostics,
+    filepath: PathId,
+    source: &str,
+    line_map: &[Option<usize>],
+    doctest: &Doctest,
+) {
+    let line_starts = source
+        .split('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len() + 1;
+            Some(start)
+        })
+        .collect::<Vec<_>>();
+
+    let map_offset = |offset: ByteOffset| {
+        let line = line_starts
+            .partition_point(|&start| start <= offset.0)
+            .saturating_sub(1);
+
+        match line_map.get(line).copied().flatten() {
+            Some(code_line) => {
+                let target = doctest.lines[code_line];
+
+                target.start + (offset.0 - line_starts[line]).min(target.end.0 - target.start.0)
+            }
+            None => doctest.location.start,
+        }
+    };
+
+    let map_location = |location: Location| {
+        if location.filepath != filepath {
+            return location;
+        }
+
+        let start = map_offset(location.start);
+
+        Location {
+            filepath: doctest.location.filepath,
+            start,
+            end: map_offset(location.end).max(start),
+        }
+    };
+
+    for diagnostic in &mut diagnostics.diagnostics {
+        for label in &mut diagnostic.labels {
+            label.location = map_location(label.location);
+        }
+
+        for suggestion in &mut diagnostic.suggestions {
+            for edit in &mut suggestion.edits {
+                edit.location = map_location(edit.location);
+            }
+        }
+    }
+
+    diagnostics.files_involved = diagnostics
+        .diagnostics
+        .iter()
+        .flat_map(Diagnostic::files_involved)
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::doctest::{parse_fence_info, synthetic_source};
+
+    #[test]
+    fn test_parse_fence_info() {
+        assert_eq!(parse_fence_info("stellar"), Some((false, false)));
+        assert_eq!(parse_fence_info("stellar,ignore"), Some((true, false)));
+        assert_eq!(parse_fence_info("stellar should_fail"), Some((false, true)));
+        assert_eq!(parse_fence_info("txt"), None);
+        assert_eq!(parse_fence_info(""), None);
+    }
+
+    #[test]
+    fn test_synthetic_source() {
+        assert_eq!(
+            synthetic_source("import a.b;\nlet x = 1;"),
+            (
+                "import a.b;\nfun main() {\nlet x = 1;\n}".to_owned(),
+                vec![Some(0), None, Some(1), None]
+            )
+        );
+        assert_eq!(
+            synthetic_source("fun main() {}"),
+            ("fun main() {}".to_owned(), vec![Some(0)])
+        );
+    }
+}
@@ -0,0 +1,473 @@
+//! Renders [`Documentation`] into a static HTML site.
+//!
+//! The layout of the generated site is:
+//!
+//! ```txt
+//! doc
+//! |_ index.html (redirects to the root module page)
+//! |_ search-index.js
+//! |_ static
+//! |  |_ style.css
+//! |  |_ search.js
+//! |_ std
+//!    |_ index.html (the root module page)
+//!    |_ option
+//!       |_ index.html
+//!       |_ enum.Option.html
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::json;
+use stellar_interner::IdentifierId;
+
+use crate::{
+    markdown::{render_markdown, summary},
+    model::{
+        Docstring, Documentation, ItemDocumentation, ItemKind, LinkTarget, MemberDocumentation,
+        ModuleDocumentation, Signature, SignatureFragment,
+    },
+};
+
+const STYLE: &str = include_str!("static/style.css");
+const SEARCH_SCRIPT: &str = include_str!("static/search.js");
+
+/// Generates a static HTML site for a given documentation in a given directory.
+///
+/// # Errors
+/// Returns an error if one of the files cannot be written.
+pub fn generate_html(documentation: &Documentation, output_directory: &Path) -> io::Result<()> {
+    let generator = HtmlGenerator {
+        documentation,
+        output_directory: output_directory.to_owned(),
+    };
+
+    generator.write(PathBuf::from("static/style.css"), STYLE)?;
+    generator.write(PathBuf::from("static/search.js"), SEARCH_SCRIPT)?;
+    generator.write(PathBuf::from("search-index.js"), &generator.search_index())?;
+
+    if let Some(root_module) = documentation.modules.first() {
+        generator.write(
+            PathBuf::from("index.html"),
+            &format!(
+                "<!DOCTYPE html><meta http-equiv=\"refresh\" content=\"0; url={}\">",
+                module_url(&root_module.path)
+            ),
+        )?;
+    }
+
+    for module in &documentation.modules {
+        generator.write(
+            PathBuf::from(module_url(&module.path)),
+            &generator.module_page(module),
+        )?;
+
+        for item in &module.items {
+            generator.write(
+                PathBuf::from(item_url(item.kind, &item.path)),
+                &generator.item_page(module, item),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+struct HtmlGenerator<'d> {
+    documentation: &'d Documentation,
+    output_directory: PathBuf,
+}
+
+impl HtmlGenerator<'_> {
+    fn write(&self, relative_path: PathBuf, contents: &str) -> io::Result<()> {
+        let path = self.output_directory.join(relative_path);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, contents)
+    }
+
+    fn search_index(&self) -> String {
+        let entries = self
+            .documentation
+            .modules
+            .iter()
+            .map(|module| {
+                json!({
+                    "name": module.path.segments().last().map(|name| name.as_str()),
+                    "path": module.path.to_string(),
+                    "kind": "module",
+                    "url": module_url(&module.path),
+                    "summary": module.docstring.as_ref().map(|docstring| summary(docstring.as_str())).unwrap_or_default(),
+                })
+            })
+            .chain(self.documentation.items().map(|item| {
+                json!({
+                    "name": item.name.as_str(),
+                    "path": item.path.to_string(),
+                    "kind": item.kind.as_str(),
+                    "url": item_url(item.kind, &item.path),
+                    "summary": item.docstring.as_ref().map(|docstring| summary(docstring.as_str())).unwrap_or_default(),
+                })
+            }))
+            .collect::<Vec<_>>();
+
+        format!(
+            "window.SEARCH_INDEX = {};\n",
+            serde_json::Value::Array(entries)
+        )
+    }
+
+    fn module_page(&self, module: &ModuleDocumentation) -> String {
+        let root = relative_root(module.path.segments().len());
+        let mut content = String::new();
+
+        content.push_str(&format!(
+            "<h1>Module {}</h1>",
+            breadcrumbs(&root, module.path.segments())
+        ));
+
+        if let Some(docstring) = &module.docstring {
+            content.push_str(&format!(
+                "<div class=\"docblock\">{}</div>",
+                render_docstring(&root, docstring.as_str(), docstring)
+            ));
+        }
+
+        if !module.submodules.is_empty() {
+            content.push_str("<h2 id=\"modules\">Modules</h2><table class=\"items\">");
+
+            for submodule in &module.submodules {
+                let summary = self
+                    .documentation
+                    .modules
+                    .iter()
+                    .find(|documented| &documented.path == submodule)
+                    .and_then(|documented| documented.docstring.as_ref());
+
+                write_listing_row(
+                    &mut content,
+                    &root,
+                    &format!("{root}{}", module_url(submodule)),
+                    submodule.segments().last().map_or("", |name| name.as_str()),
+                    summary,
+                );
+            }
+
+            content.push_str("</table>");
+        }
+
+        let mut kind = None;
+
+        for item in &module.items {
+            if kind.map(ItemKind::plural_title) != Some(item.kind.plural_title()) {
+                if kind.is_some() {
+                    content.push_str("</table>");
+                }
+
+                content.push_str(&format!(
+                    "<h2 id=\"{0}\">{1}</h2><table class=\"items\">",
+                    item.kind.as_str(),
+                    item.kind.plural_title()
+                ));
+
+                kind = Some(item.kind);
+            }
+
+            write_listing_row(
+                &mut content,
+                &root,
+                &format!("{root}{}", item_url(item.kind, &item.path)),
+                item.name.as_str(),
+                item.docstring.as_ref(),
+            );
+        }
+
+        if kind.is_some() {
+            content.push_str("</table>");
+        }
+
+        self.page(&root, &format!("{} - Stellar", module.path), &content)
+    }
+
+    fn item_page(&self, module: &ModuleDocumentation, item: &ItemDocumentation) -> String {
+        let root = relative_root(module.path.segments().len());
+        let mut content = String::new();
+
+        content.push_str(&format!(
+            "<h1>{} {}</h1><pre class=\"signature\">{}</pre>",
+            item.kind.title(),
+            breadcrumbs(&root, item.path.segments()),
+            render_signature(&root, &item.signature)
+        ));
+
+        if let Some(docstring) = &item.docstring {
+            content.push_str(&format!(
+                "<div class=\"docblock\">{}</div>",
+                render_docstring(&root, docstring.as_str(), docstring)
+            ));
+        }
+
+        write_members(&mut content, &root, "fields", "Fields", &item.fields);
+        write_members(&mut content, &root, "items", "Items", &item.enum_items);
+        write_members(&mut content, &root, "methods", "Methods", &item.methods);
+
+        for (id, title, interfaces) in [
+            ("inherits", "Inherited interfaces", &item.inherits),
+            ("implements", "Implemented interfaces", &item.implements),
+        ] {
+            if interfaces.is_empty() {
+                continue;
+            }
+
+            content.push_str(&format!(
+                "<h2 id=\"{id}\">{title}</h2><ul class=\"implements\">"
+            ));
+
+            for interface in interfaces {
+                content.push_str(&format!(
+                    "<li><code>{}</code></li>",
+                    render_signature(&root, interface)
+                ));
+            }
+
+            content.push_str("</ul>");
+        }
+
+        self.page(&root, &format!("{} - Stellar", item.path), &content)
+    }
+
+    fn page(&self, root: &str, title: &str, content: &str) -> String {
+        let mut sidebar = String::new();
+
+        sidebar.push_str(&format!(
+            "<a class=\"package\" href=\"{root}{}\">{}</a><ul>",
+            self.documentation
+                .modules
+                .first()
+                .map(|module| module_url(&module.path))
+                .unwrap_or_default(),
+            escape(self.documentation.package_name.as_str())
+        ));
+
+        for module in &self.documentation.modules {
+            sidebar.push_str(&format!(
+                "<li><a href=\"{root}{}\">{}</a></li>",
+                module_url(&module.path),
+                escape(&module.path.to_string())
+            ));
+        }
+
+        sidebar.push_str("</ul>");
+
+        format!(
+            "<!DOCTYPE html>\
+<html lang=\"en\">\
+<head>\
+<meta charset=\"utf-8\">\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+<title>{title}</title>\
+<link rel=\"stylesheet\" href=\"{root}static/style.css\">\
+<script src=\"{root}search-index.js\"></script>\
+<script src=\"{root}static/search.js\" defer></script>\
+</head>\
+<body data-root=\"{root}\">\
+<nav class=\"sidebar\">{sidebar}</nav>\
+<main>\
+<input id=\"search\" type=\"search\" placeholder=\"Search...\" autocomplete=\"off\">\
+<ul id=\"search-results\" hidden></ul>\
+<div id=\"content\">{content}</div>\
+</main>\
+</body>\
+</html>\n",
+            title = escape(title)
+        )
+    }
+}
+
+fn write_listing_row(
+    content: &mut String,
+    root: &str,
+    url: &str,
+    name: &str,
+    docstring: Option<&Docstring>,
+) {
+    content.push_str(&format!(
+        "<tr><td><a href=\"{url}\">{}</a></td><td>{}</td></tr>",
+        escape(name),
+        docstring
+            .map(|docstring| render_docstring(root, summary(docstring.as_str()), docstring))
+            .unwrap_or_default()
+    ));
+}
+
+/// Renders a given part of a docstring, hyperlinking its intra-doc links.
+fn render_docstring(root: &str, text: &str, docstring: &Docstring) -> String {
+    render_markdown(text, |path| {
+        docstring
+            .links
+            .iter()
+            .find(|link| link.path == path)
+            .map(|link| format!("{root}{}", target_url(&link.target)))
+    })
+}
+
+fn write_members(
+    content: &mut String,
+    root: &str,
+    id: &str,
+    title: &str,
+    members: &[MemberDocumentation],
+) {
+    if members.is_empty() {
+        return;
+    }
+
+    content.push_str(&format!("<h2 id=\"{id}\">{title}</h2>"));
+
+    for member in members {
+        let anchor = format!("{}.{}", id.trim_end_matches('s'), member.name);
+
+        content.push_str(&format!(
+            "<div class=\"member\" id=\"{anchor}\"><pre class=\"signature\">{}</pre>",
+            render_signature(root, &member.signature)
+        ));
+
+        if let Some(docstring) = &member.docstring {
+            content.push_str(&format!(
+                "<div class=\"docblock\">{}</div>",
+                render_docstring(root, docstring.as_str(), docstring)
+            ));
+        }
+
+        content.push_str("</div>");
+    }
+}
+
+fn render_signature(root: &str, signature: &Signature) -> String {
+    let mut html = String::new();
+
+    for fragment in &signature.fragments {
+        match fragment {
+            SignatureFragment::Keyword(keyword) => {
+                html.push_str(&format!("<span class=\"keyword\">{keyword}</span>"));
+            }
+            SignatureFragment::Text(text) => html.push_str(&escape(text)),
+            SignatureFragment::Link { text, target } => {
+                html.push_str(&format!(
+                    "<a href=\"{root}{}\">{}</a>",
+                    target_url(target),
+                    escape(text)
+                ));
+            }
+        }
+    }
+
+    html
+}
+
+/// Renders a path, where every module segment is a link to the module page.
+fn breadcrumbs(root: &str, segments: &[IdentifierId]) -> String {
+    let mut html = String::new();
+
+    for idx in 0..segments.len() {
+        if idx != 0 {
+            html.push('.');
+        }
+
+        if idx + 1 == segments.len() {
+            html.push_str(&format!(
+                "<span class=\"name\">{}</span>",
+                escape(segments[idx].as_str())
+            ));
+        } else {
+            html.push_str(&format!(
+                "<a href=\"{root}{}index.html\">{}</a>",
+                directory(&segments[..=idx]),
+                escape(segments[idx].as_str())
+            ));
+        }
+    }
+
+    html
+}
+
+/// Returns the URL of a module page relative to the documentation root.
+fn module_url(path: &stellar_database::Path) -> String {
+    let mut url = directory(path.segments());
+    url.push_str("index.html");
+    url
+}
+
+/// Returns the URL of an item page relative to the documentation root.
+fn item_url(kind: ItemKind, path: &stellar_database::Path) -> String {
+    let Some((name, module)) = path.segments().split_last() else {
+        return String::new();
+    };
+
+    let mut url = directory(module);
+    url.push_str(&format!("{}.{name}.html", kind.as_str()));
+    url
+}
+
+/// Returns the URL of a link target relative to the documentation root.
+fn target_url(target: &LinkTarget) -> String {
+    match target {
+        LinkTarget::Module(path) => module_url(path),
+        LinkTarget::Item(kind, path) => item_url(*kind, path),
+        LinkTarget::EnumItem(path) => {
+            let Some((name, enum_path)) = path.segments().split_last() else {
+                return String::new();
+            };
+
+            format!(
+                "{}#item.{name}",
+                item_url(
+                    ItemKind::Enum,
+                    &stellar_database::Path::new(enum_path.to_vec())
+                )
+            )
+        }
+    }
+}
+
+/// Returns a directory corresponding to a given module path, e.g. `std/option/`.
+fn directory(segments: &[IdentifierId]) -> String {
+    let mut directory = String::new();
+
+    for segment in segments {
+        directory.push_str(segment.as_str());
+        directory.push('/');
+    }
+
+    directory
+}
+
+/// Returns a relative path to the documentation root from a directory
+/// with a given depth.
+fn relative_root(depth: usize) -> String {
+    "../".repeat(depth)
+}
+
+/// Escapes special HTML characters.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,224 @@
+//! Renders [`Documentation`] into a JSON description of a package's public API,
+//! that is meant to be consumed by external tools.
+//!
+//! The format is versioned by [`FORMAT_VERSION`], which is bumped every time
+//! the format changes in an incompatible way. The top-level object is:
+//!
+//! ```json
+//! {
+//!     "format_version": 1,
+//!     "package": "std",
+//!     "modules": [
+//!         {
+//!             "path": "std.option",
+//!             "docstring": { "text": "...", "links": [] },
+//!             "submodules": [],
+//!             "items": [
+//!                 {
+//!                     "kind": "enum",
+//!                     "name": "Option",
+//!                     "path": "std.option.Option",
+//!                     "location": { "file": "std/option.sr", "start": 42, "end": 48 },
+//!                     "docstring": null,
+//!                     "signature": { "text": "pub enum Option[T]", "fragments": [...] },
+//!                     "generic_parameters": [...],
+//!                     "predicates": [],
+//!                     "implements": [],
+//!                     "inherits": [],
+//!                     "resolved_signature": null,
+//!                     "fields": [],
+//!                     "enum_items": [...],
+//!                     "methods": [...]
+//!                 }
+//!             ]
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Signatures, generic parameters, predicates, implemented and inherited
+//! interfaces describe the item as written in the source code. If the type
+//! checker has analyzed the item's signature, `resolved_signature` contains
+//! its predicates and implemented interfaces with fully qualified paths:
+//!
+//! ```json
+//! {
+//!     "predicates": [{ "type": "T", "bounds": ["std.string.ToString"] }],
+//!     "implements": ["std.iterator.Iterator[T]"]
+//! }
+//! ```
+//!
+//! Locations are byte offsets in the source file. Modules and items are
+//! sorted in the same way as in [`Documentation`], so the output is
+//! deterministic.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+use stellar_filesystem::location::Location;
+
+use crate::model::{
+    Docstring, Documentation, GenericParameterDocumentation, ItemDocumentation, ItemKind,
+    LinkTarget, MemberDocumentation, ModuleDocumentation, PredicateDocumentation,
+    ResolvedSignature, Signature, SignatureFragment,
+};
+
+/// The version of the JSON format.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Returns a JSON description of a given documentation.
+#[must_use]
+pub fn documentation_to_json(documentation: &Documentation) -> Value {
+    json!({
+        "format_version": FORMAT_VERSION,
+        "package": documentation.package_name.as_str(),
+        "modules": documentation.modules.iter().map(module).collect::<Vec<_>>(),
+    })
+}
+
+/// Writes a JSON description of a given documentation into `<package name>.json`
+/// in a given directory and returns the path to the written file.
+///
+/// # Errors
+/// Returns an error if the file cannot be written.
+pub fn generate_json(
+    documentation: &Documentation,
+    output_directory: &Path,
+) -> io::Result<PathBuf> {
+    fs::create_dir_all(output_directory)?;
+
+    let path = output_directory.join(format!("{}.json", documentation.package_name));
+
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&documentation_to_json(documentation))?,
+    )?;
+
+    Ok(path)
+}
+
+fn module(module: &ModuleDocumentation) -> Value {
+    json!({
+        "path": module.path.to_string(),
+        "docstring": module.docstring.as_ref().map(docstring),
+        "submodules": module.submodules.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        "items": module.items.iter().map(item).collect::<Vec<_>>(),
+    })
+}
+
+fn item(item: &ItemDocumentation) -> Value {
+    json!({
+        "kind": item_kind(item.kind),
+        "name": item.name.as_str(),
+        "path": item.path.to_string(),
+        "location": location(item.location),
+        "docstring": item.docstring.as_ref().map(docstring),
+        "signature": signature(&item.signature),
+        "generic_parameters": item.generic_parameters.iter().map(generic_parameter).collect::<Vec<_>>(),
+        "predicates": item.predicates.iter().map(predicate).collect::<Vec<_>>(),
+        "implements": item.implements.iter().map(signature).collect::<Vec<_>>(),
+        "inherits": item.inherits.iter().map(signature).collect::<Vec<_>>(),
+        "resolved_signature": item.resolved_signature.as_ref().map(resolved_signature),
+        "fields": item.fields.iter().map(member).collect::<Vec<_>>(),
+        "enum_items": item.enum_items.iter().map(member).collect::<Vec<_>>(),
+        "methods": item.methods.iter().map(member).collect::<Vec<_>>(),
+    })
+}
+
+fn member(member: &MemberDocumentation) -> Value {
+    json!({
+        "name": member.name.as_str(),
+        "location": location(member.location),
+        "docstring": member.docstring.as_ref().map(docstring),
+        "signature": signature(&member.signature),
+        "generic_parameters": member.generic_parameters.iter().map(generic_parameter).collect::<Vec<_>>(),
+        "predicates": member.predicates.iter().map(predicate).collect::<Vec<_>>(),
+    })
+}
+
+fn generic_parameter(parameter: &GenericParameterDocumentation) -> Value {
+    json!({
+        "name": parameter.name.as_str(),
+        "location": location(parameter.location),
+        "bounds": parameter.bounds.iter().map(signature).collect::<Vec<_>>(),
+        "default_value": parameter.default_value.as_ref().map(signature),
+    })
+}
+
+fn predicate(predicate: &PredicateDocumentation) -> Value {
+    json!({
+        "type": signature(&predicate.ty),
+        "location": location(predicate.location),
+        "bounds": predicate.bounds.iter().map(signature).collect::<Vec<_>>(),
+    })
+}
+
+fn resolved_signature(signature: &ResolvedSignature) -> Value {
+    json!({
+        "predicates": signature.predicates.iter().map(|predicate| json!({
+            "type": predicate.ty,
+            "bounds": predicate.bounds,
+        })).collect::<Vec<_>>(),
+        "implements": signature.implements,
+    })
+}
+
+fn docstring(docstring: &Docstring) -> Value {
+    json!({
+        "text": docstring.as_str(),
+        "links": docstring.links.iter().map(|link| json!({
+            "path": link.path,
+            "location": location(link.location),
+            "target": link_target(&link.target),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn signature(signature: &Signature) -> Value {
+    json!({
+        "text": signature.text(),
+        "fragments": signature.fragments.iter().map(|fragment| match fragment {
+            SignatureFragment::Keyword(text) => json!({ "kind": "keyword", "text": text }),
+            SignatureFragment::Text(text) => json!({ "kind": "text", "text": text }),
+            SignatureFragment::Link { text, target } => json!({
+                "kind": "link",
+                "text": text,
+                "target": link_target(target),
+            }),
+        }).collect::<Vec<_>>(),
+    })
+}
+
+fn link_target(target: &LinkTarget) -> Value {
+    match target {
+        LinkTarget::Module(path) => json!({ "kind": "module", "path": path.to_string() }),
+        LinkTarget::Item(kind, path) => {
+            json!({ "kind": item_kind(*kind), "path": path.to_string() })
+        }
+        LinkTarget::EnumItem(path) => json!({ "kind": "enum_item", "path": path.to_string() }),
+    }
+}
+
+fn location(location: Location) -> Value {
+    json!({
+        "file": location.filepath.to_string(),
+        "start": location.start.0,
+        "end": location.end.0,
+    })
+}
+
+/// Unlike [`ItemKind::as_str`], tuple-like structs are distinguished from
+/// structs, because their fields are unnamed.
+const fn item_kind(kind: ItemKind) -> &'static str {
+    match kind {
+        ItemKind::Struct => "struct",
+        ItemKind::TupleLikeStruct => "tuple_like_struct",
+        ItemKind::Enum => "enum",
+        ItemKind::Interface => "interface",
+        ItemKind::Function => "function",
+        ItemKind::TypeAlias => "type_alias",
+    }
+}
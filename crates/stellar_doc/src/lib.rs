@@ -0,0 +1,83 @@
+//! This crate implements documentation generation for Stellar packages.
+//!
+//! Documentation is collected from the HIR of package modules into a
+//! [`Documentation`] model, that contains every public module item
+//! with its docstring and signature. The model is then rendered into
+//! a static HTML site by [`html::generate_html`] or into a versioned
+//! JSON description of the package's API by [`json::generate_json`].
+//!
+//! Only items of the documented package are hyperlinked in signatures,
+//! because documentation of dependencies is not generated.
+//!
+//! Code examples in docstrings are checked by [`doctest`].
+//!
+//! [`Documentation`]: model::Documentation
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
+    html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
+)]
+#![cfg_attr(not(test), forbid(clippy::unwrap_used))]
+#![warn(clippy::dbg_macro)]
+#![warn(
+    // rustc lint groups https://doc.rust-lang.org/rustc/lints/groups.html
+    future_incompatible,
+    let_underscore,
+    nonstandard_style,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    rust_2021_compatibility,
+    unused,
+    // rustc allowed-by-default lints https://doc.rust-lang.org/rustc/lints/listing/allowed-by-default.html
+    macro_use_extern_crate,
+    meta_variable_misuse,
+    missing_abi,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unsafe_op_in_unsafe_fn,
+    unused_crate_dependencies,
+    unused_import_braces,
+    unused_lifetimes,
+    unused_qualifications,
+    variant_size_differences,
+    // rustdoc lints https://doc.rust-lang.org/rustdoc/lints.html
+    rustdoc::broken_intra_doc_links,
+    rustdoc::private_intra_doc_links,
+    rustdoc::missing_crate_level_docs,
+    rustdoc::private_doc_tests,
+    rustdoc::invalid_codeblock_attributes,
+    rustdoc::invalid_rust_codeblocks,
+    rustdoc::bare_urls,
+    // clippy categories https://doc.rust-lang.org/clippy/
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::too_many_lines,
+    clippy::option_if_let_else,
+    clippy::redundant_pub_crate,
+    clippy::unnested_or_patterns,
+    clippy::format_push_string
+)]
+
+pub mod doctest;
+pub mod html;
+pub mod json;
+mod markdown;
+pub mod model;
+mod signature;
+
+pub use model::Documentation;
@@ -0,0 +1,69 @@
+//! Renders Markdown docstrings into HTML.
+
+use pulldown_cmark::{html::push_html, BrokenLink, CowStr, Options, Parser};
+
+/// Renders a Markdown docstring into HTML.
+///
+/// `resolve_link` returns URLs of intra-doc links, e.g. [`Option`], given their
+/// paths (`Option`). Links it cannot resolve are rendered as plain text.
+pub(crate) fn render_markdown(
+    source: &str,
+    resolve_link: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut html = String::new();
+    let mut callback = |link: BrokenLink<'_>| {
+        let path = link
+            .reference
+            .strip_prefix('`')
+            .and_then(|reference| reference.strip_suffix('`'))?;
+
+        resolve_link(path).map(|url| (CowStr::from(url), CowStr::from(path.to_owned())))
+    };
+
+    push_html(
+        &mut html,
+        Parser::new_with_broken_link_callback(
+            source,
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+            Some(&mut callback),
+        ),
+    );
+
+    html
+}
+
+/// Returns the first paragraph of a docstring, that is used as a short
+/// description of an item in listings and in the search index.
+pub(crate) fn summary(docstring: &str) -> &str {
+    let docstring = docstring.trim_start();
+
+    docstring
+        .find("\n\n")
+        .map_or(docstring, |end| &docstring[..end])
+        .trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::markdown::{render_markdown, summary};
+
+    #[test]
+    fn test_intra_doc_links() {
+        assert_eq!(
+            render_markdown("See [`Option`] and [`Result`].", |path| {
+                (path == "Option").then(|| "option.html".to_owned())
+            }),
+            "<p>See <a href=\"option.html\" title=\"Option\"><code>Option</code></a> and [<code>Result</code>].</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(summary(""), "");
+        assert_eq!(summary("Hello."), "Hello.");
+        assert_eq!(
+            summary("\nFirst line\nsecond line.\n\nDetails."),
+            "First line\nsecond line."
+        );
+    }
+}
@@ -0,0 +1,689 @@
+//! Defines [`Documentation`], a renderer independent description of a package's public API.
+
+use stellar_ast::Visibility;
+use stellar_database::{Database, ModuleId, PackageId, Path, SignatureId};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::IdentifierId;
+
+use crate::signature::{
+    resolved_type, resolved_type_constructor, split_inherited_interfaces, symbol_link_target,
+    SignatureBuilder,
+};
+
+/// Documentation of a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Documentation {
+    /// The name of the package.
+    pub package_name: IdentifierId,
+
+    /// Documented modules sorted by their paths.
+    pub modules: Vec<ModuleDocumentation>,
+}
+
+/// Documentation of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDocumentation {
+    /// The ID of the module in the database.
+    pub id: ModuleId,
+
+    /// The full path of the module, e.g. `std.option`.
+    pub path: Path,
+
+    /// The module docstring (`//!` comments).
+    pub docstring: Option<Docstring>,
+
+    /// Paths of submodules sorted alphabetically.
+    pub submodules: Vec<Path>,
+
+    /// Public module items sorted by their kind and name.
+    pub items: Vec<ItemDocumentation>,
+}
+
+/// Documentation of a public module item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemDocumentation {
+    /// The kind of the item.
+    pub kind: ItemKind,
+
+    /// The name of the item.
+    pub name: IdentifierId,
+
+    /// The full path of the item, e.g. `std.option.Option`.
+    pub path: Path,
+
+    /// The location of the item's name.
+    pub location: Location,
+
+    /// The item docstring.
+    pub docstring: Option<Docstring>,
+
+    /// The signature of the item, e.g. `pub struct Box[T]`.
+    pub signature: Signature,
+
+    /// Generic parameters of the item.
+    pub generic_parameters: Vec<GenericParameterDocumentation>,
+
+    /// Predicates in the where clause of the item.
+    pub predicates: Vec<PredicateDocumentation>,
+
+    /// Interfaces implemented by the item.
+    pub implements: Vec<Signature>,
+
+    /// Interfaces inherited by an interface, e.g. `Bar` in `interface Foo: Bar`.
+    pub inherits: Vec<Signature>,
+
+    /// The signature of the item as resolved by the type checker, if it was analyzed.
+    pub resolved_signature: Option<ResolvedSignature>,
+
+    /// Public fields of structs and tuple-like structs.
+    pub fields: Vec<MemberDocumentation>,
+
+    /// Items of enums.
+    pub enum_items: Vec<MemberDocumentation>,
+
+    /// Public methods. All methods of interfaces are public.
+    pub methods: Vec<MemberDocumentation>,
+}
+
+/// Documentation of a field, an enum item or a method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberDocumentation {
+    /// The name of the member. Fields of tuple-like structs are named by their index.
+    pub name: IdentifierId,
+
+    /// The location of the member.
+    pub location: Location,
+
+    /// The member docstring.
+    pub docstring: Option<Docstring>,
+
+    /// The signature of the member, e.g. `pub name: String`.
+    pub signature: Signature,
+
+    /// Generic parameters of the member. Only methods can have them.
+    pub generic_parameters: Vec<GenericParameterDocumentation>,
+
+    /// Predicates in the where clause of the member. Only methods can have them.
+    pub predicates: Vec<PredicateDocumentation>,
+}
+
+/// Documentation of a generic parameter, e.g. `T: ToString = String`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericParameterDocumentation {
+    /// The name of the generic parameter.
+    pub name: IdentifierId,
+
+    /// The location of the generic parameter's name.
+    pub location: Location,
+
+    /// Bounds of the generic parameter, e.g. `ToString`.
+    pub bounds: Vec<Signature>,
+
+    /// The default value of the generic parameter, e.g. `String`.
+    pub default_value: Option<Signature>,
+}
+
+/// Documentation of a where clause predicate, e.g. `T: ToString`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PredicateDocumentation {
+    /// The type that is bounded.
+    pub ty: Signature,
+
+    /// The location of the bounded type.
+    pub location: Location,
+
+    /// Bounds of the type.
+    pub bounds: Vec<Signature>,
+}
+
+/// An item signature as resolved by the type checker ([`stellar_database::SignatureData`]).
+///
+/// Unlike [`ItemDocumentation`], that describes the signature as written in the
+/// source code, all types here are written with fully qualified paths, e.g.
+/// `std.string.ToString` instead of `ToString`, and bounds of generic parameters
+/// are listed as predicates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedSignature {
+    /// Resolved predicates of the item.
+    pub predicates: Vec<ResolvedPredicate>,
+
+    /// Resolved interfaces implemented by the item.
+    pub implements: Vec<String>,
+}
+
+/// A resolved predicate, e.g. `T: std.string.ToString`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPredicate {
+    /// The type that is bounded.
+    pub ty: String,
+
+    /// Bounds of the type.
+    pub bounds: Vec<String>,
+}
+
+/// A docstring with resolved intra-doc links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Docstring {
+    /// The docstring text in Markdown.
+    pub text: String,
+
+    /// Intra-doc links, e.g. [`Option`], that refer to documented items.
+    pub links: Vec<DocstringLink>,
+}
+
+impl Docstring {
+    /// Returns the docstring text.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+/// A resolved intra-doc link, e.g. [`Option`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocstringLink {
+    /// The path as written in the docstring, e.g. `std.option.Option`.
+    pub path: String,
+
+    /// The location of the path inside the doc comment.
+    pub location: Location,
+
+    /// The documented item that is referenced.
+    pub target: LinkTarget,
+}
+
+/// A kind of a documented module item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ItemKind {
+    /// A struct.
+    Struct,
+    /// A tuple-like struct.
+    TupleLikeStruct,
+    /// An enum.
+    Enum,
+    /// An interface.
+    Interface,
+    /// A function.
+    Function,
+    /// A type alias.
+    TypeAlias,
+}
+
+impl ItemKind {
+    /// Returns a short name of the item kind, that is used in file names
+    /// and in the search index.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Struct | Self::TupleLikeStruct => "struct",
+            Self::Enum => "enum",
+            Self::Interface => "interface",
+            Self::Function => "fun",
+            Self::TypeAlias => "type",
+        }
+    }
+
+    /// Returns a human readable name of the item kind in plural, e.g. `Structs`.
+    #[inline]
+    #[must_use]
+    pub const fn plural_title(self) -> &'static str {
+        match self {
+            Self::Struct | Self::TupleLikeStruct => "Structs",
+            Self::Enum => "Enums",
+            Self::Interface => "Interfaces",
+            Self::Function => "Functions",
+            Self::TypeAlias => "Type aliases",
+        }
+    }
+
+    /// Returns a human readable name of the item kind, e.g. `Struct`.
+    #[inline]
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Struct | Self::TupleLikeStruct => "Struct",
+            Self::Enum => "Enum",
+            Self::Interface => "Interface",
+            Self::Function => "Function",
+            Self::TypeAlias => "Type alias",
+        }
+    }
+}
+
+/// A signature split into fragments, so that references to other items can be
+/// hyperlinked.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Signature {
+    /// Fragments of the signature.
+    pub fragments: Vec<SignatureFragment>,
+}
+
+impl Signature {
+    /// Returns the signature as plain text.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.fragments
+            .iter()
+            .map(|fragment| match fragment {
+                SignatureFragment::Keyword(text) => *text,
+                SignatureFragment::Text(text) | SignatureFragment::Link { text, .. } => text,
+            })
+            .collect()
+    }
+}
+
+/// A part of a signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureFragment {
+    /// A keyword, e.g. `pub`, `struct`.
+    Keyword(&'static str),
+
+    /// A text that doesn't refer to any item, e.g. punctuation or names of generic parameters.
+    Text(String),
+
+    /// A reference to a documented item.
+    Link {
+        /// The text of the reference as written in the source code, e.g. `option.Option`.
+        text: String,
+
+        /// The documented item that is referenced.
+        target: LinkTarget,
+    },
+}
+
+/// A documented entity, that can be referenced.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LinkTarget {
+    /// A module with a given path.
+    Module(Path),
+
+    /// A module item of a given kind with a given path.
+    Item(ItemKind, Path),
+
+    /// An enum item with a given path, e.g. `std.option.Option.None`.
+    EnumItem(Path),
+}
+
+impl Documentation {
+    /// Collects documentation of all modules of a given package.
+    ///
+    /// `modules` should contain HIR of all modules of the package, otherwise
+    /// modules without HIR are skipped.
+    #[must_use]
+    pub fn new(
+        db: &Database,
+        package: PackageId,
+        modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+    ) -> Self {
+        let mut documented_modules = package
+            .modules(db)
+            .filter_map(|module| {
+                modules
+                    .get(&module)
+                    .map(|hir| document_module(db, module, hir))
+            })
+            .collect::<Vec<_>>();
+
+        documented_modules.sort_by_key(|module| module.path.to_string());
+
+        Self {
+            package_name: package.name(db),
+            modules: documented_modules,
+        }
+    }
+
+    /// Returns an iterator over all documented items in all modules.
+    pub fn items(&self) -> impl Iterator<Item = &ItemDocumentation> {
+        self.modules.iter().flat_map(|module| module.items.iter())
+    }
+}
+
+fn document_module(
+    db: &Database,
+    module: ModuleId,
+    hir: &stellar_hir::Module,
+) -> ModuleDocumentation {
+    let mut submodules = module
+        .submodules(db)
+        .values()
+        .map(|submodule| submodule.path(db).clone())
+        .collect::<Vec<_>>();
+    submodules.sort_by_key(ToString::to_string);
+
+    let mut items = hir
+        .items
+        .iter()
+        .filter_map(|item| document_module_item(db, module, item))
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| (a.kind, a.name.as_str()).cmp(&(b.kind, b.name.as_str())));
+
+    ModuleDocumentation {
+        id: module,
+        path: module.path(db).clone(),
+        docstring: docstring(db, module, hir.docstring.as_ref()),
+        submodules,
+        items,
+    }
+}
+
+fn document_module_item(
+    db: &Database,
+    module: ModuleId,
+    item: &stellar_hir::ModuleItem,
+) -> Option<ItemDocumentation> {
+    let builder = || SignatureBuilder::new(db, module);
+
+    let (kind, name, visibility, item_docstring) = match item {
+        stellar_hir::ModuleItem::Struct(struct_) => (
+            ItemKind::Struct,
+            struct_.name,
+            struct_.visibility,
+            &struct_.docstring,
+        ),
+        stellar_hir::ModuleItem::TupleLikeStruct(struct_) => (
+            ItemKind::TupleLikeStruct,
+            struct_.name,
+            struct_.visibility,
+            &struct_.docstring,
+        ),
+        stellar_hir::ModuleItem::Enum(enum_) => (
+            ItemKind::Enum,
+            enum_.name,
+            enum_.visibility,
+            &enum_.docstring,
+        ),
+        stellar_hir::ModuleItem::Interface(interface) => (
+            ItemKind::Interface,
+            interface.name,
+            interface.visibility,
+            &interface.docstring,
+        ),
+        stellar_hir::ModuleItem::Function(function) => (
+            ItemKind::Function,
+            function.signature.name,
+            function.signature.visibility,
+            &function.signature.docstring,
+        ),
+        stellar_hir::ModuleItem::TypeAlias(alias) => (
+            ItemKind::TypeAlias,
+            alias.name,
+            alias.visibility,
+            &alias.docstring,
+        ),
+        stellar_hir::ModuleItem::Import { .. } => return None,
+    };
+
+    if !matches!(visibility, Visibility::Public(..)) {
+        return None;
+    }
+
+    let mut documentation = ItemDocumentation {
+        kind,
+        name: name.id,
+        path: module.path(db).clone() + name.id,
+        location: name.location,
+        docstring: docstring(db, module, item_docstring.as_ref()),
+        signature: Signature::default(),
+        generic_parameters: Vec::new(),
+        predicates: Vec::new(),
+        implements: Vec::new(),
+        inherits: Vec::new(),
+        resolved_signature: module
+            .module_item_symbol_or_none(db, name.id)
+            .and_then(|symbol| resolved_signature(db, symbol.signature(db))),
+        fields: Vec::new(),
+        enum_items: Vec::new(),
+        methods: Vec::new(),
+    };
+
+    match item {
+        stellar_hir::ModuleItem::Struct(struct_) => {
+            let builder = builder().with_generic_parameters(&struct_.generic_parameters);
+
+            documentation.signature = builder.clone().struct_(struct_);
+            documentation.generic_parameters =
+                generic_parameters(&builder, &struct_.generic_parameters);
+            documentation.predicates = predicates(&builder, &struct_.where_predicates);
+            documentation.implements = type_constructors(&builder, struct_.implements.as_deref());
+            documentation.fields = struct_
+                .fields
+                .iter()
+                .filter(|field| matches!(field.visibility, Visibility::Public(..)))
+                .map(|field| MemberDocumentation {
+                    name: field.name.id,
+                    location: field.name.location,
+                    docstring: docstring(db, module, field.docstring.as_ref()),
+                    signature: builder.clone().struct_field(field),
+                    generic_parameters: Vec::new(),
+                    predicates: Vec::new(),
+                })
+                .collect();
+            documentation.methods = methods(db, module, &builder, &struct_.methods, false);
+        }
+        stellar_hir::ModuleItem::TupleLikeStruct(struct_) => {
+            let builder = builder().with_generic_parameters(&struct_.generic_parameters);
+
+            documentation.signature = builder.clone().tuple_like_struct(struct_);
+            documentation.generic_parameters =
+                generic_parameters(&builder, &struct_.generic_parameters);
+            documentation.predicates = predicates(&builder, &struct_.where_predicates);
+            documentation.implements = type_constructors(&builder, struct_.implements.as_deref());
+            documentation.fields = struct_
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| matches!(field.visibility, Visibility::Public(..)))
+                .map(|(idx, field)| MemberDocumentation {
+                    name: IdentifierId::from(idx.to_string()),
+                    location: field.ty.location(),
+                    docstring: None,
+                    signature: builder.clone().tuple_field(field),
+                    generic_parameters: Vec::new(),
+                    predicates: Vec::new(),
+                })
+                .collect();
+            documentation.methods = methods(db, module, &builder, &struct_.methods, false);
+        }
+        stellar_hir::ModuleItem::Enum(enum_) => {
+            let builder = builder().with_generic_parameters(&enum_.generic_parameters);
+
+            documentation.signature = builder.clone().enum_(enum_);
+            documentation.generic_parameters =
+                generic_parameters(&builder, &enum_.generic_parameters);
+            documentation.predicates = predicates(&builder, &enum_.where_predicates);
+            documentation.implements = type_constructors(&builder, enum_.implements.as_deref());
+            documentation.enum_items = enum_
+                .items
+                .iter()
+                .map(|item| MemberDocumentation {
+                    name: item.name_id(),
+                    location: item.name().location,
+                    docstring: docstring(db, module, enum_item_docstring(item)),
+                    signature: builder.clone().enum_item(item),
+                    generic_parameters: Vec::new(),
+                    predicates: Vec::new(),
+                })
+                .collect();
+            documentation.methods = methods(db, module, &builder, &enum_.methods, false);
+        }
+        stellar_hir::ModuleItem::Interface(interface) => {
+            let builder = builder().with_generic_parameters(&interface.generic_parameters);
+
+            let (inherits, where_predicates) = split_inherited_interfaces(interface);
+
+            documentation.signature = builder.clone().interface(interface);
+            documentation.generic_parameters =
+                generic_parameters(&builder, &interface.generic_parameters);
+            documentation.predicates = predicates(&builder, &where_predicates);
+            documentation.inherits = type_constructors(&builder, Some(&inherits));
+            documentation.methods = methods(db, module, &builder, &interface.methods, true);
+        }
+        stellar_hir::ModuleItem::Function(function) => {
+            let builder = builder().with_generic_parameters(&function.signature.generic_parameters);
+
+            documentation.signature = builder.clone().function(&function.signature);
+            documentation.generic_parameters =
+                generic_parameters(&builder, &function.signature.generic_parameters);
+            documentation.predicates = predicates(&builder, &function.signature.where_predicates);
+        }
+        stellar_hir::ModuleItem::TypeAlias(alias) => {
+            let builder = builder().with_generic_parameters(&alias.generic_parameters);
+
+            documentation.signature = builder.clone().type_alias(alias);
+            documentation.generic_parameters =
+                generic_parameters(&builder, &alias.generic_parameters);
+        }
+        stellar_hir::ModuleItem::Import { .. } => unreachable!(),
+    }
+
+    Some(documentation)
+}
+
+fn type_constructors(
+    builder: &SignatureBuilder<'_>,
+    constructors: Option<&[stellar_hir::TypeConstructor]>,
+) -> Vec<Signature> {
+    constructors
+        .unwrap_or_default()
+        .iter()
+        .map(|constructor| builder.clone().type_constructor_signature(constructor))
+        .collect()
+}
+
+fn generic_parameters(
+    builder: &SignatureBuilder<'_>,
+    parameters: &[stellar_hir::GenericParameter],
+) -> Vec<GenericParameterDocumentation> {
+    parameters
+        .iter()
+        .map(|parameter| GenericParameterDocumentation {
+            name: parameter.name.id,
+            location: parameter.name.location,
+            bounds: type_constructors(builder, parameter.bounds.as_deref()),
+            default_value: parameter
+                .default_value
+                .as_ref()
+                .map(|default_value| builder.clone().type_signature(default_value)),
+        })
+        .collect()
+}
+
+fn predicates(
+    builder: &SignatureBuilder<'_>,
+    predicates: &[stellar_hir::WherePredicate],
+) -> Vec<PredicateDocumentation> {
+    predicates
+        .iter()
+        .map(|predicate| PredicateDocumentation {
+            ty: builder.clone().type_signature(&predicate.ty),
+            location: predicate.ty.location(),
+            bounds: type_constructors(builder, Some(&predicate.bounds)),
+        })
+        .collect()
+}
+
+/// Returns the resolved signature of an item, if the type checker has analyzed it.
+fn resolved_signature(db: &Database, signature: SignatureId) -> Option<ResolvedSignature> {
+    if !signature.is_analyzed(db) {
+        return None;
+    }
+
+    let scope = signature.generic_parameter_scope(db);
+
+    Some(ResolvedSignature {
+        predicates: signature
+            .predicates(db)
+            .iter()
+            .map(|predicate| ResolvedPredicate {
+                ty: resolved_type(db, scope, predicate.ty(db)),
+                bounds: predicate
+                    .bounds(db)
+                    .iter()
+                    .map(|bound| resolved_type_constructor(db, scope, bound))
+                    .collect(),
+            })
+            .collect(),
+        implements: signature
+            .implements(db)
+            .iter()
+            .map(|interface| resolved_type_constructor(db, scope, interface))
+            .collect(),
+    })
+}
+
+fn methods(
+    db: &Database,
+    module: ModuleId,
+    builder: &SignatureBuilder<'_>,
+    methods: &[stellar_hir::Function],
+    include_private: bool,
+) -> Vec<MemberDocumentation> {
+    methods
+        .iter()
+        .filter(|method| {
+            include_private || matches!(method.signature.visibility, Visibility::Public(..))
+        })
+        .map(|method| {
+            let builder = builder
+                .clone()
+                .with_generic_parameters(&method.signature.generic_parameters);
+
+            MemberDocumentation {
+                name: method.signature.name.id,
+                location: method.signature.name.location,
+                docstring: docstring(db, module, method.signature.docstring.as_ref()),
+                signature: builder.clone().function(&method.signature),
+                generic_parameters: generic_parameters(
+                    &builder,
+                    &method.signature.generic_parameters,
+                ),
+                predicates: predicates(&builder, &method.signature.where_predicates),
+            }
+        })
+        .collect()
+}
+
+const fn enum_item_docstring(item: &stellar_hir::EnumItem) -> Option<&stellar_hir::Docstring> {
+    match item {
+        stellar_hir::EnumItem::Just { docstring, .. }
+        | stellar_hir::EnumItem::TupleLike { docstring, .. }
+        | stellar_hir::EnumItem::Struct { docstring, .. } => docstring.as_ref(),
+    }
+}
+
+/// Collects a docstring together with the intra-doc links resolved in it
+/// (see [`ModuleId::doc_links`]).
+fn docstring(
+    db: &Database,
+    module: ModuleId,
+    docstring: Option<&stellar_hir::Docstring>,
+) -> Option<Docstring> {
+    let docstring = docstring?;
+
+    let links = module
+        .doc_links(db)
+        .iter()
+        .filter(|link| {
+            docstring.lines.iter().any(|line| {
+                line.filepath == link.location.filepath
+                    && line.start <= link.location.start
+                    && link.location.end <= line.end
+            })
+        })
+        .filter_map(|link| {
+            Some(DocstringLink {
+                path: link.path.clone(),
+                location: link.location,
+                target: symbol_link_target(db, module.package(), link.target)?,
+            })
+        })
+        .collect();
+
+    Some(Docstring {
+        text: docstring.text.clone(),
+        links,
+    })
+}
@@ -0,0 +1,596 @@
+//! Builds [`Signature`]s of documented items from their HIR.
+
+use stellar_ast::Visibility;
+use stellar_database::{
+    ty::{Type, TypeConstructor},
+    Database, GenericParameterId, GenericParameterScopeId, ModuleId, PackageId, Symbol,
+};
+use stellar_interner::{builtin_identifiers::BIG_SELF, IdentifierId};
+
+use crate::model::{ItemKind, LinkTarget, Signature, SignatureFragment};
+
+/// Builds a signature fragment by fragment.
+///
+/// Names of generic parameters in scope are tracked, so that they are never
+/// hyperlinked to module items with the same name.
+#[derive(Debug, Clone)]
+pub(crate) struct SignatureBuilder<'db> {
+    db: &'db Database,
+    module: ModuleId,
+    generic_parameters: Vec<IdentifierId>,
+    fragments: Vec<SignatureFragment>,
+}
+
+impl<'db> SignatureBuilder<'db> {
+    pub(crate) const fn new(db: &'db Database, module: ModuleId) -> Self {
+        Self {
+            db,
+            module,
+            generic_parameters: Vec::new(),
+            fragments: Vec::new(),
+        }
+    }
+
+    /// Adds generic parameters into scope.
+    pub(crate) fn with_generic_parameters(
+        mut self,
+        generic_parameters: &[stellar_hir::GenericParameter],
+    ) -> Self {
+        self.generic_parameters
+            .extend(generic_parameters.iter().map(|parameter| parameter.name.id));
+        self
+    }
+
+    pub(crate) fn struct_(mut self, struct_: &stellar_hir::Struct) -> Signature {
+        self.item_header(struct_.visibility, "struct", struct_.name.id);
+        self.generic_parameters(&struct_.generic_parameters);
+        self.where_predicates(&struct_.where_predicates);
+        self.finish()
+    }
+
+    pub(crate) fn tuple_like_struct(mut self, struct_: &stellar_hir::TupleLikeStruct) -> Signature {
+        self.item_header(struct_.visibility, "struct", struct_.name.id);
+        self.generic_parameters(&struct_.generic_parameters);
+        self.text("(");
+
+        for (idx, field) in struct_.fields.iter().enumerate() {
+            if idx != 0 {
+                self.text(", ");
+            }
+
+            // Private fields are hidden, but their positions still matter.
+            if matches!(field.visibility, Visibility::Public(..)) {
+                self.tuple_field_inner(field);
+            } else {
+                self.text("_");
+            }
+        }
+
+        self.text(")");
+        self.where_predicates(&struct_.where_predicates);
+        self.finish()
+    }
+
+    pub(crate) fn enum_(mut self, enum_: &stellar_hir::Enum) -> Signature {
+        self.item_header(enum_.visibility, "enum", enum_.name.id);
+        self.generic_parameters(&enum_.generic_parameters);
+        self.where_predicates(&enum_.where_predicates);
+        self.finish()
+    }
+
+    pub(crate) fn interface(mut self, interface: &stellar_hir::Interface) -> Signature {
+        self.item_header(interface.visibility, "interface", interface.name.id);
+        self.generic_parameters(&interface.generic_parameters);
+
+        let (inherits, where_predicates) = split_inherited_interfaces(interface);
+
+        if !inherits.is_empty() {
+            self.text(": ");
+            self.bounds(&inherits);
+        }
+
+        self.where_predicates(&where_predicates);
+        self.finish()
+    }
+
+    pub(crate) fn function(mut self, function: &stellar_hir::FunctionSignature) -> Signature {
+        self.generic_parameters.extend(
+            function
+                .generic_parameters
+                .iter()
+                .map(|parameter| parameter.name.id),
+        );
+
+        self.item_header(function.visibility, "fun", function.name.id);
+        self.generic_parameters(&function.generic_parameters);
+        self.text("(");
+
+        for (idx, parameter) in function.parameters.iter().enumerate() {
+            if idx != 0 {
+                self.text(", ");
+            }
+
+            match parameter {
+                stellar_hir::FunctionParameter::SelfParameter(parameter) => {
+                    self.keyword("self");
+
+                    if let Some(ty) = &parameter.ty {
+                        self.text(": ");
+                        self.ty(ty);
+                    }
+                }
+                stellar_hir::FunctionParameter::NotSelfParameter(parameter) => {
+                    self.pattern(&parameter.pattern);
+                    self.text(": ");
+                    self.ty(&parameter.ty);
+                }
+            }
+        }
+
+        self.text(")");
+
+        if let Some(return_type) = &function.return_type {
+            self.text(": ");
+            self.ty(return_type);
+        }
+
+        self.where_predicates(&function.where_predicates);
+        self.finish()
+    }
+
+    pub(crate) fn type_alias(mut self, alias: &stellar_hir::TypeAlias) -> Signature {
+        self.item_header(alias.visibility, "type", alias.name.id);
+        self.generic_parameters(&alias.generic_parameters);
+        self.text(" = ");
+        self.ty(&alias.value);
+        self.finish()
+    }
+
+    pub(crate) fn struct_field(mut self, field: &stellar_hir::StructField) -> Signature {
+        self.visibility(field.visibility);
+        self.text(format!("{}: ", field.name.id));
+        self.ty(&field.ty);
+        self.finish()
+    }
+
+    pub(crate) fn tuple_field(mut self, field: &stellar_hir::TupleField) -> Signature {
+        self.tuple_field_inner(field);
+        self.finish()
+    }
+
+    pub(crate) fn enum_item(mut self, item: &stellar_hir::EnumItem) -> Signature {
+        self.text(item.name_id().as_str());
+
+        match item {
+            stellar_hir::EnumItem::Just { .. } => {}
+            stellar_hir::EnumItem::TupleLike { fields, .. } => {
+                self.text("(");
+
+                for (idx, field) in fields.iter().enumerate() {
+                    if idx != 0 {
+                        self.text(", ");
+                    }
+
+                    self.ty(&field.ty);
+                }
+
+                self.text(")");
+            }
+            stellar_hir::EnumItem::Struct { fields, .. } => {
+                self.text(" { ");
+
+                for (idx, field) in fields.iter().enumerate() {
+                    if idx != 0 {
+                        self.text(", ");
+                    }
+
+                    self.text(format!("{}: ", field.name.id));
+                    self.ty(&field.ty);
+                }
+
+                self.text(" }");
+            }
+        }
+
+        self.finish()
+    }
+
+    pub(crate) fn type_constructor_signature(
+        mut self,
+        constructor: &stellar_hir::TypeConstructor,
+    ) -> Signature {
+        self.type_constructor(constructor);
+        self.finish()
+    }
+
+    pub(crate) fn type_signature(mut self, ty: &stellar_hir::Type) -> Signature {
+        self.ty(ty);
+        self.finish()
+    }
+
+    fn finish(self) -> Signature {
+        Signature {
+            fragments: self.fragments,
+        }
+    }
+
+    fn keyword(&mut self, keyword: &'static str) {
+        self.fragments.push(SignatureFragment::Keyword(keyword));
+    }
+
+    fn text(&mut self, text: impl AsRef<str>) {
+        if let Some(SignatureFragment::Text(last)) = self.fragments.last_mut() {
+            last.push_str(text.as_ref());
+        } else {
+            self.fragments
+                .push(SignatureFragment::Text(text.as_ref().to_owned()));
+        }
+    }
+
+    fn visibility(&mut self, visibility: Visibility) {
+        if matches!(visibility, Visibility::Public(..)) {
+            self.keyword("pub");
+            self.text(" ");
+        }
+    }
+
+    fn item_header(&mut self, visibility: Visibility, keyword: &'static str, name: IdentifierId) {
+        self.visibility(visibility);
+        self.keyword(keyword);
+        self.text(format!(" {name}"));
+    }
+
+    fn tuple_field_inner(&mut self, field: &stellar_hir::TupleField) {
+        self.visibility(field.visibility);
+        self.ty(&field.ty);
+    }
+
+    fn generic_parameters(&mut self, generic_parameters: &[stellar_hir::GenericParameter]) {
+        if generic_parameters.is_empty() {
+            return;
+        }
+
+        self.text("[");
+
+        for (idx, parameter) in generic_parameters.iter().enumerate() {
+            if idx != 0 {
+                self.text(", ");
+            }
+
+            self.text(parameter.name.id.as_str());
+
+            if let Some(bounds) = &parameter.bounds {
+                self.text(": ");
+                self.bounds(bounds);
+            }
+
+            if let Some(default_value) = &parameter.default_value {
+                self.text(" = ");
+                self.ty(default_value);
+            }
+        }
+
+        self.text("]");
+    }
+
+    fn where_predicates(&mut self, predicates: &[stellar_hir::WherePredicate]) {
+        if predicates.is_empty() {
+            return;
+        }
+
+        self.text(" ");
+        self.keyword("where");
+        self.text(" ");
+
+        for (idx, predicate) in predicates.iter().enumerate() {
+            if idx != 0 {
+                self.text(", ");
+            }
+
+            self.ty(&predicate.ty);
+            self.text(": ");
+            self.bounds(&predicate.bounds);
+        }
+    }
+
+    fn bounds(&mut self, bounds: &[stellar_hir::TypeConstructor]) {
+        for (idx, bound) in bounds.iter().enumerate() {
+            if idx != 0 {
+                self.text(" + ");
+            }
+
+            self.type_constructor(bound);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &stellar_hir::Pattern) {
+        match pattern {
+            stellar_hir::Pattern::Identifier { identifier, .. } => {
+                self.text(identifier.id.as_str());
+            }
+            stellar_hir::Pattern::Grouped { inner, .. } => self.pattern(inner),
+            _ => self.text("_"),
+        }
+    }
+
+    fn ty(&mut self, ty: &stellar_hir::Type) {
+        match ty {
+            stellar_hir::Type::Constructor(constructor) => self.type_constructor(constructor),
+            // Parentheses don't change the type, so they are omitted in signatures.
+            stellar_hir::Type::Parenthesized { inner, .. } => self.ty(inner),
+            stellar_hir::Type::Tuple { element_types, .. } => {
+                self.text("(");
+
+                for (idx, element_type) in element_types.iter().enumerate() {
+                    if idx != 0 {
+                        self.text(", ");
+                    }
+
+                    self.ty(element_type);
+                }
+
+                if element_types.len() == 1 {
+                    self.text(",");
+                }
+
+                self.text(")");
+            }
+            stellar_hir::Type::Function {
+                parameter_types,
+                return_type,
+                ..
+            } => {
+                self.keyword("fun");
+                self.text("(");
+
+                for (idx, parameter_type) in parameter_types.iter().enumerate() {
+                    if idx != 0 {
+                        self.text(", ");
+                    }
+
+                    self.ty(parameter_type);
+                }
+
+                self.text(")");
+
+                if let Some(return_type) = return_type {
+                    self.text(": ");
+                    self.ty(return_type);
+                }
+            }
+            stellar_hir::Type::Underscore { .. } => self.text("_"),
+            stellar_hir::Type::InterfaceObject { bounds, .. } => {
+                self.keyword("dyn");
+                self.text(" ");
+                self.bounds(bounds);
+            }
+        }
+    }
+
+    fn type_constructor(&mut self, constructor: &stellar_hir::TypeConstructor) {
+        let text = constructor
+            .path
+            .identifiers
+            .iter()
+            .map(|identifier| identifier.id.as_str())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if let Some(target) = self.link_target(&constructor.path) {
+            self.fragments
+                .push(SignatureFragment::Link { text, target });
+        } else {
+            self.text(text);
+        }
+
+        if constructor.arguments.is_empty() {
+            return;
+        }
+
+        self.text("[");
+
+        for (idx, argument) in constructor.arguments.iter().enumerate() {
+            if idx != 0 {
+                self.text(", ");
+            }
+
+            self.ty(argument);
+        }
+
+        self.text("]");
+    }
+
+    /// Finds the documented item, that a given path refers to.
+    ///
+    /// Unlike name resolution in the type checker, this never reports diagnostics:
+    /// if the path cannot be resolved, or refers to an item that is not documented,
+    /// it is simply not hyperlinked.
+    fn link_target(&self, path: &stellar_ast::Path) -> Option<LinkTarget> {
+        let (first, rest) = path.identifiers.split_first()?;
+
+        if rest.is_empty() && self.generic_parameters.contains(&first.id) {
+            return None;
+        }
+
+        let package = self.module.package();
+
+        let mut symbol = self
+            .module
+            .symbol_or_none(self.db, first.id)
+            .or_else(|| {
+                self.module
+                    .resolved_imports(self.db)
+                    .get(&first.id)
+                    .copied()
+            })
+            .or_else(|| {
+                (first.id == package.name(self.db))
+                    .then(|| Symbol::Module(package.root_module(self.db)))
+            })?;
+
+        for identifier in rest {
+            symbol = match symbol {
+                Symbol::Module(module) => module.symbol_or_none(self.db, identifier.id)?,
+                Symbol::Enum(enum_) => Symbol::EnumItem(enum_.item(self.db, identifier.id)?),
+                _ => return None,
+            };
+        }
+
+        symbol_link_target(self.db, package, symbol)
+    }
+}
+
+/// Splits where predicates of an interface into interfaces, that it inherits, and
+/// the rest of predicates.
+///
+/// AST lowering turns `interface Foo: Bar + Baz` into `interface Foo where Self: Bar + Baz`
+/// and locates `Self` at the interface name, which tells it apart from predicates
+/// written by hand.
+pub(crate) fn split_inherited_interfaces(
+    interface: &stellar_hir::Interface,
+) -> (
+    Vec<stellar_hir::TypeConstructor>,
+    Vec<stellar_hir::WherePredicate>,
+) {
+    let mut inherits = Vec::new();
+    let mut where_predicates = Vec::new();
+
+    for predicate in &interface.where_predicates {
+        match &predicate.ty {
+            stellar_hir::Type::Constructor(constructor)
+                if constructor.location == interface.name.location
+                    && matches!(
+                        constructor.path.identifiers.as_slice(),
+                        [identifier] if identifier.id == BIG_SELF
+                    ) =>
+            {
+                inherits.extend(predicate.bounds.iter().cloned());
+            }
+            _ => where_predicates.push(predicate.clone()),
+        }
+    }
+
+    (inherits, where_predicates)
+}
+
+/// Returns a given type as written in a resolved signature, e.g.
+/// `std.list.List[T]`. Paths of referenced items are fully qualified.
+pub(crate) fn resolved_type(db: &Database, scope: GenericParameterScopeId, ty: &Type) -> String {
+    let join = |types: &[Type]| {
+        types
+            .iter()
+            .map(|ty| resolved_type(db, scope, ty))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match ty {
+        Type::Unit => "()".to_owned(),
+        Type::Unknown | Type::Variable(..) => "_".to_owned(),
+        Type::Constructor(constructor) => resolved_type_constructor(db, scope, constructor),
+        Type::Tuple { element_types } if element_types.len() == 1 => {
+            format!("({},)", join(element_types))
+        }
+        Type::Tuple { element_types } => format!("({})", join(element_types)),
+        Type::Function {
+            parameter_types,
+            return_type,
+        } => format!(
+            "fun({}): {}",
+            join(parameter_types),
+            resolved_type(db, scope, return_type)
+        ),
+        Type::GenericParameter(parameter) => generic_parameter_name(db, scope, *parameter)
+            .map_or_else(|| "_".to_owned(), |name| name.as_str().to_owned()),
+        Type::InterfaceObject { bounds } => format!(
+            "dyn {}",
+            bounds
+                .iter()
+                .map(|bound| resolved_type_constructor(db, scope, bound))
+                .collect::<Vec<_>>()
+                .join(" + ")
+        ),
+    }
+}
+
+/// Returns a given type constructor as written in a resolved signature, e.g.
+/// `std.list.List[T]`.
+pub(crate) fn resolved_type_constructor(
+    db: &Database,
+    scope: GenericParameterScopeId,
+    constructor: &TypeConstructor,
+) -> String {
+    let path = constructor.symbol.path(db).to_string();
+
+    if constructor.arguments.is_empty() {
+        return path;
+    }
+
+    format!(
+        "{path}[{}]",
+        constructor
+            .arguments
+            .iter()
+            .map(|argument| resolved_type(db, scope, argument))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Generic parameters are stored without their names, so the name is looked up
+/// in a given scope and its parents.
+fn generic_parameter_name(
+    db: &Database,
+    scope: GenericParameterScopeId,
+    parameter: GenericParameterId,
+) -> Option<IdentifierId> {
+    let mut scope = Some(scope);
+
+    while let Some(current) = scope {
+        if let Some((name, _)) = current
+            .parameters(db)
+            .iter()
+            .find(|(_, id)| **id == parameter)
+        {
+            return Some(*name);
+        }
+
+        scope = current.parent_scope(db);
+    }
+
+    None
+}
+
+/// Returns the link target for a given symbol, if it is documented, i.e. it is a public
+/// item of a given package.
+pub(crate) fn symbol_link_target(
+    db: &Database,
+    package: PackageId,
+    symbol: Symbol,
+) -> Option<LinkTarget> {
+    if matches!(symbol, Symbol::BuiltinSymbol(..)) || symbol.module(db).package() != package {
+        return None;
+    }
+
+    let kind = match symbol {
+        Symbol::Module(module) => return Some(LinkTarget::Module(module.path(db).clone())),
+        Symbol::EnumItem(item) => {
+            let enum_ = Symbol::Enum(item.enum_(db));
+
+            return is_public(db, enum_).then(|| LinkTarget::EnumItem(symbol.path(db)));
+        }
+        Symbol::Struct(..) => ItemKind::Struct,
+        Symbol::TupleLikeStruct(..) => ItemKind::TupleLikeStruct,
+        Symbol::Enum(..) => ItemKind::Enum,
+        Symbol::Interface(..) => ItemKind::Interface,
+        Symbol::Function(..) => ItemKind::Function,
+        Symbol::TypeAlias(..) => ItemKind::TypeAlias,
+        Symbol::BuiltinSymbol(..) => unreachable!(),
+    };
+
+    is_public(db, symbol).then(|| LinkTarget::Item(kind, symbol.path(db)))
+}
+
+fn is_public(db: &Database, symbol: Symbol) -> bool {
+    matches!(symbol.signature(db).visibility(db), Visibility::Public(..))
+}
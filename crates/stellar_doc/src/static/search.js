@@ -0,0 +1,51 @@
+(function () {
+    const input = document.getElementById("search");
+    const results = document.getElementById("search-results");
+    const content = document.getElementById("content");
+    const root = document.body.dataset.root;
+    const maxResults = 50;
+
+    function search(query) {
+        query = query.trim().toLowerCase();
+
+        if (query === "") {
+            results.hidden = true;
+            content.hidden = false;
+            return;
+        }
+
+        const matches = window.SEARCH_INDEX
+            .filter((entry) => entry.path.toLowerCase().includes(query))
+            .sort((a, b) => {
+                // Exact name matches go first, then shorter paths.
+                const exactA = a.name.toLowerCase() === query ? 0 : 1;
+                const exactB = b.name.toLowerCase() === query ? 0 : 1;
+
+                return exactA - exactB || a.path.length - b.path.length;
+            })
+            .slice(0, maxResults);
+
+        results.replaceChildren(
+            ...matches.map((entry) => {
+                const item = document.createElement("li");
+                const kind = document.createElement("span");
+                const link = document.createElement("a");
+                const summary = document.createElement("div");
+
+                kind.className = "kind";
+                kind.textContent = entry.kind;
+                link.href = root + entry.url;
+                link.textContent = entry.path;
+                summary.textContent = entry.summary;
+
+                item.append(kind, link, summary);
+                return item;
+            })
+        );
+
+        results.hidden = false;
+        content.hidden = true;
+    }
+
+    input.addEventListener("input", () => search(input.value));
+})();
@@ -0,0 +1,66 @@
+//! Helpers shared by documentation tests.
+
+// Not every test file uses every helper.
+#![allow(dead_code)]
+
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{ModuleId, PackageData, PackageId, Path, State};
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{IdentifierId, PathId};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    collect_definitions::CollectDefinitions, resolve_doc_links::ResolveDocLinks,
+    resolve_imports::ResolveImports,
+};
+
+pub fn path(segments: &[&str]) -> Path {
+    Path::new(segments.iter().map(|&s| IdentifierId::from(s)).collect())
+}
+
+/// Analyzes the package `a`, that consists of a root module and its submodules,
+/// given by names and source code, e.g. `analyze("", &[("b", "pub struct Foo {}")])`.
+pub fn analyze(
+    root_source: &str,
+    submodules: &[(&str, &str)],
+) -> (State, PackageId, FxHashMap<ModuleId, stellar_hir::Module>) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
+    let root = parse_module(
+        &mut state,
+        package,
+        path(&["a"]),
+        PathId::from("a/package.sr"),
+        root_source,
+    );
+
+    package.set_root_module(state.db_mut(), root.module());
+
+    let mut modules = vec![];
+
+    for (name, source) in submodules {
+        let submodule = parse_module(
+            &mut state,
+            package,
+            path(&["a", name]),
+            PathId::from(format!("a/{name}.sr")),
+            source,
+        );
+
+        root.module()
+            .add_submodule(state.db_mut(), submodule.module());
+        modules.push(submodule);
+    }
+
+    modules.insert(0, root);
+
+    let hir = LowerToHir::run_all(modules);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+    ResolveDocLinks::run_all(&mut state, &hir);
+
+    assert!(!state.diagnostics().is_fatal());
+
+    (state, package, hir)
+}
@@ -0,0 +1,83 @@
+use common::analyze;
+use stellar_doc::doctest::{collect_doctests, run_doctest, DoctestOutcome};
+use stellar_filesystem::location::ByteOffset;
+use stellar_interner::PathId;
+
+mod common;
+
+/// Runs all doctests of the package `a` with a single root module and checks,
+/// that the state of the package is left untouched.
+fn run_doctests(source: &str) -> Vec<(String, DoctestOutcome)> {
+    let (state, package, hir) = analyze(source, &[]);
+
+    assert!(state.diagnostics().is_ok());
+
+    let outcomes = collect_doctests(state.db(), &hir)
+        .iter()
+        .map(|doctest| {
+            (
+                doctest.path.to_string(),
+                run_doctest(&state, package, doctest),
+            )
+        })
+        .collect();
+
+    // Doctests are checked in copies of the state.
+    assert_eq!(state.db().packages().count(), 1);
+    assert!(state.diagnostics().diagnostics.is_empty());
+
+    outcomes
+}
+
+#[test]
+fn outcomes() {
+    let outcomes = run_doctests(
+        "/// ```stellar\n\
+         /// import a.Foo;\n\
+         /// ```\n\
+         ///\n\
+         /// ```stellar,ignore\n\
+         /// garbage\n\
+         /// ```\n\
+         ///\n\
+         /// ```stellar,should_fail\n\
+         /// import a.Bar;\n\
+         /// ```\n\
+         ///\n\
+         /// ```txt\n\
+         /// not a doctest\n\
+         /// ```\n\
+         pub struct Foo {}\n\
+         /// ```stellar,should_fail\n\
+         /// import a.Foo;\n\
+         /// ```\n\
+         pub fun bar() {}",
+    );
+
+    assert_eq!(
+        outcomes,
+        vec![
+            ("a.Foo".to_owned(), DoctestOutcome::Passed),
+            ("a.Foo".to_owned(), DoctestOutcome::Ignored),
+            ("a.Foo".to_owned(), DoctestOutcome::Passed),
+            ("a.bar".to_owned(), DoctestOutcome::UnexpectedlyPassed),
+        ]
+    );
+}
+
+#[test]
+fn locations_are_mapped_into_doc_comment() {
+    let source = "/// ```stellar\n/// import a.Fooo;\n/// ```\npub struct Foo {}";
+    let outcomes = run_doctests(source);
+
+    let DoctestOutcome::Failed(diagnostics) = &outcomes[0].1 else {
+        panic!("expected the doctest to fail");
+    };
+
+    let location = diagnostics.diagnostics[0].labels[0].location;
+    let start = source.find("Fooo").unwrap();
+
+    assert_eq!(location.filepath, PathId::from("a/package.sr"));
+    assert_eq!(location.start, ByteOffset(start));
+    assert_eq!(location.end, ByteOffset(start + 4));
+}
@@ -0,0 +1,264 @@
+use std::{fs, process};
+
+use common::{analyze, path};
+use stellar_database::{
+    ty::{Type, TypeConstructor},
+    GenericParameterData, PredicateData,
+};
+use stellar_doc::{
+    html::generate_html,
+    json::{documentation_to_json, FORMAT_VERSION},
+    model::{ItemKind, LinkTarget, SignatureFragment},
+    Documentation,
+};
+use stellar_filesystem::location::DUMMY_LOCATION;
+use stellar_interner::IdentifierId;
+
+mod common;
+
+/// Documents the package `a` with the root module and a single submodule `b`.
+fn document(submodule_source: &str, root_source: &str) -> Documentation {
+    let (state, package, hir) = analyze(root_source, &[("b", submodule_source)]);
+
+    Documentation::new(state.db(), package, &hir)
+}
+
+#[test]
+fn private_items_are_not_documented() {
+    let documentation = document("pub fun foo() {} fun bar() {}", "");
+
+    assert_eq!(
+        documentation
+            .items()
+            .map(|item| item.path.to_string())
+            .collect::<Vec<_>>(),
+        vec!["a.b.foo"]
+    );
+}
+
+#[test]
+fn docstrings() {
+    let documentation = document(
+        "/// A point.\n///\n/// More details.\npub struct Point {}",
+        "//! The package.",
+    );
+
+    assert_eq!(
+        documentation.modules[0]
+            .docstring
+            .as_ref()
+            .map(|docstring| docstring.as_str()),
+        Some("The package.")
+    );
+
+    let item = documentation.items().next().unwrap();
+
+    assert_eq!(item.kind, ItemKind::Struct);
+    assert_eq!(
+        item.docstring.as_ref().map(|docstring| docstring.as_str()),
+        Some("A point.\n\nMore details.")
+    );
+}
+
+#[test]
+fn signature_links() {
+    let documentation = document(
+        "pub struct Point {}",
+        "import a.b.Point; pub fun origin(): Point {}",
+    );
+
+    let origin = documentation
+        .items()
+        .find(|item| item.name.as_str() == "origin")
+        .unwrap();
+
+    assert_eq!(origin.signature.text(), "pub fun origin(): Point");
+    assert!(origin
+        .signature
+        .fragments
+        .contains(&SignatureFragment::Link {
+            text: "Point".to_owned(),
+            target: LinkTarget::Item(ItemKind::Struct, path(&["a", "b", "Point"])),
+        }));
+}
+
+#[test]
+fn intra_doc_links() {
+    let documentation = document(
+        "pub struct Point {}",
+        "import a.b.Point;\n/// Returns [`Point`], see [`a.b`].\npub fun origin(): Point {}",
+    );
+
+    let links = documentation
+        .items()
+        .find(|item| item.name.as_str() == "origin")
+        .unwrap()
+        .docstring
+        .as_ref()
+        .unwrap()
+        .links
+        .iter()
+        .map(|link| (link.path.as_str(), link.target.clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        links,
+        vec![
+            (
+                "Point",
+                LinkTarget::Item(ItemKind::Struct, path(&["a", "b", "Point"]))
+            ),
+            ("a.b", LinkTarget::Module(path(&["a", "b"])))
+        ]
+    );
+}
+
+#[test]
+fn html() {
+    let documentation = document("pub enum Option[T] { Some(T), None }", "");
+    let output_directory =
+        std::env::temp_dir().join(format!("stellar_doc_html_test_{}", process::id()));
+    let _ = fs::remove_dir_all(&output_directory);
+
+    generate_html(&documentation, &output_directory).unwrap();
+
+    for file in [
+        "index.html",
+        "search-index.js",
+        "static/style.css",
+        "static/search.js",
+        "a/index.html",
+        "a/b/index.html",
+    ] {
+        assert!(output_directory.join(file).exists(), "{file}");
+    }
+
+    let page = fs::read_to_string(output_directory.join("a/b/enum.Option.html")).unwrap();
+
+    assert!(page.contains("id=\"item.Some\""));
+
+    fs::remove_dir_all(&output_directory).unwrap();
+}
+
+#[test]
+fn json() {
+    let documentation = document(
+        "pub interface Show {}
+
+        /// A box.
+        pub enum Box[T: Show = int32] implements Show where T: Show { Empty, Full(T) }",
+        "",
+    );
+    let json = documentation_to_json(&documentation);
+
+    assert_eq!(json["format_version"], FORMAT_VERSION);
+    assert_eq!(json["package"], "a");
+
+    let module = &json["modules"][1];
+    assert_eq!(module["path"], "a.b");
+
+    let box_ = &module["items"][0];
+    assert_eq!(box_["kind"], "enum");
+    assert_eq!(box_["path"], "a.b.Box");
+    assert_eq!(box_["docstring"]["text"], "A box.");
+    assert_eq!(box_["location"]["file"], "a/b.sr");
+
+    let parameter = &box_["generic_parameters"][0];
+    assert_eq!(parameter["name"], "T");
+    assert_eq!(parameter["bounds"][0]["text"], "Show");
+    assert_eq!(parameter["default_value"]["text"], "int32");
+
+    assert_eq!(box_["predicates"][0]["type"]["text"], "T");
+    assert_eq!(
+        box_["implements"][0]["fragments"][0]["target"]["path"],
+        "a.b.Show"
+    );
+    assert_eq!(box_["enum_items"][1]["name"], "Full");
+}
+
+#[test]
+fn inherited_interfaces() {
+    let documentation = document(
+        "pub interface Show {}
+        pub interface Debug {}
+        pub interface Pretty: Show + Debug where Self: Sized {}
+        interface Sized {}",
+        "",
+    );
+    let pretty = documentation
+        .items()
+        .find(|item| item.name.as_str() == "Pretty")
+        .unwrap();
+
+    assert_eq!(
+        pretty.signature.text(),
+        "pub interface Pretty: Show + Debug where Self: Sized"
+    );
+    assert_eq!(
+        pretty
+            .inherits
+            .iter()
+            .map(|interface| interface.text())
+            .collect::<Vec<_>>(),
+        vec!["Show", "Debug"]
+    );
+    assert_eq!(pretty.predicates.len(), 1);
+
+    let json = documentation_to_json(&documentation);
+    let pretty = &json["modules"][1]["items"][1];
+
+    assert_eq!(pretty["name"], "Pretty");
+    assert_eq!(
+        pretty["inherits"][0]["fragments"][0]["target"]["path"],
+        "a.b.Show"
+    );
+}
+
+#[test]
+fn resolved_signature() {
+    let (mut state, package, hir) =
+        analyze("", &[("b", "pub interface Show {} pub struct Box[T] {}")]);
+
+    let module = package.root_module(state.db()).submodules(state.db())[&IdentifierId::from("b")];
+    let show = module.module_item_symbol(state.db(), IdentifierId::from("Show"));
+    let signature = module
+        .module_item_symbol(state.db(), IdentifierId::from("Box"))
+        .signature(state.db());
+
+    // Signature analysis is emulated, so that the test doesn't depend on it.
+    let parameter = GenericParameterData::alloc(state.db_mut(), package, DUMMY_LOCATION, None);
+    signature
+        .generic_parameter_scope(state.db())
+        .add_generic_parameter(state.db_mut(), IdentifierId::from("T"), parameter);
+
+    let show = TypeConstructor {
+        symbol: show,
+        arguments: vec![],
+    };
+    let predicate = PredicateData::alloc(
+        state.db_mut(),
+        package,
+        Type::GenericParameter(parameter),
+        vec![show.clone()],
+    );
+    signature.add_predicate(state.db_mut(), predicate);
+    signature.add_implemented_interface(state.db_mut(), show);
+
+    let documentation = Documentation::new(state.db(), package, &hir);
+    let box_ = documentation
+        .items()
+        .find(|item| item.name.as_str() == "Box")
+        .unwrap();
+
+    assert_eq!(box_.resolved_signature, None);
+
+    signature.set_analyzed(state.db_mut());
+
+    let json = documentation_to_json(&Documentation::new(state.db(), package, &hir));
+    let resolved = &json["modules"][1]["items"][0]["resolved_signature"];
+
+    assert_eq!(json["modules"][1]["items"][0]["name"], "Box");
+    assert_eq!(resolved["predicates"][0]["type"], "T");
+    assert_eq!(resolved["predicates"][0]["bounds"][0], "a.b.Show");
+    assert_eq!(resolved["implements"][0], "a.b.Show");
+}
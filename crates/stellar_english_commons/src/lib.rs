@@ -61,3 +61,4 @@
 pub mod enumeration;
 pub mod ordinal;
 pub mod pluralize;
+pub mod similarity;
@@ -0,0 +1,130 @@
+//! Allows to find names similar to a given one, which is useful for "did you mean" hints.
+
+/// Returns the edit distance between two strings.
+///
+/// The distance is the minimal number of single character insertions, deletions,
+/// substitutions and transpositions of two adjacent characters, required to turn
+/// one string into another (also known as the optimal string alignment distance).
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::similarity::edit_distance;
+///
+/// assert_eq!(edit_distance("Option", "Option"), 0);
+/// assert_eq!(edit_distance("Optoin", "Option"), 1);
+/// assert_eq!(edit_distance("kitten", "sitting"), 3);
+/// ```
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // Only three rows of the matrix are needed: the current one and two previous ones
+    // (the second previous row is used for transpositions).
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+
+        for j in 1..=b.len() {
+            let substitution_cost = usize::from(a[i - 1] != b[j - 1]);
+
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + substitution_cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+        }
+
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Returns the candidate, that is the most similar to a given name, or `None` if there
+/// are no candidates similar enough.
+///
+/// Candidates that differ from the name only in case are always preferred. Otherwise
+/// the candidate with the smallest [`edit_distance`] is chosen, if the distance doesn't
+/// exceed one third of the name's length. If multiple candidates have the same
+/// distance, the first one is returned.
+///
+/// # Example
+///
+/// ```
+/// use stellar_english_commons::similarity::find_similar;
+///
+/// assert_eq!(find_similar("Optoin", ["Result", "Option"]), Some("Option"));
+/// assert_eq!(find_similar("string", ["String", "str"]), Some("String"));
+/// assert_eq!(find_similar("foo", ["Option", "Result"]), None);
+/// ```
+#[must_use]
+pub fn find_similar<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    let lowercase_name = name.to_lowercase();
+
+    let mut best: Option<(&str, usize)> = None;
+
+    for candidate in candidates {
+        if candidate == name {
+            continue;
+        }
+
+        if candidate.to_lowercase() == lowercase_name {
+            return Some(candidate);
+        }
+
+        let distance = edit_distance(name, candidate);
+
+        if distance <= max_distance && best.is_none_or(|(_, best)| distance < best) {
+            best = Some((candidate, distance));
+        }
+    }
+
+    best.map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::similarity::{edit_distance, find_similar};
+
+    #[test]
+    fn test_edit_distance() {
+        let test_cases = vec![
+            ("", "", 0),
+            ("", "abc", 3),
+            ("abc", "", 3),
+            ("abc", "abc", 0),
+            ("abc", "abd", 1),
+            ("abc", "acb", 1),
+            ("abc", "ab", 1),
+            ("ab", "abc", 1),
+            ("ca", "abc", 3),
+            ("flaw", "lawn", 2),
+            ("Результат", "Результ", 2),
+        ];
+
+        for case in test_cases {
+            assert_eq!(edit_distance(case.0, case.1), case.2, "{case:?}");
+        }
+    }
+
+    #[test]
+    fn test_find_similar() {
+        assert_eq!(find_similar("Foo", ["Foo"]), None);
+        assert_eq!(find_similar("Fooo", ["Foo", "Fo"]), Some("Foo"));
+        assert_eq!(find_similar("ab", ["cd", "ac"]), Some("ac"));
+        assert_eq!(find_similar("Vec", ["vec", "Ve"]), Some("vec"));
+        assert_eq!(find_similar("HashMap", ["BTreeMap"]), None);
+    }
+}
@@ -41,6 +41,21 @@ impl<'p> PackagePathResolver<'p> {
         self.root.join("package.toml")
     }
 
+    /// Returns the path of the lockfile, that records packages used in a build.
+    #[inline]
+    #[must_use]
+    pub fn lockfile(&self) -> PathBuf {
+        self.root.join("package.lock")
+    }
+
+    /// Returns the path of the configuration file of the package manager, e.g.
+    /// the registry to use.
+    #[inline]
+    #[must_use]
+    pub fn config(&self) -> PathBuf {
+        self.root.join(".stellar").join("config.toml")
+    }
+
     /// Returns the path of the package source directory.
     #[inline]
     #[must_use]
@@ -181,11 +181,13 @@
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-pub use stellar_ast::{IdentifierAST, ImportPath, Literal, Path, Visibility};
+pub use stellar_ast::{Docstring, IdentifierAST, ImportPath, Literal, Path, Visibility};
 use stellar_ast::{ModuleItemKind, NegativeNumericLiteral};
 use stellar_filesystem::location::Location;
 use stellar_interner::{IdentifierId, PathId};
 
+pub mod visit;
+
 /// A type constructor, e.g. `Option[T]`.
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -208,6 +210,14 @@ pub enum Pattern {
     #[cfg_attr(feature = "serde", serde(rename = "negative_numeric_literal_pattern"))]
     NegativeNumericLiteral(NegativeNumericLiteral),
 
+    /// A grouped pattern, e.g. `(a)`. Kept in HIR, so that lints can report
+    /// unnecessary parentheses.
+    #[cfg_attr(feature = "serde", serde(rename = "grouped_pattern"))]
+    Grouped {
+        location: Location,
+        inner: Box<Self>,
+    },
+
     /// An identifier pattern, e.g. `f`, `list @ [3, ..]`.
     #[cfg_attr(feature = "serde", serde(rename = "identifier_pattern"))]
     Identifier {
@@ -276,7 +286,8 @@ impl Pattern {
     #[must_use]
     pub const fn location(&self) -> Location {
         match self {
-            Self::Identifier { location, .. }
+            Self::Grouped { location, .. }
+            | Self::Identifier { location, .. }
             | Self::NegativeNumericLiteral(
                 NegativeNumericLiteral::Float { location, .. }
                 | NegativeNumericLiteral::Integer { location, .. },
@@ -328,6 +339,14 @@ pub enum Type {
     #[cfg_attr(feature = "serde", serde(rename = "type_constructor"))]
     Constructor(TypeConstructor),
 
+    /// A parenthesized type, e.g. `(int32)`. Kept in HIR, so that lints can
+    /// report unnecessary parentheses.
+    #[cfg_attr(feature = "serde", serde(rename = "parenthesized_type"))]
+    Parenthesized {
+        location: Location,
+        inner: Box<Self>,
+    },
+
     /// A tuple type, e.g. `(int32, String, char)`.
     #[cfg_attr(feature = "serde", serde(rename = "tuple_type"))]
     Tuple {
@@ -364,6 +383,7 @@ impl Type {
             Self::Function { location, .. }
             | Self::Constructor(TypeConstructor { location, .. })
             | Self::InterfaceObject { location, .. }
+            | Self::Parenthesized { location, .. }
             | Self::Tuple { location, .. }
             | Self::Underscore { location } => *location,
         }
@@ -393,7 +413,7 @@ pub struct TypeAlias {
     pub value: Type,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A where clause item, e.g. `T: ToString`.
@@ -441,6 +461,14 @@ pub enum Expression {
         block: Vec<Statement>,
     },
 
+    /// Parenthesized expression, e.g. `(a + b)`. Kept in HIR, so that lints can
+    /// report unnecessary parentheses.
+    #[cfg_attr(feature = "serde", serde(rename = "parenthesized_expression"))]
+    Parenthesized {
+        location: Location,
+        inner: Box<Self>,
+    },
+
     /// Literal expression, e.g. `true`, `\"hello\"`, `1.2`.
     #[cfg_attr(feature = "serde", serde(rename = "literal_expression"))]
     Literal(Literal),
@@ -569,6 +597,7 @@ impl Expression {
             | Self::Struct { location, .. }
             | Self::Match { location, .. }
             | Self::Lambda { location, .. }
+            | Self::Parenthesized { location, .. }
             | Self::TypeArguments { location, .. }
             | Self::Underscore { location } => *location,
             Self::Literal(literal) => literal.location(),
@@ -674,7 +703,7 @@ pub struct FunctionSignature {
     pub where_predicates: Vec<WherePredicate>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// An enum module item.
@@ -692,7 +721,7 @@ pub struct Enum {
     pub implements: Option<Vec<TypeConstructor>>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// An interface module item.
@@ -706,7 +735,7 @@ pub struct Interface {
     pub methods: Vec<Function>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A struct module item.
@@ -724,7 +753,7 @@ pub struct Struct {
     pub implements: Option<Vec<TypeConstructor>>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A tuple-like struct module item.
@@ -742,7 +771,7 @@ pub struct TupleLikeStruct {
     pub implements: Option<Vec<TypeConstructor>>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A module item.
@@ -935,7 +964,7 @@ pub enum EnumItem {
         name: IdentifierAST,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-        docstring: Option<String>,
+        docstring: Option<Docstring>,
     },
     /// A tuple-like enum item, e.g. `None` in `enum Option<T> { Some(T), None }`.
     #[cfg_attr(feature = "serde", serde(rename = "tuple_like"))]
@@ -944,7 +973,7 @@ pub enum EnumItem {
         fields: Vec<TupleField>,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-        docstring: Option<String>,
+        docstring: Option<Docstring>,
     },
     /// A struct item, e.g. `A { b: T }` in `enum B { A { b: T } }`.
     #[cfg_attr(feature = "serde", serde(rename = "struct"))]
@@ -953,7 +982,7 @@ pub enum EnumItem {
         fields: Vec<StructField>,
 
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-        docstring: Option<String>,
+        docstring: Option<Docstring>,
     },
 }
 
@@ -996,7 +1025,7 @@ pub struct StructField {
     pub ty: Type,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
 
 /// A function parameter, e.g. `self`, `self: Self`, `a: uint32`.
@@ -1042,5 +1071,5 @@ pub struct Module {
     pub items: Vec<ModuleItem>,
 
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub docstring: Option<String>,
+    pub docstring: Option<Docstring>,
 }
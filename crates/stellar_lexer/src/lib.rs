@@ -76,7 +76,9 @@
 
 use std::{mem, str::Chars, string::String};
 
-use stellar_ast::token::{resolve_keyword, LexError, Punctuator, RawLexError, RawToken, Token};
+use stellar_ast::token::{
+    resolve_keyword, Edition, LexError, Punctuator, RawLexError, RawToken, Token,
+};
 use stellar_filesystem::location::{ByteOffset, Location};
 use stellar_interner::{IdentifierId, PathId};
 use stellar_stable_likely::unlikely;
@@ -136,6 +138,9 @@ pub struct Lexer<'s> {
     /// Content of the file being scanned.
     pub source: &'s str,
 
+    /// Edition of the language, that determines which words are keywords.
+    edition: Edition,
+
     /// Current character.
     ///
     /// **NOTE**: Can easily be stored as `Option<char>` without worrying about additional discriminant
@@ -180,6 +185,7 @@ impl<'s> Lexer<'s> {
         Self {
             filepath,
             source,
+            edition: Edition::default(),
             current: chars.next(),
             next: chars.next(),
             chars,
@@ -190,6 +196,22 @@ impl<'s> Lexer<'s> {
         }
     }
 
+    /// Builds a new lexer, that reserves keywords of a given edition. Words, that only
+    /// become keywords in later editions, are identifiers.
+    #[inline]
+    #[must_use]
+    pub const fn with_edition(mut self, edition: Edition) -> Self {
+        self.edition = edition;
+        self
+    }
+
+    /// Returns the edition of the language, that the lexer reserves keywords of.
+    #[inline]
+    #[must_use]
+    pub const fn edition(&self) -> Edition {
+        self.edition
+    }
+
     /// Returns a string being scanned early on (after processing escape sequences) and
     /// cleans internal lexer string buffer. So it must be used only once!
     #[inline]
@@ -680,7 +702,7 @@ impl<'s> Lexer<'s> {
             };
         }
 
-        if let Some(reserved) = resolve_keyword(name) {
+        if let Some(reserved) = resolve_keyword(name, self.edition) {
             Token {
                 raw: reserved.into(),
                 location: self.location_from(start_location),
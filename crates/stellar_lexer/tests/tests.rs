@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use stellar_ast::token::{RawLexError, RawToken::*};
+    use stellar_ast::token::{Edition, Keyword, RawLexError, RawToken::*};
     use stellar_interner::DUMMY_PATH_ID;
     use stellar_lexer::Lexer;
 
@@ -49,6 +49,18 @@ mod tests {
         "`test\n",
         Error(RawLexError::UnterminatedWrappedIdentifier)
     );
+    lexer_test!(wrapped_keyword, "`fun`", Identifier);
+    lexer_test!(edition_keyword, "async", Identifier);
+
+    #[test]
+    fn edition_keyword_in_later_edition() {
+        let mut lexer =
+            Lexer::new(DUMMY_PATH_ID, "async `async`").with_edition(Edition::Edition2024);
+
+        assert_eq!(lexer.next_token().raw, Keyword(Keyword::Async));
+        assert_eq!(lexer.next_token().raw, Identifier);
+    }
+
     lexer_test!(small_u, "'\\u{1E41}'", CharLiteral);
     lexer_test!(big_u, "\"\\U{0010FFFF}\"", StringLiteral);
 }
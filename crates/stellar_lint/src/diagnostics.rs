@@ -1,12 +1,17 @@
 use stellar_diagnostics::define_diagnostics;
 use stellar_filesystem::location::Location;
 
+use crate::parentheses::{
+    UNNECESSARY_GROUPED_PATTERN, UNNECESSARY_PARENTHESIZED_EXPRESSION,
+    UNNECESSARY_PARENTHESIZED_TYPE,
+};
+
 define_diagnostics! {
     diagnostic(warning) UnnecessaryGroupedPattern(
         self,
         location: Location
     ) {
-        code { "W000" }
+        code { UNNECESSARY_GROUPED_PATTERN.code }
         message { "unnecessary grouped pattern" }
         labels {
             primary { self.location.start_byte_location() }
@@ -18,7 +23,7 @@ define_diagnostics! {
         self,
         location: Location
     ) {
-        code { "W001" }
+        code { UNNECESSARY_PARENTHESIZED_EXPRESSION.code }
         message { "unnecessary parenthesized expression" }
         labels {
             primary { self.location.start_byte_location() }
@@ -30,7 +35,7 @@ define_diagnostics! {
         self,
         location: Location
     ) {
-        code { "W002" }
+        code { UNNECESSARY_PARENTHESIZED_TYPE.code }
         message { "unnecessary parenthesized type" }
         labels {
             primary { self.location.start_byte_location() }
@@ -0,0 +1,181 @@
+//! # Lints
+//!
+//! Lints are checked in a dedicated pass after lowering. Every lint is implemented
+//! by visiting HIR of every module and owns its declaration with the name, default
+//! level and description, see [`HirLint`]. Lints are added with
+//! [`register_hir_lint`], that puts the declaration into the lint registry of
+//! [`stellar_diagnostics::lint`], so that its level can be configured, and makes
+//! [`RunLints`] check the lint.
+//!
+//! Diagnostics are reported through [`LintContext`], that attaches the code of
+//! the lint to them, so that [`Diagnostics`] can apply the configured level and
+//! `allow` directives.
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png",
+    html_favicon_url = "https://raw.githubusercontent.com/quantumatic/stellar/main/additional/icon/stellar.png"
+)]
+#![warn(clippy::dbg_macro)]
+#![warn(
+    // rustc lint groups https://doc.rust-lang.org/rustc/lints/groups.html
+    future_incompatible,
+    let_underscore,
+    nonstandard_style,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    rust_2021_compatibility,
+    unused,
+    // rustc allowed-by-default lints https://doc.rust-lang.org/rustc/lints/listing/allowed-by-default.html
+    macro_use_extern_crate,
+    meta_variable_misuse,
+    missing_abi,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    non_ascii_idents,
+    noop_method_call,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unsafe_op_in_unsafe_fn,
+    unused_crate_dependencies,
+    unused_import_braces,
+    unused_lifetimes,
+    variant_size_differences,
+    // rustdoc lints https://doc.rust-lang.org/rustdoc/lints.html
+    rustdoc::broken_intra_doc_links,
+    rustdoc::private_intra_doc_links,
+    //rustdoc::missing_crate_level_docs,
+    rustdoc::private_doc_tests,
+    rustdoc::invalid_codeblock_attributes,
+    rustdoc::invalid_rust_codeblocks,
+    rustdoc::bare_urls,
+    // clippy categories https://doc.rust-lang.org/clippy/
+    clippy::all,
+    clippy::correctness,
+    clippy::suspicious,
+    clippy::style,
+    clippy::complexity,
+    clippy::perf,
+    clippy::pedantic,
+    clippy::nursery,
+)]
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::too_many_lines,
+    clippy::option_if_let_else,
+    clippy::unnested_or_patterns
+)]
+
+use parking_lot::RwLock;
+use stellar_database::{ModuleId, PackageId, State};
+use stellar_diagnostics::{
+    lint::{register_lint, Lint},
+    BuildDiagnostic, Diagnostics,
+};
+use stellar_fx_hash::FxHashMap;
+
+mod diagnostics;
+pub mod parentheses;
+
+/// A lint, that is checked by visiting HIR of a module.
+pub trait HirLint: Sync {
+    /// Returns the declaration of the lint.
+    fn lint(&self) -> &'static Lint;
+
+    /// Checks a module and reports diagnostics through a given context.
+    fn check_module(&self, module: &stellar_hir::Module, context: &mut LintContext<'_>);
+}
+
+/// Allows a lint to report diagnostics.
+#[derive(Debug)]
+pub struct LintContext<'d> {
+    lint: &'static Lint,
+    diagnostics: &'d mut Diagnostics,
+}
+
+impl<'d> LintContext<'d> {
+    /// Creates a new context for a given lint.
+    #[inline]
+    #[must_use]
+    pub const fn new(lint: &'static Lint, diagnostics: &'d mut Diagnostics) -> Self {
+        Self { lint, diagnostics }
+    }
+
+    /// Returns the declaration of the lint, that is being checked.
+    #[inline]
+    #[must_use]
+    pub const fn lint(&self) -> &'static Lint {
+        self.lint
+    }
+
+    /// Reports a diagnostic with the code of the lint.
+    ///
+    /// The diagnostic is reported even if the lint is allowed, so that
+    /// [`Diagnostics`] can tell which `allow` directives are used.
+    #[inline]
+    pub fn report(&mut self, diagnostic: impl BuildDiagnostic) {
+        self.diagnostics
+            .add_diagnostic(diagnostic.build().with_code(self.lint.code));
+    }
+}
+
+/// Lints added with [`register_hir_lint`].
+static HIR_LINTS: RwLock<Vec<&'static dyn HirLint>> = RwLock::new(Vec::new());
+
+/// Adds the declaration of a given lint to the lint registry, so that its level
+/// can be configured and it can be allowed with directives, and makes [`RunLints`]
+/// check it. Registering the same lint twice has no effect.
+///
+/// Lints must be registered before manifests and source files are parsed.
+///
+/// # Panics
+/// Panics if another lint with the same name or code is already registered.
+pub fn register_hir_lint(lint: &'static dyn HirLint) {
+    register_lint(lint.lint());
+
+    let mut lints = HIR_LINTS.write();
+
+    if !lints
+        .iter()
+        .any(|registered| registered.lint() == lint.lint())
+    {
+        lints.push(lint);
+    }
+}
+
+/// Registers all lints implemented in this crate, see [`register_hir_lint`].
+pub fn register_lints() {
+    register_hir_lint(&parentheses::UnnecessaryGroupedPattern);
+    register_hir_lint(&parentheses::UnnecessaryParenthesizedExpression);
+    register_hir_lint(&parentheses::UnnecessaryParenthesizedType);
+}
+
+/// Checks all HIR lints in modules of local packages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunLints;
+
+impl RunLints {
+    /// Checks all registered HIR lints in given lowered modules, that belong to
+    /// given packages.
+    ///
+    /// Modules of other packages, e.g. registry dependencies, whose lints are never
+    /// reported, are skipped.
+    pub fn run_all(
+        state: &mut State,
+        modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+        packages: &[PackageId],
+    ) {
+        for (id, module) in modules {
+            if !packages.contains(&id.package()) {
+                continue;
+            }
+
+            for lint in HIR_LINTS.read().iter() {
+                lint.check_module(
+                    module,
+                    &mut LintContext::new(lint.lint(), state.diagnostics_mut()),
+                );
+            }
+        }
+    }
+}
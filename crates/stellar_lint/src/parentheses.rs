@@ -0,0 +1,181 @@
+//! Lints, that report parentheses, which can be removed without changing
+//! the meaning of the code.
+
+use stellar_diagnostics::lint::{Lint, LintLevel};
+use stellar_filesystem::location::Location;
+use stellar_hir::{
+    visit::Visitor, Expression, MatchExpressionItem, Module, Pattern, Statement, Type,
+};
+
+use crate::{diagnostics, HirLint, LintContext};
+
+/// Reports patterns wrapped in parentheses twice, e.g. `((a))`.
+pub const UNNECESSARY_GROUPED_PATTERN: Lint = Lint {
+    name: "unnecessary_grouped_pattern",
+    code: "W000",
+    group: "style",
+    default_level: LintLevel::Warn,
+    description: "patterns wrapped in parentheses, that are not needed",
+};
+
+/// Reports parentheses around conditions, scrutinees and match arms, as well as
+/// expressions wrapped in parentheses twice, e.g. `if (a) {}`, `((a + b))`.
+pub const UNNECESSARY_PARENTHESIZED_EXPRESSION: Lint = Lint {
+    name: "unnecessary_parenthesized_expression",
+    code: "W001",
+    group: "style",
+    default_level: LintLevel::Warn,
+    description: "expressions wrapped in parentheses, that are not needed",
+};
+
+/// Reports types wrapped in parentheses twice, e.g. `((int32))`.
+pub const UNNECESSARY_PARENTHESIZED_TYPE: Lint = Lint {
+    name: "unnecessary_parenthesized_type",
+    code: "W002",
+    group: "style",
+    default_level: LintLevel::Warn,
+    description: "types wrapped in parentheses, that are not needed",
+};
+
+/// Reports grouped patterns wrapped in parentheses, e.g. `((a))`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnnecessaryGroupedPattern;
+
+impl HirLint for UnnecessaryGroupedPattern {
+    fn lint(&self) -> &'static Lint {
+        &UNNECESSARY_GROUPED_PATTERN
+    }
+
+    fn check_module(&self, module: &Module, context: &mut LintContext<'_>) {
+        GroupedPatternVisitor { context }.visit_module(module);
+    }
+}
+
+struct GroupedPatternVisitor<'c, 'd> {
+    context: &'c mut LintContext<'d>,
+}
+
+impl Visitor for GroupedPatternVisitor<'_, '_> {
+    fn visit_grouped_pattern(&mut self, _: Location, inner: &Pattern) {
+        if let Pattern::Grouped { location, .. } = inner {
+            self.context
+                .report(diagnostics::UnnecessaryGroupedPattern::new(*location));
+        }
+
+        self.visit_pattern(inner);
+    }
+}
+
+/// Reports parenthesized expressions wrapped in parentheses, e.g. `((a + b))`,
+/// and parentheses around conditions, scrutinees and match arms, e.g. `if (a) {}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnnecessaryParenthesizedExpression;
+
+impl HirLint for UnnecessaryParenthesizedExpression {
+    fn lint(&self) -> &'static Lint {
+        &UNNECESSARY_PARENTHESIZED_EXPRESSION
+    }
+
+    fn check_module(&self, module: &Module, context: &mut LintContext<'_>) {
+        ParenthesizedExpressionVisitor { context }.visit_module(module);
+    }
+}
+
+struct ParenthesizedExpressionVisitor<'c, 'd> {
+    context: &'c mut LintContext<'d>,
+}
+
+impl ParenthesizedExpressionVisitor<'_, '_> {
+    fn check(&mut self, expression: &Expression) {
+        if let Expression::Parenthesized { location, .. } = expression {
+            self.context
+                .report(diagnostics::UnnecessaryParenthesizedExpression::new(
+                    *location,
+                ));
+        }
+    }
+}
+
+impl Visitor for ParenthesizedExpressionVisitor<'_, '_> {
+    fn visit_parenthesized_expression(&mut self, _: Location, inner: &Expression) {
+        self.check(inner);
+        self.visit_expression(inner);
+    }
+
+    fn visit_if_expression(
+        &mut self,
+        _: Location,
+        if_blocks: &[(Expression, Vec<Statement>)],
+        r#else: Option<&[Statement]>,
+    ) {
+        for (condition, block) in if_blocks {
+            self.check(condition);
+            self.visit_expression(condition);
+            self.visit_statements_block(block);
+        }
+
+        if let Some(r#else) = r#else {
+            self.visit_statements_block(r#else);
+        }
+    }
+
+    fn visit_while_expression(
+        &mut self,
+        _: Location,
+        condition: &Expression,
+        statements_block: &[Statement],
+    ) {
+        self.check(condition);
+        self.visit_expression(condition);
+        self.visit_statements_block(statements_block);
+    }
+
+    fn visit_match_expression(
+        &mut self,
+        _: Location,
+        expression: &Expression,
+        block: &[MatchExpressionItem],
+    ) {
+        self.check(expression);
+        self.visit_expression(expression);
+
+        for item in block {
+            self.visit_match_expression_item(item);
+        }
+    }
+
+    fn visit_match_expression_item(&mut self, item: &MatchExpressionItem) {
+        self.check(&item.right);
+        self.visit_pattern(&item.left);
+        self.visit_expression(&item.right);
+    }
+}
+
+/// Reports parenthesized types wrapped in parentheses, e.g. `((int32))`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnnecessaryParenthesizedType;
+
+impl HirLint for UnnecessaryParenthesizedType {
+    fn lint(&self) -> &'static Lint {
+        &UNNECESSARY_PARENTHESIZED_TYPE
+    }
+
+    fn check_module(&self, module: &Module, context: &mut LintContext<'_>) {
+        ParenthesizedTypeVisitor { context }.visit_module(module);
+    }
+}
+
+struct ParenthesizedTypeVisitor<'c, 'd> {
+    context: &'c mut LintContext<'d>,
+}
+
+impl Visitor for ParenthesizedTypeVisitor<'_, '_> {
+    fn visit_parenthesized_type(&mut self, _: Location, inner: &Type) {
+        if let Type::Parenthesized { location, .. } = inner {
+            self.context
+                .report(diagnostics::UnnecessaryParenthesizedType::new(*location));
+        }
+
+        self.visit_type(inner);
+    }
+}
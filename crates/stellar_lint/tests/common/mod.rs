@@ -0,0 +1,37 @@
+//! Helpers shared by lint tests.
+
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID};
+use stellar_lint::{register_lints, RunLints};
+use stellar_parser::parse_module;
+
+/// Lowers a module, checks lints and returns codes of all diagnostics.
+pub fn lint_codes(source_code: &str) -> Vec<String> {
+    lint_codes_in(State::new(), "test.sr", source_code)
+}
+
+/// Lowers a module located at a given path, checks lints and returns codes of
+/// all diagnostics.
+pub fn lint_codes_in(mut state: State, filepath: &str, source_code: &str) -> Vec<String> {
+    register_lints();
+
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        PathId::from(filepath),
+        source_code,
+    );
+
+    let hir = LowerToHir::run_all(vec![parse_result]);
+    RunLints::run_all(&mut state, &hir, &[package]);
+
+    state
+        .diagnostics()
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code.clone())
+        .collect()
+}
@@ -0,0 +1,75 @@
+use common::{lint_codes, lint_codes_in};
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_diagnostics::lint::{LintLevel, LintLevels};
+use stellar_interner::{PathId, DUMMY_IDENTIFIER_ID};
+use stellar_lint::{register_lints, RunLints};
+use stellar_parser::parse_module;
+
+mod common;
+
+#[test]
+fn grouped_pattern() {
+    assert_eq!(lint_codes("fun f() { let ((a)) = 1; }"), ["W000"]);
+    assert!(lint_codes("fun f() { let (a) = 1; }").is_empty());
+}
+
+#[test]
+fn parenthesized_expression() {
+    assert_eq!(lint_codes("fun f() { if (a) {} }"), ["W001"]);
+    assert_eq!(lint_codes("fun f() { while (a) {} }"), ["W001"]);
+    assert_eq!(lint_codes("fun f() { let a = ((b + c)); }"), ["W001"]);
+    assert_eq!(
+        lint_codes("fun f() { match (a) { _ -> (b) } }"),
+        ["W001", "W001"]
+    );
+    assert!(lint_codes("fun f() { let a = (b + c) * d; }").is_empty());
+}
+
+#[test]
+fn parenthesized_type() {
+    assert_eq!(lint_codes("fun f(a: ((int32))) {}"), ["W002"]);
+    assert!(lint_codes("fun f(a: (int32)) {}").is_empty());
+}
+
+#[test]
+fn allowed() {
+    assert!(lint_codes("// stellar: allow(style)\nfun f() { if (a) {} }").is_empty());
+}
+
+#[test]
+fn package_lint_levels() {
+    let mut state = State::new();
+    state
+        .diagnostics_mut()
+        .set_lint_levels(LintLevels::new().with_level("style", LintLevel::Deny));
+    state
+        .diagnostics_mut()
+        .set_package_lint_levels("dependency", LintLevels::new().with_cap(LintLevel::Allow));
+
+    assert!(lint_codes_in(state.clone(), "dependency/test.sr", "fun f() { if (a) {} }").is_empty());
+    assert_eq!(
+        lint_codes_in(state, "app/test.sr", "fun f() { if (a) {} }"),
+        ["W001"]
+    );
+}
+
+#[test]
+fn other_packages() {
+    register_lints();
+
+    let mut state = State::new();
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        DUMMY_IDENTIFIER_ID.into(),
+        PathId::from("test.sr"),
+        "fun f() { let ((a)) = 1; }",
+    );
+
+    let hir = LowerToHir::run_all(vec![parse_result]);
+    RunLints::run_all(&mut state, &hir, &[]);
+
+    assert!(state.diagnostics().diagnostics.is_empty());
+}
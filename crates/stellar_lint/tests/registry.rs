@@ -0,0 +1,63 @@
+use common::{lint_codes, lint_codes_in};
+use stellar_database::State;
+use stellar_diagnostics::{
+    diagnostic::Diagnostic,
+    lint::{find_lint, is_lint_or_group, register_lint, Lint, LintLevel, LintLevels},
+};
+use stellar_lint::{register_hir_lint, HirLint, LintContext};
+
+mod common;
+
+/// Reports modules without items.
+const EMPTY_MODULE: Lint = Lint {
+    name: "empty_module",
+    code: "W999",
+    group: "testing",
+    default_level: LintLevel::Warn,
+    description: "modules without items",
+};
+
+struct EmptyModule;
+
+impl HirLint for EmptyModule {
+    fn lint(&self) -> &'static Lint {
+        &EMPTY_MODULE
+    }
+
+    fn check_module(&self, module: &stellar_hir::Module, context: &mut LintContext<'_>) {
+        if module.items.is_empty() {
+            context.report(Diagnostic::warning().with_message("empty module"));
+        }
+    }
+}
+
+#[test]
+fn registered_lint() {
+    register_hir_lint(&EmptyModule);
+
+    assert_eq!(find_lint("empty_module"), Some(&EMPTY_MODULE));
+    assert!(is_lint_or_group("testing"));
+    assert_eq!(lint_codes(""), ["W999"]);
+    assert!(lint_codes("fun main() {}").is_empty());
+
+    let mut state = State::new();
+    state
+        .diagnostics_mut()
+        .set_lint_levels(LintLevels::new().with_level("testing", LintLevel::Allow));
+
+    assert!(lint_codes_in(state, "test.sr", "").is_empty());
+}
+
+#[test]
+#[should_panic(expected = "conflicts with the registered lint `unused_allow`")]
+fn conflicting_code() {
+    const UNUSED_DIRECTIVE: Lint = Lint {
+        name: "unused_directive",
+        code: "W007",
+        group: "unused",
+        default_level: LintLevel::Warn,
+        description: "directives, that have no effect",
+    };
+
+    register_lint(&UNUSED_DIRECTIVE);
+}
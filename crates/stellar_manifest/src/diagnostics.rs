@@ -0,0 +1,141 @@
+//! Diagnostics reported when parsing a manifest.
+
+use stellar_diagnostics::define_diagnostics;
+use stellar_filesystem::location::Location;
+
+define_diagnostics! {
+    /// Diagnostic related to a manifest, that is not a valid TOML document or
+    /// doesn't have the expected structure.
+    diagnostic(error) InvalidManifest(
+        self,
+        message: String,
+        location: Location
+    ) {
+        code { "E010" }
+        message { self.message }
+        labels {
+            primary { self.location }
+        }
+    }
+
+    /// Diagnostic related to a dependency, that has neither `version`, `path` nor `git`.
+    diagnostic(error) DependencyWithoutSource(
+        self,
+        name: String,
+        location: Location
+    ) {
+        code { "E011" }
+        message { format!("dependency `{}` specifies neither `version`, `path` nor `git`", self.name) }
+        labels {
+            primary { self.location }
+        }
+        notes {
+            format!("help: add a version requirement, e.g. `{} = {{ version = \"1.0\" }}`", self.name)
+        }
+    }
+
+    /// Diagnostic related to a dependency with conflicting sources, e.g. both `path`
+    /// and `git`.
+    diagnostic(error) InvalidDependencySource(
+        self,
+        name: String,
+        reason: &'static str,
+        location: Location
+    ) {
+        code { "E015" }
+        message { format!("invalid source of dependency `{}`", self.name) }
+        labels {
+            primary { self.location => self.reason }
+        }
+    }
+
+    /// Diagnostic related to a license, that is not a valid SPDX license expression.
+    diagnostic(error) InvalidLicense(
+        self,
+        license: String,
+        reason: String,
+        location: Location,
+        suggestion: Option<String>
+    ) {
+        code { "E016" }
+        message { format!("invalid license expression `{}`", self.license) }
+        labels {
+            primary { self.location => self.reason }
+        }
+        notes {
+            "note: licenses are SPDX expressions, e.g. `MIT OR Apache-2.0`, see https://spdx.org/licenses"
+        }
+        optional_notes {
+            self.suggestion.map(|suggestion| format!("help: did you mean `{suggestion}`?"))
+        }
+    }
+
+    /// Diagnostic related to a build target in the `[lib]` or a `[[bin]]` table,
+    /// that cannot be built.
+    diagnostic(error) InvalidTarget(
+        self,
+        target: String,
+        reason: String,
+        location: Location
+    ) {
+        code { "E017" }
+        message { format!("invalid {} target", self.target) }
+        labels {
+            primary { self.location => self.reason }
+        }
+    }
+
+    /// Diagnostic related to a member of a feature in the `[features]` table, that
+    /// is neither a feature nor an optional dependency.
+    diagnostic(error) UnknownFeatureMember(
+        self,
+        feature: String,
+        member: String,
+        reason: String,
+        location: Location
+    ) {
+        code { "E012" }
+        message { format!("feature `{}` enables unknown `{}`", self.feature, self.member) }
+        labels {
+            primary { self.location => self.reason }
+        }
+        notes {
+            "note: features can enable other features and optional dependencies with `dep:<name>`"
+        }
+    }
+
+    /// Diagnostic related to a lint or a lint group in the `[lints]` table, that
+    /// doesn't exist.
+    diagnostic(warning) UnknownLint(
+        self,
+        name: String,
+        location: Location,
+        similar_name: Option<String>
+    ) {
+        code { "W006" }
+        message { format!("unknown lint `{}`", self.name) }
+        labels {
+            primary { self.location }
+        }
+        optional_notes {
+            self.similar_name.map(|name| format!("help: did you mean `{name}`?"))
+        }
+    }
+
+    /// Diagnostic related to a key in the manifest, that is not recognized and ignored.
+    diagnostic(warning) UnknownManifestKey(
+        self,
+        key: String,
+        location: Location,
+        similar_key: Option<String>
+    ) {
+        code { "W004" }
+        message { format!("unused manifest key `{}`", self.key) }
+        labels {
+            primary { self.location }
+        }
+        optional_notes {
+            self.similar_key.map(|key| format!("help: did you mean `{key}`?"))
+        }
+    }
+}
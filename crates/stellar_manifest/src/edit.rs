@@ -0,0 +1,165 @@
+//! Edits manifests, preserving formatting and comments.
+
+use std::fmt;
+
+use toml_edit::{value, DocumentMut, InlineTable, Item, Table, TableLike};
+
+use crate::{Edition, TomlDependency};
+
+/// A table of the manifest, that contains dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyTable {
+    /// The `[dependencies]` table.
+    Dependencies,
+    /// The `[dev-dependencies]` table.
+    DevDependencies,
+}
+
+impl DependencyTable {
+    /// Returns the name of the table in the manifest.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dependencies => "dependencies",
+            Self::DevDependencies => "dev-dependencies",
+        }
+    }
+}
+
+impl fmt::Display for DependencyTable {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Edits the source of a manifest. Only modified entries are changed, so the
+/// rest of the file, including comments and formatting, stays the same.
+///
+/// ```
+/// use stellar_manifest::{DependencyTable, ManifestEditor, TomlDependency};
+///
+/// let mut editor = ManifestEditor::new(
+///     "[package] # metadata
+/// name = \"server\"
+/// version = \"1.0.0\"
+/// ",
+/// )
+/// .unwrap();
+///
+/// editor
+///     .add_dependency(
+///         DependencyTable::Dependencies,
+///         "http",
+///         &TomlDependency::new().with_path("../http"),
+///     )
+///     .unwrap();
+///
+/// assert_eq!(
+///     editor.to_string(),
+///     "[package] # metadata
+/// name = \"server\"
+/// version = \"1.0.0\"
+///
+/// [dependencies]
+/// http = { path = \"../http\" }
+/// "
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManifestEditor {
+    document: DocumentMut,
+}
+
+impl ManifestEditor {
+    /// Parses the source of a manifest.
+    ///
+    /// # Errors
+    /// Returns an error if the source is not a valid TOML document.
+    pub fn new(source: &str) -> Result<Self, String> {
+        source
+            .parse()
+            .map(|document| Self { document })
+            .map_err(|err: toml_edit::TomlError| err.message().to_owned())
+    }
+
+    /// Inserts a dependency into a given table, or updates it if it's already
+    /// there. Only `version`, `path` and `author` of the dependency are written,
+    /// other keys of an existing entry are preserved.
+    ///
+    /// # Errors
+    /// Returns an error if the table or the existing entry is not a table.
+    pub fn add_dependency(
+        &mut self,
+        table: DependencyTable,
+        name: &str,
+        dependency: &TomlDependency,
+    ) -> Result<(), String> {
+        let dependencies = self
+            .document
+            .entry(table.as_str())
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_like_mut()
+            .ok_or_else(|| format!("`{table}` is not a table"))?;
+
+        let entry = dependencies
+            .entry(name)
+            .or_insert_with(|| value(InlineTable::new()))
+            .as_table_like_mut()
+            .ok_or_else(|| format!("dependency `{name}` in `{table}` is not a table"))?;
+
+        set(
+            entry,
+            "version",
+            dependency.version.as_ref().map(ToString::to_string),
+        );
+        set(entry, "path", dependency.path.clone());
+        set(entry, "author", dependency.author.clone());
+
+        // Normalizes whitespace around the modified entry.
+        TableLike::fmt(entry);
+
+        Ok(())
+    }
+
+    /// Sets the edition of the package, keeping the key in place if it's already
+    /// there.
+    ///
+    /// # Errors
+    /// Returns an error if the `[package]` table is missing.
+    pub fn set_edition(&mut self, edition: Edition) -> Result<(), String> {
+        let package = self
+            .document
+            .get_mut("package")
+            .and_then(Item::as_table_like_mut)
+            .ok_or_else(|| "`[package]` table is missing".to_owned())?;
+
+        set(package, "edition", Some(edition.to_string()));
+
+        Ok(())
+    }
+
+    /// Removes a dependency from a given table. Returns `false` if the dependency
+    /// is not found.
+    pub fn remove_dependency(&mut self, table: DependencyTable, name: &str) -> bool {
+        self.document
+            .get_mut(table.as_str())
+            .and_then(Item::as_table_like_mut)
+            .and_then(|dependencies| dependencies.remove(name))
+            .is_some()
+    }
+}
+
+impl fmt::Display for ManifestEditor {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.document.fmt(f)
+    }
+}
+
+fn set(table: &mut dyn TableLike, key: &str, new_value: Option<String>) {
+    if let Some(new_value) = new_value {
+        table.insert(key, value(new_value));
+    }
+}
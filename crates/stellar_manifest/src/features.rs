@@ -0,0 +1,163 @@
+//! Resolves features, that are enabled in a package.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use stellar_english_commons::similarity::find_similar;
+
+use crate::TomlManifest;
+
+/// Features requested for a package, e.g. with `--features` or in the manifest
+/// of a package, that depends on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureSelection {
+    /// Features to enable in addition to the default ones.
+    pub features: BTreeSet<String>,
+
+    /// Whether every feature of the package is enabled.
+    pub all_features: bool,
+
+    /// Whether the `default` feature is enabled.
+    pub default_features: bool,
+}
+
+impl Default for FeatureSelection {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            features: BTreeSet::new(),
+            all_features: false,
+            default_features: true,
+        }
+    }
+}
+
+impl FeatureSelection {
+    /// Returns a new feature selection, that only enables the `default` feature.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a new feature selection with given features enabled.
+    #[inline]
+    #[must_use]
+    pub fn with_features(mut self, features: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    /// Builds a new feature selection, that enables every feature.
+    #[inline]
+    #[must_use]
+    pub const fn with_all_features(mut self) -> Self {
+        self.all_features = true;
+        self
+    }
+
+    /// Builds a new feature selection with the `default` feature disabled.
+    #[inline]
+    #[must_use]
+    pub const fn without_default_features(mut self) -> Self {
+        self.default_features = false;
+        self
+    }
+
+    /// Merges another selection into the current one, so that every feature
+    /// requested by either of them is enabled. Returns `true` if the selection
+    /// changed.
+    pub fn merge(&mut self, other: &Self) -> bool {
+        let before = self.clone();
+
+        self.features.extend(other.features.iter().cloned());
+        self.all_features |= other.all_features;
+        self.default_features |= other.default_features;
+
+        *self != before
+    }
+}
+
+/// Features and optional dependencies, that are enabled in a package.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EnabledFeatures {
+    /// Names of enabled features.
+    pub features: BTreeSet<String>,
+
+    /// Names of enabled optional dependencies.
+    pub optional_dependencies: BTreeSet<String>,
+}
+
+impl EnabledFeatures {
+    /// Returns `true` if a dependency with a given name is used.
+    #[inline]
+    #[must_use]
+    pub fn is_dependency_enabled(&self, manifest: &TomlManifest, name: &str) -> bool {
+        manifest
+            .dependencies
+            .as_ref()
+            .and_then(|dependencies| dependencies.get(name))
+            .is_some_and(|dependency| {
+                !dependency.is_optional() || self.optional_dependencies.contains(name)
+            })
+    }
+}
+
+impl TomlManifest {
+    /// Returns features and optional dependencies enabled by a given selection,
+    /// following features, that enable other features.
+    ///
+    /// The `default` feature is only enabled, if it's declared in the manifest.
+    ///
+    /// # Errors
+    /// Returns an error if the selection contains a feature, that doesn't exist.
+    pub fn enabled_features(
+        &self,
+        selection: &FeatureSelection,
+    ) -> Result<EnabledFeatures, String> {
+        let empty = BTreeMap::new();
+        let declared = self.features.as_ref().unwrap_or(&empty);
+        let mut enabled = EnabledFeatures::default();
+        let mut stack = Vec::new();
+
+        for feature in &selection.features {
+            if !declared.contains_key(feature) {
+                let package = self
+                    .package
+                    .as_ref()
+                    .map_or("the package", |package| package.name.as_str());
+
+                let hint = find_similar(feature, declared.keys().map(String::as_str))
+                    .map(|similar| format!(", did you mean `{similar}`?"))
+                    .unwrap_or_default();
+
+                return Err(format!(
+                    "`{package}` doesn't have feature `{feature}`{hint}"
+                ));
+            }
+
+            stack.push(feature.as_str());
+        }
+
+        if selection.all_features {
+            stack.extend(declared.keys().map(String::as_str));
+        } else if selection.default_features && declared.contains_key("default") {
+            stack.push("default");
+        }
+
+        while let Some(feature) = stack.pop() {
+            if !enabled.features.insert(feature.to_owned()) {
+                continue;
+            }
+
+            for member in declared.get(feature).into_iter().flatten() {
+                if let Some(dependency) = member.strip_prefix("dep:") {
+                    enabled.optional_dependencies.insert(dependency.to_owned());
+                } else {
+                    stack.push(member);
+                }
+            }
+        }
+
+        Ok(enabled)
+    }
+}
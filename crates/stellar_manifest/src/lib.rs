@@ -9,6 +9,7 @@
 //! [package]
 //! name = "json"
 //! version = "0.1.0"
+//! edition = "2024"
 //! author = "abs0luty"
 //! license = "MIT"
 //! repository = "https://github.com/abs0luty/json"
@@ -23,6 +24,122 @@
 //! The first part is a general information about the package, the second part is optional
 //! and contains information about the dependencies of the current package.
 //!
+//! Package versions must follow [Semantic Versioning], while dependency versions are
+//! version requirements, e.g. `^1.2`, `~0.3`, `>=1, <2` or `=1.0.0`. A bare version
+//! like `1.0.1` is the same as `^1.0.1`.
+//!
+//! [Semantic Versioning]: https://semver.org
+//!
+//! The `edition` determines which words are reserved as keywords in the package
+//! sources. Packages without it use the first edition, `2023`.
+//!
+//! Dependencies can also come from a git repository, given by a URL or a path, and
+//! are checked out at a branch, a tag or a revision (the default branch otherwise):
+//!
+//! ```toml
+//! [dependencies]
+//! json = { git = "https://github.com/abs0luty/json", tag = "v0.1.0" }
+//! ```
+//!
+//! # Targets
+//!
+//! A package is a library, whose module tree starts at `src/package.sr`, and can
+//! also contain binaries. Every binary has its own module tree, starting at
+//! `src/bin/<name>.sr` by default, and must define `pub fun main()`:
+//!
+//! ```toml
+//! [lib]
+//! path = "src/package.sr"
+//!
+//! [[bin]]
+//! name = "server"
+//! path = "src/bin/server.sr"
+//! ```
+//!
+//! Submodules of a root module are located in the directory with the same name,
+//! e.g. `src/bin/server/` for `src/bin/server.sr`, or in the same directory for
+//! `package.sr`.
+//!
+//! # Profiles
+//!
+//! Code is compiled according to a build profile: `dev` by default, or `release`
+//! with `--release`. Default settings of profiles can be overridden:
+//!
+//! ```toml
+//! [profile.dev]
+//! opt-level = 1
+//!
+//! [profile.release]
+//! debug = true
+//! overflow-checks = true
+//! strip-assertions = false
+//! warnings-as-errors = true
+//! ```
+//!
+//! In a workspace, profiles are only read from the manifest of the workspace root.
+//!
+//! # Lints
+//!
+//! Levels of lints, e.g. `unnecessary_parenthesized_type`, or lint groups, e.g.
+//! `style`, are set in the `[lints]` table to `allow`, `warn`, `deny` or `forbid`.
+//! Levels of individual lints take precedence over levels of groups:
+//!
+//! ```toml
+//! [lints]
+//! style = "deny"
+//! unnecessary_grouped_pattern = "allow"
+//! ```
+//!
+//! A lint can also be allowed for a single item, method or statement with
+//! a `// stellar: allow(<lint>, ...)` comment placed before it.
+//!
+//! # Workspaces
+//!
+//! Multiple packages can be grouped into a workspace with the `[workspace]` table.
+//! Members are listed with glob patterns relative to the workspace root, and can
+//! inherit dependencies declared in `[workspace.dependencies]`:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["packages/*"]
+//!
+//! [workspace.dependencies]
+//! http = { version = "1.0.0" }
+//! ```
+//!
+//! ```toml
+//! [package]
+//! name = "server"
+//! version = "0.1.0"
+//!
+//! [dependencies]
+//! http = { workspace = true }
+//! ```
+//!
+//! The manifest in the workspace root may omit the `[package]` table, if the root
+//! is not a package itself.
+//!
+//! # Features
+//!
+//! Features are named flags, that enable optional dependencies and code guarded
+//! by `// stellar: cfg(feature = "<name>")`. A feature can enable other features
+//! and optional dependencies with `dep:<name>`. The `default` feature is enabled
+//! unless `default-features = false` is specified by the dependent package:
+//!
+//! ```toml
+//! [features]
+//! default = ["json"]
+//! json = ["dep:serialization_engine"]
+//!
+//! [dependencies]
+//! serialization_engine = { path = "../serialization_engine", optional = true }
+//!
+//! [dev-dependencies]
+//! test_utils = { path = "../test_utils" }
+//! ```
+//!
+//! Dev dependencies are only used when testing the package, e.g. in doctests.
+//!
 //! [TOML]: https://toml.io/en/v1.0.0
 
 #![doc(
@@ -85,11 +202,35 @@
     clippy::unnested_or_patterns
 )]
 
-use std::collections::BTreeMap;
+mod diagnostics;
+mod edit;
+mod features;
+pub mod license;
+pub mod lockfile;
+pub mod registry;
 
-use serde::{de::IntoDeserializer, Deserialize, Serialize};
-use toml as _;
-use toml_edit::Document;
+use std::{collections::BTreeMap, fmt, ops::Range, path::Path};
+
+pub use edit::{DependencyTable, ManifestEditor};
+pub use features::{EnabledFeatures, FeatureSelection};
+pub use semver::{Version, VersionReq};
+use serde::{Deserialize, Deserializer, Serialize};
+pub use stellar_ast::token::Edition;
+pub use stellar_diagnostics::lint::LintLevel;
+use stellar_diagnostics::{
+    lint::{find_lint, is_lint_or_group, lint_and_group_names, LintLevels},
+    Diagnostics,
+};
+use stellar_english_commons::similarity::find_similar;
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::PathId;
+use toml_edit::{ImDocument, TableLike};
+
+use crate::diagnostics::{
+    DependencyWithoutSource, InvalidDependencySource, InvalidLicense, InvalidManifest,
+    InvalidTarget, UnknownFeatureMember, UnknownLint, UnknownManifestKey,
+};
+use crate::license::LicenseExpression;
 
 /// Describes the package manifest, which contains information about the package.
 ///
@@ -98,10 +239,26 @@ use toml_edit::Document;
 /// [crate level documentation]: crate
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct TomlManifest {
-    /// The `[package]` section of the manifest.
-    pub package: TomlPackage,
+    /// The `[package]` section of the manifest. Can only be omitted in a workspace root.
+    pub package: Option<TomlPackage>,
+    /// The `[workspace]` section of the manifest.
+    pub workspace: Option<TomlWorkspace>,
     /// The `[dependencies]` section of the manifest.
     pub dependencies: Option<BTreeMap<String, TomlDependency>>,
+    /// The `[dev-dependencies]` section of the manifest.
+    #[serde(rename = "dev-dependencies")]
+    pub dev_dependencies: Option<BTreeMap<String, TomlDependency>>,
+    /// The `[features]` section of the manifest.
+    pub features: Option<BTreeMap<String, Vec<String>>>,
+    /// The `[lib]` section of the manifest.
+    pub lib: Option<TomlLibrary>,
+    /// The `[[bin]]` sections of the manifest.
+    pub bin: Option<Vec<TomlBinary>>,
+    /// The `[profile]` section of the manifest.
+    pub profile: Option<TomlProfiles>,
+    /// The `[lints]` section of the manifest: levels of lints and lint groups by
+    /// their names.
+    pub lints: Option<BTreeMap<String, LintLevel>>,
 }
 
 impl TomlManifest {
@@ -110,11 +267,43 @@ impl TomlManifest {
     #[must_use]
     pub const fn new(package: TomlPackage) -> Self {
         Self {
-            package,
+            package: Some(package),
+            workspace: None,
             dependencies: None,
+            dev_dependencies: None,
+            features: None,
+            lib: None,
+            bin: None,
+            profile: None,
+            lints: None,
         }
     }
 
+    /// Returns a new toml manifest struct of a workspace root, that is not a package.
+    #[inline]
+    #[must_use]
+    pub const fn new_virtual(workspace: TomlWorkspace) -> Self {
+        Self {
+            package: None,
+            workspace: Some(workspace),
+            dependencies: None,
+            dev_dependencies: None,
+            features: None,
+            lib: None,
+            bin: None,
+            profile: None,
+            lints: None,
+        }
+    }
+
+    /// Builds a new toml manifest struct with a given workspace.
+    #[inline]
+    #[must_use]
+    pub fn with_workspace(mut self, workspace: TomlWorkspace) -> Self {
+        self.workspace = Some(workspace);
+        self
+    }
+
     /// Returns a new toml manifest struct with given dependencies.
     #[inline]
     #[must_use]
@@ -130,6 +319,252 @@ impl TomlManifest {
         );
         self
     }
+
+    /// Returns a new toml manifest struct with given dev dependencies.
+    #[inline]
+    #[must_use]
+    pub fn with_dev_dependencies(
+        mut self,
+        dev_dependencies: impl IntoIterator<Item = (impl Into<String>, TomlDependency)>,
+    ) -> Self {
+        self.dev_dependencies = Some(
+            dev_dependencies
+                .into_iter()
+                .map(|(s, d)| (s.into(), d))
+                .collect(),
+        );
+        self
+    }
+
+    /// Returns a new toml manifest struct with given features.
+    #[inline]
+    #[must_use]
+    pub fn with_features(
+        mut self,
+        features: impl IntoIterator<Item = (impl Into<String>, Vec<String>)>,
+    ) -> Self {
+        self.features = Some(features.into_iter().map(|(s, f)| (s.into(), f)).collect());
+        self
+    }
+
+    /// Builds a new toml manifest struct with a given library target.
+    #[inline]
+    #[must_use]
+    pub fn with_library(mut self, library: TomlLibrary) -> Self {
+        self.lib = Some(library);
+        self
+    }
+
+    /// Builds a new toml manifest struct with given binary targets.
+    #[inline]
+    #[must_use]
+    pub fn with_binaries(mut self, binaries: impl IntoIterator<Item = TomlBinary>) -> Self {
+        self.bin = Some(binaries.into_iter().collect());
+        self
+    }
+
+    /// Builds a new toml manifest struct with given build profiles.
+    #[inline]
+    #[must_use]
+    pub const fn with_profiles(mut self, profiles: TomlProfiles) -> Self {
+        self.profile = Some(profiles);
+        self
+    }
+
+    /// Builds a new toml manifest struct with given levels of lints.
+    #[inline]
+    #[must_use]
+    pub fn with_lints(
+        mut self,
+        lints: impl IntoIterator<Item = (impl Into<String>, LintLevel)>,
+    ) -> Self {
+        self.lints = Some(lints.into_iter().map(|(s, l)| (s.into(), l)).collect());
+        self
+    }
+
+    /// Returns levels of lints from the `[lints]` table. Levels of lint groups
+    /// come first, so that levels of individual lints override them.
+    #[must_use]
+    pub fn lint_levels(&self) -> LintLevels {
+        let lints = self.lints.iter().flatten();
+        let (individual, groups): (Vec<_>, Vec<_>) =
+            lints.partition(|(name, _)| find_lint(name).is_some());
+
+        groups
+            .into_iter()
+            .chain(individual)
+            .fold(LintLevels::new(), |levels, (name, level)| {
+                levels.with_level(name.clone(), *level)
+            })
+    }
+
+    /// Returns the path of the root module of the library target relative to the
+    /// package root, `src/package.sr` by default.
+    #[inline]
+    #[must_use]
+    pub fn library_root_module(&self) -> &str {
+        self.lib
+            .as_ref()
+            .and_then(|library| library.path.as_deref())
+            .unwrap_or(DEFAULT_LIBRARY_ROOT_MODULE)
+    }
+
+    /// Returns an iterator over binary targets of the package.
+    #[inline]
+    pub fn binaries(&self) -> impl Iterator<Item = &TomlBinary> {
+        self.bin.iter().flatten()
+    }
+}
+
+/// The path of the root module of the library target, if the `[lib]` section
+/// doesn't specify it.
+pub const DEFAULT_LIBRARY_ROOT_MODULE: &str = "src/package.sr";
+
+/// Represents data in the `[lib]` section of the manifest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TomlLibrary {
+    /// The path of the root module relative to the package root.
+    pub path: Option<String>,
+}
+
+impl TomlLibrary {
+    /// Returns a new toml library struct with the default root module.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { path: None }
+    }
+
+    /// Builds a new toml library struct with a given root module path.
+    #[inline]
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+/// Represents data in a `[[bin]]` section of the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TomlBinary {
+    /// The name of the binary.
+    pub name: String,
+    /// The path of the root module relative to the package root.
+    pub path: Option<String>,
+}
+
+impl TomlBinary {
+    /// Returns a new toml binary struct with a given name and the default root
+    /// module.
+    #[inline]
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            path: None,
+        }
+    }
+
+    /// Builds a new toml binary struct with a given root module path.
+    #[inline]
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Returns the path of the root module relative to the package root,
+    /// `src/bin/<name>.sr` by default.
+    #[inline]
+    #[must_use]
+    pub fn root_module(&self) -> String {
+        self.path
+            .clone()
+            .unwrap_or_else(|| format!("src/bin/{}.sr", self.name))
+    }
+}
+
+/// Represents data in the `[profile]` section of the manifest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TomlProfiles {
+    /// The `[profile.dev]` section, used by default.
+    pub dev: Option<TomlProfile>,
+    /// The `[profile.release]` section, used with `--release`.
+    pub release: Option<TomlProfile>,
+}
+
+/// Represents data in the `[profile.dev]` or `[profile.release]` section of the
+/// manifest. Settings, that are not specified, have default values of the profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TomlProfile {
+    /// The optimization level, from `0` (no optimizations) to `3`.
+    #[serde(default, deserialize_with = "deserialize_optimization_level")]
+    pub opt_level: Option<u8>,
+    /// Whether debug information is emitted.
+    pub debug: Option<bool>,
+    /// Whether integer overflows are checked at runtime.
+    pub overflow_checks: Option<bool>,
+    /// Whether assertions are removed.
+    pub strip_assertions: Option<bool>,
+    /// Whether warnings are treated as errors.
+    pub warnings_as_errors: Option<bool>,
+}
+
+impl TomlProfile {
+    /// Returns a new toml profile struct, that doesn't override any settings.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            opt_level: None,
+            debug: None,
+            overflow_checks: None,
+            strip_assertions: None,
+            warnings_as_errors: None,
+        }
+    }
+
+    /// Builds a new toml profile struct with a given optimization level.
+    #[inline]
+    #[must_use]
+    pub const fn with_opt_level(mut self, opt_level: u8) -> Self {
+        self.opt_level = Some(opt_level);
+        self
+    }
+
+    /// Builds a new toml profile struct, that enables or disables debug information.
+    #[inline]
+    #[must_use]
+    pub const fn with_debug(mut self, debug: bool) -> Self {
+        self.debug = Some(debug);
+        self
+    }
+
+    /// Builds a new toml profile struct, that enables or disables overflow checks.
+    #[inline]
+    #[must_use]
+    pub const fn with_overflow_checks(mut self, overflow_checks: bool) -> Self {
+        self.overflow_checks = Some(overflow_checks);
+        self
+    }
+
+    /// Builds a new toml profile struct, that removes or keeps assertions.
+    #[inline]
+    #[must_use]
+    pub const fn with_strip_assertions(mut self, strip_assertions: bool) -> Self {
+        self.strip_assertions = Some(strip_assertions);
+        self
+    }
+
+    /// Builds a new toml profile struct, that treats or doesn't treat warnings as
+    /// errors.
+    #[inline]
+    #[must_use]
+    pub const fn with_warnings_as_errors(mut self, warnings_as_errors: bool) -> Self {
+        self.warnings_as_errors = Some(warnings_as_errors);
+        self
+    }
 }
 
 /// Represents data in the `[package]` section of the manifest.
@@ -138,7 +573,10 @@ pub struct TomlPackage {
     /// The name of the package.
     pub name: String,
     /// The latest version of the package.
-    pub version: String,
+    #[serde(deserialize_with = "deserialize_version")]
+    pub version: Version,
+    /// The edition of the language, that the package is written in.
+    pub edition: Option<Edition>,
     /// The authors of the package.
     pub description: Option<String>,
     /// The license of the package.
@@ -158,10 +596,11 @@ impl TomlPackage {
     /// Other fieds can be constructed using `with_*` methods.
     #[inline]
     #[must_use]
-    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+    pub fn new(name: impl Into<String>, version: Version) -> Self {
         Self {
             name: name.into(),
-            version: version.into(),
+            version,
+            edition: None,
             description: None,
             license: None,
             author: None,
@@ -171,6 +610,23 @@ impl TomlPackage {
         }
     }
 
+    /// Builds a new toml package struct with a given edition.
+    #[inline]
+    #[must_use]
+    pub const fn with_edition(mut self, edition: Edition) -> Self {
+        self.edition = Some(edition);
+        self
+    }
+
+    /// Returns the edition of the package. Packages without the `edition` field
+    /// use the first edition, so that they keep compiling when new keywords are
+    /// reserved.
+    #[inline]
+    #[must_use]
+    pub fn edition(&self) -> Edition {
+        self.edition.unwrap_or_default()
+    }
+
     /// Builds a new toml package struct with a given description.
     #[inline]
     #[must_use]
@@ -223,17 +679,88 @@ impl TomlPackage {
     }
 }
 
+/// Represents data in the `[workspace]` section of the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub struct TomlWorkspace {
+    /// Glob patterns of member package directories relative to the workspace root,
+    /// e.g. `packages/*`.
+    #[serde(default)]
+    pub members: Vec<String>,
+
+    /// Dependencies, that members can inherit with `workspace = true`.
+    pub dependencies: Option<BTreeMap<String, TomlDependency>>,
+}
+
+impl TomlWorkspace {
+    /// Returns a new empty toml workspace struct.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a new toml workspace struct with given member globs.
+    #[inline]
+    #[must_use]
+    pub fn with_members(mut self, members: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.members = members.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Builds a new toml workspace struct with given dependencies.
+    #[inline]
+    #[must_use]
+    pub fn with_dependencies(
+        mut self,
+        dependencies: impl IntoIterator<Item = (impl Into<String>, TomlDependency)>,
+    ) -> Self {
+        self.dependencies = Some(
+            dependencies
+                .into_iter()
+                .map(|(s, d)| (s.into(), d))
+                .collect(),
+        );
+        self
+    }
+}
+
 /// Represents dependency (value part of the key-value pair in the `[dependencies]` section of the manifest).
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
 pub struct TomlDependency {
-    /// The version of the dependency.
-    pub version: Option<String>,
+    /// The version requirement of the dependency, e.g. `^1.2`.
+    #[serde(default, deserialize_with = "deserialize_version_requirement")]
+    pub version: Option<VersionReq>,
 
     /// The path to the local dependency's folder.
     pub path: Option<String>,
 
+    /// The URL or the path of the git repository, that contains the dependency.
+    pub git: Option<String>,
+
+    /// The branch of the git repository to check out.
+    pub branch: Option<String>,
+
+    /// The tag of the git repository to check out.
+    pub tag: Option<String>,
+
+    /// The commit of the git repository to check out.
+    pub rev: Option<String>,
+
     /// The author of the dependency.
     pub author: Option<String>,
+
+    /// Whether the dependency is inherited from `[workspace.dependencies]`.
+    pub workspace: Option<bool>,
+
+    /// Whether the dependency is only used, when a feature enables it with `dep:<name>`.
+    pub optional: Option<bool>,
+
+    /// Features of the dependency to enable.
+    pub features: Option<Vec<String>>,
+
+    /// Whether the `default` feature of the dependency is enabled (`true` by default).
+    #[serde(rename = "default-features")]
+    pub default_features: Option<bool>,
 }
 
 impl TomlDependency {
@@ -244,15 +771,72 @@ impl TomlDependency {
         Self::default()
     }
 
-    /// Builds a new toml dependency struct with a given version.
+    /// Builds a new toml dependency struct with a given version requirement.
+    #[inline]
+    #[must_use]
+    pub fn with_version(mut self, version: VersionReq) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Builds a new toml dependency struct, that is inherited from the workspace.
+    #[inline]
+    #[must_use]
+    pub const fn inherited_from_workspace(mut self) -> Self {
+        self.workspace = Some(true);
+        self
+    }
+
+    /// Returns `true` if the dependency is inherited from `[workspace.dependencies]`.
+    #[inline]
+    #[must_use]
+    pub fn is_inherited_from_workspace(&self) -> bool {
+        self.workspace == Some(true)
+    }
+
+    /// Builds a new toml dependency struct, that is optional.
     #[inline]
     #[must_use]
-    pub fn with_version(mut self, version: impl Into<String>) -> Self {
-        self.version = Some(version.into());
+    pub const fn optional(mut self) -> Self {
+        self.optional = Some(true);
         self
     }
 
-    /// Builds a new toml dependency struct with a given path.s
+    /// Returns `true` if the dependency is only used when a feature enables it.
+    #[inline]
+    #[must_use]
+    pub fn is_optional(&self) -> bool {
+        self.optional == Some(true)
+    }
+
+    /// Builds a new toml dependency struct with given features to enable.
+    #[inline]
+    #[must_use]
+    pub fn with_features(mut self, features: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.features = Some(features.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Builds a new toml dependency struct with the `default` feature disabled.
+    #[inline]
+    #[must_use]
+    pub const fn without_default_features(mut self) -> Self {
+        self.default_features = Some(false);
+        self
+    }
+
+    /// Returns features of the dependency, that the dependent package requests.
+    #[inline]
+    #[must_use]
+    pub fn feature_selection(&self) -> FeatureSelection {
+        FeatureSelection {
+            features: self.features.iter().flatten().cloned().collect(),
+            all_features: false,
+            default_features: self.default_features != Some(false),
+        }
+    }
+
+    /// Builds a new toml dependency struct with a given path.
     #[inline]
     #[must_use]
     pub fn with_path(mut self, path: impl Into<String>) -> Self {
@@ -267,57 +851,672 @@ impl TomlDependency {
         self.author = Some(author.into());
         self
     }
+
+    /// Builds a new toml dependency struct with a given git repository.
+    #[inline]
+    #[must_use]
+    pub fn with_git(mut self, git: impl Into<String>) -> Self {
+        self.git = Some(git.into());
+        self
+    }
+
+    /// Builds a new toml dependency struct with a given git reference to check out.
+    #[inline]
+    #[must_use]
+    pub fn with_git_reference(mut self, reference: GitReference) -> Self {
+        match reference {
+            GitReference::DefaultBranch => {}
+            GitReference::Branch(branch) => self.branch = Some(branch),
+            GitReference::Tag(tag) => self.tag = Some(tag),
+            GitReference::Rev(rev) => self.rev = Some(rev),
+        }
+        self
+    }
+
+    /// Returns the git reference to check out, if the dependency comes from a git
+    /// repository.
+    #[inline]
+    #[must_use]
+    pub fn git_reference(&self) -> GitReference {
+        if let Some(rev) = &self.rev {
+            GitReference::Rev(rev.clone())
+        } else if let Some(tag) = &self.tag {
+            GitReference::Tag(tag.clone())
+        } else if let Some(branch) = &self.branch {
+            GitReference::Branch(branch.clone())
+        } else {
+            GitReference::DefaultBranch
+        }
+    }
+}
+
+/// A reference in a git repository, that a git dependency is checked out at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum GitReference {
+    /// The branch `HEAD` of the repository points to.
+    #[default]
+    DefaultBranch,
+
+    /// A branch, e.g. `branch = "main"`.
+    Branch(String),
+
+    /// A tag, e.g. `tag = "v1.0.0"`.
+    Tag(String),
+
+    /// A commit hash or any other revision, e.g. `rev = "8f43434"`.
+    Rev(String),
+}
+
+impl GitReference {
+    /// Parses a reference in the form used by the lockfile: `branch=<name>`,
+    /// `tag=<name>` or `rev=<revision>`. An empty string is the default branch.
+    ///
+    /// # Errors
+    /// Returns an error if the reference kind is not known.
+    pub fn parse(reference: &str) -> Result<Self, String> {
+        if reference.is_empty() {
+            return Ok(Self::DefaultBranch);
+        }
+
+        match reference.split_once('=') {
+            Some(("branch", branch)) => Ok(Self::Branch(branch.to_owned())),
+            Some(("tag", tag)) => Ok(Self::Tag(tag.to_owned())),
+            Some(("rev", rev)) => Ok(Self::Rev(rev.to_owned())),
+            _ => Err(format!(
+                "invalid git reference `{reference}`, expected `branch=<name>`, `tag=<name>` or `rev=<revision>`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GitReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DefaultBranch => Ok(()),
+            Self::Branch(branch) => write!(f, "branch={branch}"),
+            Self::Tag(tag) => write!(f, "tag={tag}"),
+            Self::Rev(rev) => write!(f, "rev={rev}"),
+        }
+    }
+}
+
+/// The result of parsing a manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedManifest {
+    /// The manifest, or `None` if it's not valid.
+    pub manifest: Option<TomlManifest>,
+
+    /// Errors and warnings found in the manifest.
+    pub diagnostics: Diagnostics,
+}
+
+impl ParsedManifest {
+    /// Returns the manifest if there are no errors.
+    ///
+    /// # Errors
+    /// Returns diagnostics if the manifest is not valid.
+    #[inline]
+    pub fn into_result(self) -> Result<TomlManifest, Box<Diagnostics>> {
+        match self.manifest {
+            Some(manifest) if self.diagnostics.is_ok() => Ok(manifest),
+            _ => Err(Box::new(self.diagnostics)),
+        }
+    }
 }
 
-/// # Errors
+/// Parses the manifest located at a given path.
 ///
-/// Error occurs when manifest format is not valid, or the structure itself is also not valid.
+/// Errors and warnings point to the exact locations in the manifest source,
+/// so they can be emitted with [`DiagnosticsEmitter`] like source code diagnostics.
+/// The manifest is not valid if:
+///
+/// * it's not a valid TOML document;
+/// * it doesn't have the expected structure, e.g. the package version is not a valid
+///   semantic version;
+/// * a dependency has neither `version`, `path` nor `git`;
+/// * a dependency has both `path` and `git`, more than one of `branch`, `tag`
+///   and `rev`, or one of them without `git`;
+/// * `git`, `branch`, `tag` or `rev` of a dependency starts with `-`;
+/// * a feature enables a feature or an optional dependency, that doesn't exist;
+/// * the license is not a valid SPDX license expression, see [`license`];
+/// * a root module of a build target is not a source file, two binaries have the
+///   same name or two targets have the same root module.
+///
+/// Keys and lints, that are not recognized, are reported as warnings.
 /// See [crate level documentation] for more information.
 ///
 /// [crate level documentation]: crate
-pub fn parse_manifest(source: impl AsRef<str>) -> Result<TomlManifest, String> {
-    let toml = parse_document(source)?;
-
-    let manifest: TomlManifest =
-        match serde_ignored::deserialize(toml.into_deserializer(), |path| {
-            let mut key = String::new();
-            stringify(&mut key, &path);
-        }) {
-            Ok(manifest) => manifest,
-            Err(err) => return Err(format!("{err}")),
+/// [`DiagnosticsEmitter`]: stellar_diagnostics::DiagnosticsEmitter
+pub fn parse_manifest(filepath: PathId, source: impl AsRef<str>) -> ParsedManifest {
+    let source = source.as_ref();
+    let mut diagnostics = Diagnostics::new();
+
+    // Unlike `DocumentMut`, `ImDocument` keeps spans of keys and values,
+    // so that diagnostics can point to the exact location in the manifest.
+    let document = match ImDocument::parse(source) {
+        Ok(document) => document,
+        Err(err) => {
+            diagnostics.add_diagnostic(InvalidManifest::new(
+                err.message(),
+                location(filepath, err.span()),
+            ));
+
+            return ParsedManifest {
+                manifest: None,
+                diagnostics,
+            };
+        }
+    };
+
+    let mut unknown_keys = Vec::new();
+    let manifest: Result<TomlManifest, _> = serde_ignored::deserialize(
+        toml_edit::de::Deserializer::from(document.clone()),
+        |path| {
+            let mut key = Vec::new();
+            key_segments(&mut key, &path);
+            unknown_keys.push(key);
+        },
+    );
+
+    for key in unknown_keys {
+        let Some((name, parent)) = key.split_last() else {
+            continue;
+        };
+
+        diagnostics.add_diagnostic(UnknownManifestKey::new(
+            key.join("."),
+            location(filepath, key_span(&document, &key)),
+            find_similar(name, known_keys(parent).iter().copied()).map(ToOwned::to_owned),
+        ));
+    }
+
+    let manifest = match manifest {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            diagnostics.add_diagnostic(InvalidManifest::new(
+                err.message(),
+                location(filepath, err.span()),
+            ));
+
+            return ParsedManifest {
+                manifest: None,
+                diagnostics,
+            };
+        }
+    };
+
+    if manifest.package.is_none() && manifest.workspace.is_none() {
+        diagnostics.add_diagnostic(InvalidManifest::new(
+            "manifest must contain either the `[package]` or the `[workspace]` table",
+            location(filepath, None),
+        ));
+    }
+
+    if let Some(license) = manifest
+        .package
+        .as_ref()
+        .and_then(|package| package.license.as_ref())
+    {
+        if let Err(err) = LicenseExpression::parse(license) {
+            diagnostics.add_diagnostic(InvalidLicense::new(
+                license.clone(),
+                err.reason,
+                location(filepath, license_span(&document, license, err.span)),
+                err.suggestion,
+            ));
+        }
+    }
+
+    let dependencies = manifest
+        .dependencies
+        .iter()
+        .flatten()
+        .map(|(name, dependency)| (vec!["dependencies", name.as_str()], dependency, true));
+    let dev_dependencies = manifest
+        .dev_dependencies
+        .iter()
+        .flatten()
+        .map(|(name, dependency)| (vec!["dev-dependencies", name.as_str()], dependency, true));
+    let workspace_dependencies = manifest
+        .workspace
+        .iter()
+        .flat_map(|workspace| workspace.dependencies.iter().flatten())
+        .map(|(name, dependency)| {
+            (
+                vec!["workspace", "dependencies", name.as_str()],
+                dependency,
+                false,
+            )
+        });
+
+    for (key, dependency, can_be_inherited) in dependencies
+        .chain(dev_dependencies)
+        .chain(workspace_dependencies)
+    {
+        if dependency.version.is_none()
+            && dependency.path.is_none()
+            && dependency.git.is_none()
+            && !(can_be_inherited && dependency.is_inherited_from_workspace())
+        {
+            diagnostics.add_diagnostic(DependencyWithoutSource::new(
+                key[key.len() - 1].to_owned(),
+                location(filepath, key_span(&document, &key)),
+            ));
+        }
+
+        if let Some(reason) = check_dependency_source(dependency) {
+            diagnostics.add_diagnostic(InvalidDependencySource::new(
+                key[key.len() - 1].to_owned(),
+                reason,
+                location(filepath, key_span(&document, &key)),
+            ));
+        }
+    }
+
+    for (feature, members) in manifest.features.iter().flatten() {
+        for (idx, member) in members.iter().enumerate() {
+            if let Some(error) = check_feature_member(&manifest, member) {
+                diagnostics.add_diagnostic(UnknownFeatureMember::new(
+                    feature.clone(),
+                    member.clone(),
+                    error,
+                    location(
+                        filepath,
+                        document
+                            .get("features")
+                            .and_then(|features| features.get(feature))
+                            .and_then(|members| members.as_array())
+                            .and_then(|members| members.get(idx))
+                            .and_then(toml_edit::Value::span),
+                    ),
+                ));
+            }
+        }
+    }
+
+    check_targets(&manifest, &document, filepath, &mut diagnostics);
+
+    for name in manifest.lints.iter().flat_map(BTreeMap::keys) {
+        if !is_lint_or_group(name) {
+            diagnostics.add_diagnostic(UnknownLint::new(
+                name.clone(),
+                location(filepath, key_span(&document, &["lints", name.as_str()])),
+                find_similar(name, lint_and_group_names()).map(ToOwned::to_owned),
+            ));
+        }
+    }
+
+    ParsedManifest {
+        manifest: diagnostics.is_ok().then_some(manifest),
+        diagnostics,
+    }
+}
+
+/// Returns the location of the key of a dependency with a given name in a given
+/// table of the manifest source, e.g. of `json` in `json = { path = "../json" }`.
+///
+/// Returns an empty location at the start of the file, if the dependency is not
+/// found.
+#[must_use]
+pub fn dependency_location(
+    filepath: PathId,
+    source: &str,
+    table: DependencyTable,
+    name: &str,
+) -> Location {
+    location(
+        filepath,
+        ImDocument::parse(source)
+            .ok()
+            .and_then(|document| key_span(&document, &[table.as_str(), name])),
+    )
+}
+
+/// Returns the location of the `[[bin]]` entry of a binary with a given name in
+/// the manifest source, i.e. of the value of its `name` key.
+///
+/// Returns an empty location at the start of the file, if the binary is not
+/// found.
+#[must_use]
+pub fn binary_location(filepath: PathId, source: &str, name: &str) -> Location {
+    location(
+        filepath,
+        ImDocument::parse(source).ok().and_then(|document| {
+            document
+                .get("bin")?
+                .as_array_of_tables()?
+                .iter()
+                .filter_map(|binary| binary.get("name"))
+                .find(|value| value.as_str() == Some(name))?
+                .span()
+        }),
+    )
+}
+
+/// Returns a description of the problem, if a given dependency mixes sources,
+/// specifies a git reference without a git repository, or has a git repository
+/// or a reference, that would be passed to `git` as an option.
+fn check_dependency_source(dependency: &TomlDependency) -> Option<&'static str> {
+    let references = [&dependency.branch, &dependency.tag, &dependency.rev]
+        .into_iter()
+        .filter(|reference| reference.is_some())
+        .count();
+
+    if [
+        &dependency.git,
+        &dependency.branch,
+        &dependency.tag,
+        &dependency.rev,
+    ]
+    .into_iter()
+    .flatten()
+    .any(|value| value.starts_with('-'))
+    {
+        Some("`git`, `branch`, `tag` and `rev` cannot start with `-`")
+    } else if dependency.path.is_some() && dependency.git.is_some() {
+        Some("`path` and `git` cannot be used together")
+    } else if references > 1 {
+        Some("only one of `branch`, `tag` and `rev` can be used")
+    } else if references == 1 && dependency.git.is_none() {
+        Some("`branch`, `tag` and `rev` can only be used with `git`")
+    } else {
+        None
+    }
+}
+
+/// Reports build targets with root modules, that are not source files, binaries
+/// with the same name and targets sharing a root module.
+fn check_targets<S: AsRef<str>>(
+    manifest: &TomlManifest,
+    document: &ImDocument<S>,
+    filepath: PathId,
+    diagnostics: &mut Diagnostics,
+) {
+    let library_root_module = manifest.library_root_module();
+
+    if !is_source_file(library_root_module) {
+        diagnostics.add_diagnostic(InvalidTarget::new(
+            "library".to_owned(),
+            format!("root module `{library_root_module}` is not a source file"),
+            location(
+                filepath,
+                document
+                    .get("lib")
+                    .and_then(|library| library.get("path"))
+                    .and_then(toml_edit::Item::span),
+            ),
+        ));
+    }
+
+    let mut root_modules = vec![library_root_module.to_owned()];
+    let mut names = Vec::new();
+
+    for (idx, binary) in manifest.binaries().enumerate() {
+        let root_module = binary.root_module();
+        let reason = if names.contains(&binary.name.as_str()) {
+            Some("another binary has the same name".to_owned())
+        } else if !is_source_file(&root_module) {
+            Some(format!("root module `{root_module}` is not a source file"))
+        } else if root_modules.contains(&root_module) {
+            Some(format!(
+                "root module `{root_module}` belongs to another target"
+            ))
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            diagnostics.add_diagnostic(InvalidTarget::new(
+                format!("binary `{}`", binary.name),
+                reason,
+                location(
+                    filepath,
+                    document
+                        .get("bin")
+                        .and_then(toml_edit::Item::as_array_of_tables)
+                        .and_then(|binaries| binaries.get(idx))
+                        .and_then(|binary| binary.get("name"))
+                        .and_then(toml_edit::Item::span),
+                ),
+            ));
+        }
+
+        names.push(&binary.name);
+        root_modules.push(root_module);
+    }
+}
+
+/// Returns `true` if a given path has the `.sr` extension, in any case.
+fn is_source_file(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("sr"))
+}
+
+/// Returns a description of the problem, if a given member of a feature, i.e. an other
+/// feature or `dep:<name>`, doesn't refer to a feature or an optional dependency.
+fn check_feature_member(manifest: &TomlManifest, member: &str) -> Option<String> {
+    if let Some(dependency_name) = member.strip_prefix("dep:") {
+        return match manifest
+            .dependencies
+            .as_ref()
+            .and_then(|dependencies| dependencies.get(dependency_name))
+        {
+            None => Some(format!("there is no dependency named `{dependency_name}`")),
+            Some(dependency) if !dependency.is_optional() => Some(format!(
+                "dependency `{dependency_name}` is not optional, so it's always enabled"
+            )),
+            Some(_) => None,
         };
+    }
+
+    let features = manifest.features.iter().flat_map(BTreeMap::keys);
 
-    Ok(manifest)
+    if features.clone().any(|feature| feature == member) {
+        None
+    } else {
+        Some(
+            find_similar(member, features.map(String::as_str)).map_or_else(
+                || format!("there is no feature named `{member}`"),
+                |similar| {
+                    format!("there is no feature named `{member}`, did you mean `{similar}`?")
+                },
+            ),
+        )
+    }
 }
 
-fn parse_document(source: impl AsRef<str>) -> Result<Document, String> {
-    match source.as_ref().parse::<Document>() {
-        Ok(table) => Ok(table),
-        Err(err) => Err(format!("{err}")),
+/// Returns keys, that are expected in a table with a given path.
+fn known_keys(table: &[String]) -> &'static [&'static str] {
+    match table {
+        [] => &[
+            "package",
+            "workspace",
+            "dependencies",
+            "dev-dependencies",
+            "features",
+            "lib",
+            "bin",
+            "profile",
+            "lints",
+        ],
+        [package] if package == "package" => &[
+            "name",
+            "version",
+            "edition",
+            "description",
+            "license",
+            "author",
+            "repository",
+            "keywords",
+            "categories",
+        ],
+        [workspace] if workspace == "workspace" => &["members", "dependencies"],
+        [lib] if lib == "lib" => &["path"],
+        [profile] if profile == "profile" => &["dev", "release"],
+        [profile, _] if profile == "profile" => &[
+            "opt-level",
+            "debug",
+            "overflow-checks",
+            "strip-assertions",
+            "warnings-as-errors",
+        ],
+        [bin, _] if bin == "bin" => &["name", "path"],
+        [dependencies, _]
+            if dependencies == "dependencies" || dependencies == "dev-dependencies" =>
+        {
+            &[
+                "version",
+                "path",
+                "git",
+                "branch",
+                "tag",
+                "rev",
+                "author",
+                "workspace",
+                "optional",
+                "features",
+                "default-features",
+            ]
+        }
+        [workspace, dependencies, _]
+            if workspace == "workspace" && dependencies == "dependencies" =>
+        {
+            &[
+                "version",
+                "path",
+                "git",
+                "branch",
+                "tag",
+                "rev",
+                "author",
+                "features",
+                "default-features",
+            ]
+        }
+        _ => &[],
     }
 }
 
-fn stringify(dst: &mut String, path: &serde_ignored::Path<'_>) {
+/// Returns the span of the problematic part of the license expression, if the
+/// `license` value is written without escapes, or the span of the whole value.
+fn license_span<S: AsRef<str>>(
+    document: &ImDocument<S>,
+    license: &str,
+    error_span: Range<usize>,
+) -> Option<Range<usize>> {
+    let span = document
+        .get("package")
+        .and_then(|package| package.get("license"))
+        .and_then(toml_edit::Item::span)?;
+    let raw = &document.raw()[span.clone()];
+
+    if raw.len() == license.len() + 2 && &raw[1..raw.len() - 1] == license {
+        Some(span.start + 1 + error_span.start..span.start + 1 + error_span.end)
+    } else {
+        Some(span)
+    }
+}
+
+/// Returns the span of the last key in a given path.
+///
+/// Elements of arrays, e.g. `[[bin]]` tables, are referred to by their indices.
+fn key_span<S: AsRef<str>>(
+    document: &ImDocument<S>,
+    key: &[impl AsRef<str>],
+) -> Option<Range<usize>> {
+    let (last, parents) = key.split_last()?;
+    let mut table: &dyn TableLike = document.as_table();
+    let mut parents = parents.iter();
+
+    while let Some(parent) = parents.next() {
+        let item = table.get(parent.as_ref())?;
+
+        table = match item {
+            toml_edit::Item::ArrayOfTables(array) => array.get(array_index(parents.next()?)?)?,
+            toml_edit::Item::Value(toml_edit::Value::Array(array)) => array
+                .get(array_index(parents.next()?)?)?
+                .as_inline_table()?,
+            _ => item.as_table_like()?,
+        };
+    }
+
+    table.key(last.as_ref())?.span()
+}
+
+fn array_index(segment: &impl AsRef<str>) -> Option<usize> {
+    segment.as_ref().parse().ok()
+}
+
+fn location(filepath: PathId, span: Option<Range<usize>>) -> Location {
+    let span = span.unwrap_or_default();
+
+    Location {
+        filepath,
+        start: ByteOffset(span.start),
+        end: ByteOffset(span.end),
+    }
+}
+
+fn deserialize_optimization_level<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let level = u8::deserialize(deserializer)?;
+
+    if level > 3 {
+        return Err(serde::de::Error::custom(format!(
+            "invalid optimization level `{level}`, expected a number from 0 to 3"
+        )));
+    }
+
+    Ok(Some(level))
+}
+
+fn deserialize_version<'de, D>(deserializer: D) -> Result<Version, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version = String::deserialize(deserializer)?;
+
+    version.parse().map_err(|err| {
+        serde::de::Error::custom(format!(
+            "invalid version `{version}`: {err}, expected a semantic version like `1.0.0`"
+        ))
+    })
+}
+
+fn deserialize_version_requirement<'de, D>(deserializer: D) -> Result<Option<VersionReq>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(requirement) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    requirement.parse().map(Some).map_err(|err| {
+        serde::de::Error::custom(format!(
+            "invalid version requirement `{requirement}`: {err}, \
+expected a requirement like `^1.2`, `~0.3`, `>=1, <2` or `=1.0.0`"
+        ))
+    })
+}
+
+fn key_segments(segments: &mut Vec<String>, path: &serde_ignored::Path<'_>) {
     use serde_ignored::Path;
 
     match *path {
         Path::Root => {}
         Path::Seq { parent, index } => {
-            stringify(dst, parent);
-            if !dst.is_empty() {
-                dst.push('.');
-            }
-            dst.push_str(&index.to_string());
+            key_segments(segments, parent);
+            segments.push(index.to_string());
         }
         Path::Map { parent, ref key } => {
-            stringify(dst, parent);
-            if !dst.is_empty() {
-                dst.push('.');
-            }
-            dst.push_str(key);
+            key_segments(segments, parent);
+            segments.push(key.clone());
         }
         Path::Some { parent }
         | Path::NewtypeVariant { parent }
-        | Path::NewtypeStruct { parent } => stringify(dst, parent),
+        | Path::NewtypeStruct { parent } => key_segments(segments, parent),
     }
 }
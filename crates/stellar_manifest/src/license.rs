@@ -0,0 +1,110 @@
+//! SPDX license expressions, e.g. `MIT OR Apache-2.0`, used in the `license` field
+//! of the manifest.
+//!
+//! Expressions consist of license identifiers from the [SPDX license list],
+//! combined with `AND`, `OR` and `WITH <exception>`. Licenses, that are not in the
+//! list, can be referred to as `LicenseRef-<name>`.
+//!
+//! [SPDX license list]: https://spdx.org/licenses
+
+use std::{fmt, ops::Range};
+
+/// A parsed SPDX license expression.
+#[derive(Debug)]
+pub struct LicenseExpression {
+    expression: spdx::Expression,
+}
+
+/// The reason, why a license expression is not valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidLicenseExpression {
+    /// Description of the problem, e.g. `unknown license id`.
+    pub reason: String,
+
+    /// The byte range of the problematic part of the expression.
+    pub span: Range<usize>,
+
+    /// A valid expression, that was probably meant, e.g. `MIT OR Apache-2.0`
+    /// for `mit/apache-2.0`.
+    pub suggestion: Option<String>,
+}
+
+impl LicenseExpression {
+    /// Parses a license expression.
+    ///
+    /// # Errors
+    /// Returns an error if the expression is not valid, or refers to licenses or
+    /// exceptions, that are not in the SPDX list.
+    pub fn parse(expression: &str) -> Result<Self, InvalidLicenseExpression> {
+        spdx::Expression::parse(expression)
+            .map(|expression| Self { expression })
+            .map_err(|err| InvalidLicenseExpression {
+                reason: err.reason.to_string(),
+                span: err.span,
+                suggestion: spdx::Expression::canonicalize(expression)
+                    .ok()
+                    .flatten()
+                    .filter(|canonical| spdx::Expression::parse(canonical).is_ok()),
+            })
+    }
+
+    /// Returns identifiers of licenses, that the expression refers to, in the
+    /// order, in which they appear.
+    #[must_use]
+    pub fn licenses(&self) -> Vec<String> {
+        let original = self.expression.as_ref();
+
+        self.expression
+            .requirements()
+            .filter_map(|requirement| {
+                original
+                    .get(requirement.span.start as usize..requirement.span.end as usize)?
+                    .split_whitespace()
+                    .next()
+                    .map(ToOwned::to_owned)
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the expression can be satisfied without using any of
+    /// given licenses, e.g. `MIT OR GPL-3.0-only` is allowed when only
+    /// `GPL-3.0-only` is denied. Denied licenses, that are not valid identifiers,
+    /// never match. GNU licenses are denied regardless of the `-only` and
+    /// `-or-later` suffixes.
+    #[must_use]
+    pub fn is_allowed(&self, denied: &[impl AsRef<str>]) -> bool {
+        let denied = denied
+            .iter()
+            .map(|license| without_gnu_suffix(license.as_ref()))
+            .collect::<Vec<_>>();
+
+        self.expression.evaluate(|requirement| {
+            requirement
+                .license
+                .id()
+                .is_none_or(|license| !denied.contains(&without_gnu_suffix(license.name)))
+        })
+    }
+}
+
+impl fmt::Display for LicenseExpression {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expression.fmt(f)
+    }
+}
+
+/// Strips the `-only` and `-or-later` suffixes of GNU licenses, which are not
+/// kept in parsed expressions.
+fn without_gnu_suffix(id: &str) -> &str {
+    id.strip_suffix("-only")
+        .or_else(|| id.strip_suffix("-or-later"))
+        .unwrap_or(id)
+}
+
+/// Returns `true` if a given string is an identifier of a license in the SPDX
+/// list, e.g. `MIT`.
+#[must_use]
+pub fn is_license_id(id: &str) -> bool {
+    spdx::license_id(id).is_some()
+}
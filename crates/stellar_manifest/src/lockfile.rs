@@ -0,0 +1,351 @@
+//! The lockfile (`package.lock`), which records every package used in a build, so
+//! that the build can be reproduced.
+//!
+//! ```toml
+//! # This file is generated by the Stellar compiler. Do not edit it manually.
+//! version = 1
+//!
+//! [[package]]
+//! name = "json"
+//! version = "0.1.0"
+//! dependencies = ["serialization_engine"]
+//!
+//! [[package]]
+//! name = "serialization_engine"
+//! version = "1.2.0"
+//! author = "quantumatic"
+//! source = "registry+../registry"
+//! checksum = "8f434346648f6b96df89dda901c5176b10a6d83961dd3c1ac88b59b2dc327aa4"
+//! ```
+//!
+//! Packages being built (the package itself or workspace members) have no source
+//! and no checksum, because they change all the time. Path dependencies have
+//! a source, but no checksum for the same reason. Paths are relative to the
+//! directory containing the lockfile.
+
+use std::{fmt, str::FromStr};
+
+use semver::Version;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::GitReference;
+
+/// The version of the lockfile format.
+pub const LOCKFILE_VERSION: u32 = 1;
+
+const HEADER: &str = "# This file is generated by the Stellar compiler. Do not edit it manually.\n";
+
+/// Describes the lockfile.
+///
+/// See [module level documentation] for more information.
+///
+/// [module level documentation]: crate::lockfile
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Lockfile {
+    /// The version of the lockfile format.
+    pub version: u32,
+
+    /// Locked packages sorted by name and version.
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    /// Returns a new lockfile with given packages.
+    #[must_use]
+    pub fn new(packages: impl IntoIterator<Item = LockedPackage>) -> Self {
+        let mut packages = packages.into_iter().collect::<Vec<_>>();
+        packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+        Self {
+            version: LOCKFILE_VERSION,
+            packages,
+        }
+    }
+
+    /// Parses the lockfile.
+    ///
+    /// # Errors
+    /// Returns an error if the source is not a valid lockfile.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let lockfile: Self = toml::from_str(source).map_err(|err| err.message().to_owned())?;
+
+        if lockfile.version != LOCKFILE_VERSION {
+            return Err(format!(
+                "unsupported lockfile version {}, expected {LOCKFILE_VERSION}",
+                lockfile.version
+            ));
+        }
+
+        Ok(lockfile)
+    }
+
+    /// Returns the locked package with a given name and source.
+    #[must_use]
+    pub fn find(&self, name: &str, source: Option<&PackageSource>) -> Option<&LockedPackage> {
+        self.packages
+            .iter()
+            .find(|package| package.name == name && package.source.as_ref() == source)
+    }
+
+    /// Returns human-readable descriptions of changes between the lockfile and
+    /// a newer one, e.g. `added json 0.1.0`. Returns an empty vector if the
+    /// lockfiles are the same.
+    #[must_use]
+    pub fn changes(&self, newer: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        for package in &self.packages {
+            match newer.find(&package.name, package.source.as_ref()) {
+                None => changes.push(format!("removed {package}")),
+                Some(new) if new != package => {
+                    changes.push(format!("changed {package}: {}", package.difference(new)));
+                }
+                Some(_) => {}
+            }
+        }
+
+        for package in &newer.packages {
+            if self.find(&package.name, package.source.as_ref()).is_none() {
+                changes.push(format!("added {package}"));
+            }
+        }
+
+        changes
+    }
+}
+
+impl fmt::Display for Lockfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(HEADER)?;
+        f.write_str(&toml::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// A package recorded in the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedPackage {
+    /// The name of the package.
+    pub name: String,
+
+    /// The version of the package.
+    pub version: Version,
+
+    /// The author of the package.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Where the package comes from. `None` for packages being built.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<PackageSource>,
+
+    /// SHA-256 checksum of the package contents. `None` for packages being built.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+
+    /// Names of packages, that the package depends on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+impl LockedPackage {
+    /// Returns a new locked package with a given name and version.
+    #[inline]
+    #[must_use]
+    pub fn new(name: impl Into<String>, version: Version) -> Self {
+        Self {
+            name: name.into(),
+            version,
+            author: None,
+            source: None,
+            checksum: None,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Builds a new locked package with a given author.
+    #[inline]
+    #[must_use]
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Builds a new locked package with a given source.
+    #[inline]
+    #[must_use]
+    pub fn with_source(mut self, source: PackageSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Builds a new locked package with a given checksum.
+    #[inline]
+    #[must_use]
+    pub fn with_checksum(mut self, checksum: impl Into<String>) -> Self {
+        self.checksum = Some(checksum.into());
+        self
+    }
+
+    /// Builds a new locked package with given dependencies. They are sorted, so
+    /// that the lockfile is deterministic.
+    #[inline]
+    #[must_use]
+    pub fn with_dependencies(
+        mut self,
+        dependencies: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.dependencies = dependencies.into_iter().map(Into::into).collect();
+        self.dependencies.sort();
+        self.dependencies.dedup();
+        self
+    }
+
+    fn difference(&self, newer: &Self) -> String {
+        let mut differences = Vec::new();
+
+        if self.version != newer.version {
+            differences.push(format!("version {} -> {}", self.version, newer.version));
+        }
+
+        if self.author != newer.author {
+            differences.push("author".to_owned());
+        }
+
+        if self.checksum != newer.checksum {
+            differences.push("checksum".to_owned());
+        }
+
+        if self.dependencies != newer.dependencies {
+            differences.push("dependencies".to_owned());
+        }
+
+        differences.join(", ")
+    }
+}
+
+impl fmt::Display for LockedPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.version)?;
+
+        if let Some(source) = &self.source {
+            write!(f, " ({source})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Where a locked package comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PackageSource {
+    /// A local directory, e.g. `path+../json`.
+    Path(String),
+
+    /// A package registry, e.g. `registry+/srv/registry`.
+    Registry(String),
+
+    /// A commit in a git repository, e.g.
+    /// `git+https://github.com/quantumatic/json?tag=v0.1.0#8f434346648f6b96df89dda901c5176b10a6d839`.
+    Git(GitSource),
+}
+
+/// A git repository, a reference in it and the commit the reference pointed to,
+/// when the package was locked.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GitSource {
+    /// The URL of the repository, or the path relative to the lockfile directory.
+    pub url: String,
+
+    /// The reference specified in the manifest.
+    pub reference: GitReference,
+
+    /// The full hash of the locked commit.
+    pub commit: String,
+}
+
+impl GitSource {
+    /// Returns a new git source.
+    #[inline]
+    #[must_use]
+    pub fn new(url: impl Into<String>, reference: GitReference, commit: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            reference,
+            commit: commit.into(),
+        }
+    }
+}
+
+impl fmt::Display for GitSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.url)?;
+
+        if self.reference != GitReference::DefaultBranch {
+            write!(f, "?{}", self.reference)?;
+        }
+
+        write!(f, "#{}", self.commit)
+    }
+}
+
+impl FromStr for GitSource {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let Some((location, commit)) = source.rsplit_once('#') else {
+            return Err(format!(
+                "invalid git source `{source}`, expected `<url>#<commit>`"
+            ));
+        };
+
+        let (url, reference) = match location.rsplit_once('?') {
+            Some((url, reference)) => (url, GitReference::parse(reference)?),
+            None => (location, GitReference::DefaultBranch),
+        };
+
+        Ok(Self::new(url, reference, commit))
+    }
+}
+
+impl fmt::Display for PackageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "path+{path}"),
+            Self::Registry(registry) => write!(f, "registry+{registry}"),
+            Self::Git(git) => write!(f, "git+{git}"),
+        }
+    }
+}
+
+impl FromStr for PackageSource {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = source.strip_prefix("path+") {
+            Ok(Self::Path(path.to_owned()))
+        } else if let Some(registry) = source.strip_prefix("registry+") {
+            Ok(Self::Registry(registry.to_owned()))
+        } else if let Some(git) = source.strip_prefix("git+") {
+            git.parse().map(Self::Git)
+        } else {
+            Err(format!(
+                "invalid package source `{source}`, expected `path+<path>`, `registry+<location>` or `git+<url>#<commit>`"
+            ))
+        }
+    }
+}
+
+impl Serialize for PackageSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PackageSource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
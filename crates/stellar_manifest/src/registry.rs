@@ -0,0 +1,276 @@
+//! The package registry index.
+//!
+//! A registry is a directory, that contains an index file with published versions
+//! of every package and archives of the versions themselves:
+//!
+//! ```txt
+//! registry
+//! |_ index
+//! |  |_ http.toml
+//! |  |_ json.toml
+//! |_ packages
+//!    |_ http
+//!    |  |_ http-1.0.0.tar.gz
+//!    |  |_ http-1.1.0.tar.gz
+//!    |_ json
+//!       |_ json-0.1.0.tar.gz
+//! ```
+//!
+//! An index file lists versions of the package together with checksums of their
+//! archives and their dependencies, so that versions can be resolved without
+//! downloading archives:
+//!
+//! ```toml
+//! author = "quantumatic"
+//!
+//! [[version]]
+//! version = "1.0.0"
+//! checksum = "8f434346648f6b96df89dda901c5176b10a6d83961dd3c1ac88b59b2dc327aa4"
+//! optional = ["yaml"]
+//!
+//! [version.dependencies]
+//! json = "^0.1"
+//! yaml = "^0.3"
+//!
+//! [version.features]
+//! default = ["json"]
+//! yaml = ["dep:yaml"]
+//!
+//! [[version]]
+//! version = "1.1.0"
+//! checksum = "4e07408562bedb8b60ce05c1decfe3ad16b72230967de01f640b7e4729b49fce"
+//! yanked = true
+//! ```
+//!
+//! Optional dependencies are listed in `optional` and features are copied from
+//! the `[features]` section of the manifest. Optional dependencies are resolved
+//! like other dependencies, so that enabling a feature doesn't change the versions
+//! of packages, that are already locked.
+//!
+//! Since the registry is just a directory, it can be used without network, e.g.
+//! in tests or as an internal mirror.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use crate::TomlManifest;
+
+/// Returns the path of the index file of a package with a given name in the
+/// registry located in a given directory.
+#[inline]
+#[must_use]
+pub fn index_path(registry: &Path, name: &str) -> PathBuf {
+    registry.join("index").join(format!("{name}.toml"))
+}
+
+/// Returns the path of the archive of a given version of a package in the
+/// registry located in a given directory.
+#[inline]
+#[must_use]
+pub fn archive_path(registry: &Path, name: &str, version: &Version) -> PathBuf {
+    registry
+        .join("packages")
+        .join(name)
+        .join(format!("{name}-{version}.tar.gz"))
+}
+
+/// Describes the index file of a package.
+///
+/// See [module level documentation] for more information.
+///
+/// [module level documentation]: crate::registry
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct IndexFile {
+    /// The author of the package.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Published versions of the package sorted in ascending order.
+    #[serde(default, rename = "version")]
+    pub versions: Vec<IndexVersion>,
+}
+
+impl IndexFile {
+    /// Returns a new index file without versions.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a new index file with a given author.
+    #[inline]
+    #[must_use]
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Builds a new index file with a given version.
+    #[inline]
+    #[must_use]
+    pub fn with_version(mut self, version: IndexVersion) -> Self {
+        self.add_version(version);
+        self
+    }
+
+    /// Adds a version, keeping versions sorted.
+    pub fn add_version(&mut self, version: IndexVersion) {
+        let idx = self
+            .versions
+            .partition_point(|published| published.version < version.version);
+        self.versions.insert(idx, version);
+    }
+
+    /// Returns a published version.
+    #[must_use]
+    pub fn version(&self, version: &Version) -> Option<&IndexVersion> {
+        self.versions
+            .iter()
+            .find(|published| published.version == *version)
+    }
+
+    /// Parses the index file.
+    ///
+    /// # Errors
+    /// Returns an error if the source is not a valid index file.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut index: Self = toml::from_str(source).map_err(|err| err.message().to_owned())?;
+        index.versions.sort_by(|a, b| a.version.cmp(&b.version));
+
+        Ok(index)
+    }
+}
+
+impl fmt::Display for IndexFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&toml::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// A published version of a package.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IndexVersion {
+    /// The version.
+    pub version: Version,
+
+    /// SHA-256 checksum of the archive.
+    pub checksum: String,
+
+    /// Version requirements of dependencies by their names.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, VersionReq>,
+
+    /// Names of dependencies, that are only used when a feature enables them.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub optional: BTreeSet<String>,
+
+    /// Features of the version with features and optional dependencies, that
+    /// they enable.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub features: BTreeMap<String, Vec<String>>,
+
+    /// Whether the version must not be used, unless it's already locked.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub yanked: bool,
+}
+
+impl IndexVersion {
+    /// Returns a new published version with a given archive checksum.
+    #[inline]
+    #[must_use]
+    pub fn new(version: Version, checksum: impl Into<String>) -> Self {
+        Self {
+            version,
+            checksum: checksum.into(),
+            dependencies: BTreeMap::new(),
+            optional: BTreeSet::new(),
+            features: BTreeMap::new(),
+            yanked: false,
+        }
+    }
+
+    /// Builds a new published version with given dependencies.
+    #[inline]
+    #[must_use]
+    pub fn with_dependencies(
+        mut self,
+        dependencies: impl IntoIterator<Item = (impl Into<String>, VersionReq)>,
+    ) -> Self {
+        self.dependencies = dependencies
+            .into_iter()
+            .map(|(name, requirement)| (name.into(), requirement))
+            .collect();
+        self
+    }
+
+    /// Builds a new published version with given optional dependencies. They
+    /// must also be among the dependencies of the version.
+    #[inline]
+    #[must_use]
+    pub fn with_optional_dependencies(
+        mut self,
+        names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.optional = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Builds a new published version with given features.
+    #[inline]
+    #[must_use]
+    pub fn with_features(
+        mut self,
+        features: impl IntoIterator<Item = (impl Into<String>, Vec<String>)>,
+    ) -> Self {
+        self.features = features
+            .into_iter()
+            .map(|(name, members)| (name.into(), members))
+            .collect();
+        self
+    }
+
+    /// Builds a new published version with dependencies, optional dependencies
+    /// and features from a given manifest. Dependencies without a version
+    /// requirement are recorded as `*`.
+    #[inline]
+    #[must_use]
+    pub fn with_manifest(self, manifest: &TomlManifest) -> Self {
+        let dependencies = manifest.dependencies.iter().flatten();
+
+        self.with_dependencies(dependencies.clone().map(|(name, dependency)| {
+            (name, dependency.version.clone().unwrap_or(VersionReq::STAR))
+        }))
+        .with_optional_dependencies(
+            dependencies
+                .filter(|(_, dependency)| dependency.is_optional())
+                .map(|(name, _)| name),
+        )
+        .with_features(
+            manifest
+                .features
+                .iter()
+                .flatten()
+                .map(|(name, members)| (name, members.clone())),
+        )
+    }
+
+    /// Builds a new published version, that is yanked.
+    #[inline]
+    #[must_use]
+    pub const fn yanked(mut self) -> Self {
+        self.yanked = true;
+        self
+    }
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn is_false(value: &bool) -> bool {
+    !*value
+}
@@ -1,5 +1,43 @@
+use std::collections::BTreeSet;
+
+use stellar_diagnostics::lint::{find_lint, LintLevel};
+use stellar_diagnostics::{
+    diagnostic::{Diagnostic, Severity},
+    Diagnostics,
+};
+use stellar_interner::PathId;
+use stellar_lint::register_lints;
 use stellar_manifest::TomlPackage;
-use stellar_manifest::{parse_manifest, TomlDependency, TomlManifest};
+use stellar_manifest::{
+    parse_manifest, Edition, FeatureSelection, TomlBinary, TomlDependency, TomlLibrary,
+    TomlManifest, TomlProfile, TomlProfiles, TomlWorkspace, Version, VersionReq,
+};
+
+fn parse(source: &str) -> Result<TomlManifest, Box<Diagnostics>> {
+    parse_manifest(PathId::from("package.toml"), source).into_result()
+}
+
+/// Returns the only diagnostic reported for a given manifest and the source text it points to.
+fn single_diagnostic(source: &str) -> (Diagnostic, &str) {
+    let mut diagnostics = parse_manifest(PathId::from("package.toml"), source)
+        .diagnostics
+        .diagnostics;
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+
+    let diagnostic = diagnostics.remove(0);
+    let location = diagnostic.labels[0].location;
+
+    (diagnostic, &source[location])
+}
+
+fn version(version: &str) -> Version {
+    Version::parse(version).unwrap()
+}
+
+fn requirement(requirement: &str) -> VersionReq {
+    VersionReq::parse(requirement).unwrap()
+}
 
 #[test]
 fn simple_manifest() {
@@ -7,8 +45,11 @@ fn simple_manifest() {
 name = \"json\"
 version = \"1.0.0\"";
     assert_eq!(
-        parse_manifest(manifest),
-        Ok(TomlManifest::new(TomlPackage::new("json", "1.0.0")))
+        parse(manifest),
+        Ok(TomlManifest::new(TomlPackage::new(
+            "json",
+            version("1.0.0")
+        )))
     );
 }
 
@@ -17,6 +58,7 @@ fn full_package_metadata() {
     let manifest = "[package]
 name = \"json\"
 version = \"1.0.0\"
+edition = \"2024\"
 author = \"abs0luty\"
 repository = \"github.com/quantumatic/stellar\"
 license = \"MIT\"
@@ -26,9 +68,10 @@ categories = [\"json\", \"parser\", \"serialization\", \"deserialization\",
 keywords = [\"json\", \"config\"]";
 
     assert_eq!(
-        parse_manifest(manifest),
+        parse(manifest),
         Ok(TomlManifest::new(
-            TomlPackage::new("json", "1.0.0")
+            TomlPackage::new("json", version("1.0.0"))
+                .with_edition(Edition::Edition2024)
                 .with_author("abs0luty")
                 .with_repository("github.com/quantumatic/stellar")
                 .with_license("MIT")
@@ -45,6 +88,239 @@ keywords = [\"json\", \"config\"]";
     );
 }
 
+#[test]
+fn unknown_edition() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+edition = \"2025\"",
+    );
+
+    assert_eq!(
+        diagnostic.message,
+        "unknown variant `2025`, expected `2023` or `2024`"
+    );
+    assert_eq!(span, "\"2025\"");
+}
+
+#[test]
+fn invalid_license() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+license = \"MIT OR apache2\"",
+    );
+
+    assert_eq!(
+        diagnostic.message,
+        "invalid license expression `MIT OR apache2`"
+    );
+    assert_eq!(diagnostic.labels[0].message, "unknown term");
+    assert_eq!(span, "apache2");
+    assert_eq!(
+        diagnostic.notes[1],
+        "help: did you mean `MIT OR Apache-2.0`?"
+    );
+}
+
+#[test]
+fn targets() {
+    let manifest = parse(
+        "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[lib]
+path = \"src/server.sr\"
+
+[[bin]]
+name = \"server\"
+
+[[bin]]
+name = \"migrate\"
+path = \"src/migrate.sr\"",
+    )
+    .unwrap();
+
+    assert_eq!(
+        manifest,
+        TomlManifest::new(TomlPackage::new("server", version("1.0.0")))
+            .with_library(TomlLibrary::new().with_path("src/server.sr"))
+            .with_binaries([
+                TomlBinary::new("server"),
+                TomlBinary::new("migrate").with_path("src/migrate.sr")
+            ])
+    );
+    assert_eq!(manifest.library_root_module(), "src/server.sr");
+    assert_eq!(
+        manifest
+            .binaries()
+            .map(TomlBinary::root_module)
+            .collect::<Vec<_>>(),
+        ["src/bin/server.sr", "src/migrate.sr"]
+    );
+}
+
+#[test]
+fn invalid_target() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[[bin]]
+name = \"server\"
+
+[[bin]]
+name = \"server\"
+path = \"src/main.sr\"",
+    );
+
+    assert_eq!(diagnostic.message, "invalid binary `server` target");
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "another binary has the same name"
+    );
+    assert_eq!(span, "\"server\"");
+}
+
+#[test]
+fn target_extension() {
+    assert!(parse(
+        "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[lib]
+path = \"src/Server.SR\"",
+    )
+    .is_ok());
+
+    let (diagnostic, _) = single_diagnostic(
+        "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[lib]
+path = \"src/.sr\"",
+    );
+
+    assert_eq!(diagnostic.message, "invalid library target");
+}
+
+#[test]
+fn binary_location() {
+    let source = "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[[bin]]
+name = \"server\"
+
+[[bin]]
+name = \"migrate\"";
+    let location =
+        stellar_manifest::binary_location(PathId::from("package.toml"), source, "migrate");
+
+    assert_eq!(&source[location.start.0..location.end.0], "\"migrate\"");
+}
+
+#[test]
+fn profiles() {
+    assert_eq!(
+        parse(
+            "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[profile.dev]
+opt-level = 1
+
+[profile.release]
+debug = true
+warnings-as-errors = true"
+        ),
+        Ok(
+            TomlManifest::new(TomlPackage::new("server", version("1.0.0"))).with_profiles(
+                TomlProfiles {
+                    dev: Some(TomlProfile::new().with_opt_level(1)),
+                    release: Some(
+                        TomlProfile::new()
+                            .with_debug(true)
+                            .with_warnings_as_errors(true)
+                    ),
+                }
+            )
+        )
+    );
+
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[profile.release]
+opt-level = 4",
+    );
+
+    assert_eq!(
+        diagnostic.message,
+        "invalid optimization level `4`, expected a number from 0 to 3"
+    );
+    assert_eq!(span, "4");
+}
+
+#[test]
+fn lints() {
+    register_lints();
+
+    let manifest = parse(
+        "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[lints]
+unnecessary_grouped_pattern = \"allow\"
+style = \"deny\"",
+    )
+    .unwrap();
+    let levels = manifest.lint_levels();
+
+    assert_eq!(
+        levels.level(find_lint("unnecessary_grouped_pattern").unwrap()),
+        LintLevel::Allow
+    );
+    assert_eq!(
+        levels.level(find_lint("unnecessary_parenthesized_type").unwrap()),
+        LintLevel::Deny
+    );
+    assert_eq!(
+        levels.level(find_lint("unresolved_doc_link").unwrap()),
+        LintLevel::Warn
+    );
+    assert_eq!(
+        levels
+            .with_cap(LintLevel::Warn)
+            .level(find_lint("unnecessary_parenthesized_type").unwrap()),
+        LintLevel::Warn
+    );
+
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[lints]
+syle = \"deny\"",
+    );
+
+    assert_eq!(diagnostic.message, "unknown lint `syle`");
+    assert_eq!(diagnostic.notes[0], "help: did you mean `style`?");
+    assert_eq!(span, "syle");
+}
+
 #[test]
 fn dependencies() {
     let manifest = "[package]
@@ -58,20 +334,348 @@ bar = { version = \"1.0\", author = \"bar\" }
 foo2 = { path = \"../foo\" }";
 
     assert_eq!(
-        parse_manifest(manifest),
+        parse(manifest),
         Ok(
-            TomlManifest::new(TomlPackage::new("json", "1.0.0").with_author("abs0luty"))
+            TomlManifest::new(TomlPackage::new("json", version("1.0.0")).with_author("abs0luty"))
                 .with_dependencies([
                     (
                         "foo",
-                        TomlDependency::new().with_version("1.0").with_author("foo")
+                        TomlDependency::new()
+                            .with_version(requirement("1.0"))
+                            .with_author("foo")
                     ),
                     (
                         "bar",
-                        TomlDependency::new().with_version("1.0").with_author("bar")
+                        TomlDependency::new()
+                            .with_version(requirement("1.0"))
+                            .with_author("bar")
                     ),
                     ("foo2", TomlDependency::new().with_path("../foo")),
                 ])
         )
     );
 }
+
+#[test]
+fn version_requirements() {
+    let manifest = parse(
+        "[package]
+name = \"json\"
+version = \"1.2.3-beta.1\"
+
+[dependencies]
+a = { version = \"^1.2\" }
+b = { version = \"~0.3\" }
+c = { version = \">=1, <2\" }
+d = { version = \"=1.0.0\" }",
+    )
+    .unwrap();
+
+    assert_eq!(manifest.package.unwrap().version, version("1.2.3-beta.1"));
+
+    let dependencies = manifest.dependencies.unwrap();
+    let matches = |name: &str, version_: &str| {
+        dependencies[name]
+            .version
+            .as_ref()
+            .unwrap()
+            .matches(&version(version_))
+    };
+
+    assert!(matches("a", "1.9.0") && !matches("a", "2.0.0"));
+    assert!(matches("b", "0.3.7") && !matches("b", "0.4.0"));
+    assert!(matches("c", "1.5.0") && !matches("c", "2.0.0"));
+    assert!(matches("d", "1.0.0") && !matches("d", "1.0.1"));
+}
+
+#[test]
+fn invalid_package_version() {
+    let source = "[package]
+name = \"json\"
+version = \"banana\"";
+    let (diagnostic, span) = single_diagnostic(source);
+
+    assert!(diagnostic.message.contains("invalid version `banana`"));
+    assert_eq!(span, "\"banana\"");
+}
+
+#[test]
+fn invalid_dependency_version_requirement() {
+    let source = "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[dependencies]
+foo = { version = \">>1\" }";
+    let (diagnostic, span) = single_diagnostic(source);
+
+    assert!(diagnostic
+        .message
+        .contains("invalid version requirement `>>1`"));
+    assert_eq!(span, "\">>1\"");
+}
+
+#[test]
+fn syntax_error() {
+    let (diagnostic, _) = single_diagnostic("[package");
+
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.code.as_deref(), Some("E010"));
+}
+
+#[test]
+fn unknown_keys() {
+    let source = "[package]
+name = \"json\"
+version = \"1.0.0\"
+licence = \"MIT\"";
+    let parsed = parse_manifest(PathId::from("package.toml"), source);
+
+    assert!(parsed.manifest.is_some());
+
+    let diagnostic = &parsed.diagnostics.diagnostics[0];
+
+    assert_eq!(diagnostic.severity, Severity::Warning);
+    assert_eq!(diagnostic.code.as_deref(), Some("W004"));
+    assert_eq!(diagnostic.message, "unused manifest key `package.licence`");
+    assert_eq!(&source[diagnostic.labels[0].location], "licence");
+    assert_eq!(diagnostic.notes, vec!["help: did you mean `license`?"]);
+}
+
+#[test]
+fn unknown_binary_key() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[[bin]]
+name = \"json\"
+
+[[bin]]
+name = \"json-fmt\"
+pth = \"src/fmt.sr\"",
+    );
+
+    assert_eq!(diagnostic.message, "unused manifest key `bin.1.pth`");
+    assert_eq!(span, "pth");
+    assert_eq!(diagnostic.notes, vec!["help: did you mean `path`?"]);
+}
+
+#[test]
+fn unknown_dependency_key() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[dependencies]
+foo = { path = \"../foo\", verison = \"1.0\" }",
+    );
+
+    assert_eq!(
+        diagnostic.message,
+        "unused manifest key `dependencies.foo.verison`"
+    );
+    assert_eq!(span, "verison");
+    assert_eq!(diagnostic.notes, vec!["help: did you mean `version`?"]);
+}
+
+#[test]
+fn dependency_without_source() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[dependencies]
+foo = { author = \"foo\" }",
+    );
+
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(
+        diagnostic.message,
+        "dependency `foo` specifies neither `version`, `path` nor `git`"
+    );
+    assert_eq!(span, "foo");
+}
+
+#[test]
+fn invalid_dependency_source() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[dependencies]
+foo = { path = \"../foo\", tag = \"v1.0.0\" }",
+    );
+
+    assert_eq!(diagnostic.message, "invalid source of dependency `foo`");
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "`branch`, `tag` and `rev` can only be used with `git`"
+    );
+    assert_eq!(span, "foo");
+}
+
+#[test]
+fn git_dependency_source_starting_with_dash() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[dependencies]
+foo = { git = \"--upload-pack=touch pwned;://x\" }",
+    );
+
+    assert_eq!(diagnostic.message, "invalid source of dependency `foo`");
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "`git`, `branch`, `tag` and `rev` cannot start with `-`"
+    );
+    assert_eq!(span, "foo");
+}
+
+#[test]
+fn virtual_workspace() {
+    let manifest = "[workspace]
+members = [\"packages/*\"]
+
+[workspace.dependencies]
+http = { version = \"1.0\" }";
+
+    assert_eq!(
+        parse(manifest),
+        Ok(TomlManifest::new_virtual(
+            TomlWorkspace::new()
+                .with_members(["packages/*"])
+                .with_dependencies([(
+                    "http",
+                    TomlDependency::new().with_version(requirement("1.0"))
+                )])
+        ))
+    );
+}
+
+#[test]
+fn inherited_dependency() {
+    let manifest = "[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[dependencies]
+http = { workspace = true }";
+
+    assert_eq!(
+        parse(manifest),
+        Ok(
+            TomlManifest::new(TomlPackage::new("server", version("1.0.0")))
+                .with_dependencies([("http", TomlDependency::new().inherited_from_workspace())])
+        )
+    );
+}
+
+#[test]
+fn manifest_without_package_and_workspace() {
+    let (diagnostic, _) = single_diagnostic("[dependencies]");
+
+    assert_eq!(
+        diagnostic.message,
+        "manifest must contain either the `[package]` or the `[workspace]` table"
+    );
+}
+
+#[test]
+fn features_and_dev_dependencies() {
+    let manifest = "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[features]
+default = [\"std\"]
+std = []
+serde = [\"std\", \"dep:serialization_engine\"]
+
+[dependencies]
+serialization_engine = { path = \"../serialization_engine\", optional = true }
+
+[dev-dependencies]
+test_utils = { path = \"../test_utils\" }";
+
+    let manifest = parse(manifest).unwrap();
+
+    assert_eq!(
+        manifest,
+        TomlManifest::new(TomlPackage::new("json", version("1.0.0")))
+            .with_features([
+                ("default", vec!["std".to_owned()]),
+                ("std", vec![]),
+                (
+                    "serde",
+                    vec!["std".to_owned(), "dep:serialization_engine".to_owned()]
+                ),
+            ])
+            .with_dependencies([(
+                "serialization_engine",
+                TomlDependency::new()
+                    .with_path("../serialization_engine")
+                    .optional()
+            )])
+            .with_dev_dependencies([(
+                "test_utils",
+                TomlDependency::new().with_path("../test_utils")
+            )])
+    );
+
+    let enabled = manifest.enabled_features(&FeatureSelection::new()).unwrap();
+    assert_eq!(
+        enabled.features,
+        BTreeSet::from(["default".to_owned(), "std".to_owned()])
+    );
+    assert!(!enabled.is_dependency_enabled(&manifest, "serialization_engine"));
+
+    let enabled = manifest
+        .enabled_features(
+            &FeatureSelection::new()
+                .with_features(["serde"])
+                .without_default_features(),
+        )
+        .unwrap();
+    assert_eq!(
+        enabled.features,
+        BTreeSet::from(["serde".to_owned(), "std".to_owned()])
+    );
+    assert!(enabled.is_dependency_enabled(&manifest, "serialization_engine"));
+
+    assert_eq!(
+        manifest.enabled_features(&FeatureSelection::new().with_features(["serd"])),
+        Err("`json` doesn't have feature `serd`, did you mean `serde`?".to_owned())
+    );
+}
+
+#[test]
+fn unknown_feature_member() {
+    let (diagnostic, span) = single_diagnostic(
+        "[package]
+name = \"json\"
+version = \"1.0.0\"
+
+[features]
+serde = [\"dep:serialization_engine\"]
+
+[dependencies]
+serialization_engine = { path = \"../serialization_engine\" }",
+    );
+
+    assert_eq!(
+        diagnostic.message,
+        "feature `serde` enables unknown `dep:serialization_engine`"
+    );
+    assert_eq!(
+        diagnostic.labels[0].message,
+        "dependency `serialization_engine` is not optional, so it's always enabled"
+    );
+    assert_eq!(span, "\"dep:serialization_engine\"");
+}
@@ -0,0 +1,100 @@
+use stellar_manifest::{DependencyTable, Edition, ManifestEditor, TomlDependency, VersionReq};
+
+const MANIFEST: &str = "# The server.
+[package]
+name = \"server\"
+version = \"1.0.0\"
+
+[dependencies]
+# HTTP client and server.
+http = { version = \"1.0\", optional = true }
+json = { path = \"../json\" } # local
+";
+
+#[test]
+fn add_dependency() {
+    let mut editor = ManifestEditor::new(MANIFEST).unwrap();
+
+    editor
+        .add_dependency(
+            DependencyTable::Dependencies,
+            "hashmap",
+            &TomlDependency::new()
+                .with_version(VersionReq::parse("1.2").unwrap())
+                .with_author("quantumatic"),
+        )
+        .unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.to_owned() + "hashmap = { version = \"^1.2\", author = \"quantumatic\" }\n"
+    );
+}
+
+#[test]
+fn update_dependency() {
+    let mut editor = ManifestEditor::new(MANIFEST).unwrap();
+
+    editor
+        .add_dependency(
+            DependencyTable::Dependencies,
+            "http",
+            &TomlDependency::new().with_version(VersionReq::parse("=2.0.0").unwrap()),
+        )
+        .unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            "http = { version = \"1.0\", optional = true }",
+            "http = { version = \"=2.0.0\", optional = true }"
+        )
+    );
+}
+
+#[test]
+fn add_dev_dependency() {
+    let mut editor = ManifestEditor::new(MANIFEST).unwrap();
+
+    editor
+        .add_dependency(
+            DependencyTable::DevDependencies,
+            "test_utils",
+            &TomlDependency::new().with_path("../test_utils"),
+        )
+        .unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.to_owned() + "\n[dev-dependencies]\ntest_utils = { path = \"../test_utils\" }\n"
+    );
+}
+
+#[test]
+fn remove_dependency() {
+    let mut editor = ManifestEditor::new(MANIFEST).unwrap();
+
+    assert!(editor.remove_dependency(DependencyTable::Dependencies, "json"));
+    assert!(!editor.remove_dependency(DependencyTable::Dependencies, "json"));
+    assert!(!editor.remove_dependency(DependencyTable::DevDependencies, "http"));
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace("json = { path = \"../json\" } # local\n", "")
+    );
+}
+
+#[test]
+fn set_edition() {
+    let mut editor = ManifestEditor::new(MANIFEST).unwrap();
+
+    editor.set_edition(Edition::Edition2024).unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            "version = \"1.0.0\"\n",
+            "version = \"1.0.0\"\nedition = \"2024\"\n"
+        )
+    );
+}
@@ -0,0 +1,27 @@
+use stellar_manifest::license::{is_license_id, LicenseExpression};
+
+#[test]
+fn licenses() {
+    let expression = LicenseExpression::parse(
+        "MIT OR (GPL-3.0-only WITH Classpath-exception-2.0 AND BSD-3-Clause)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        expression.licenses(),
+        ["MIT", "GPL-3.0-only", "BSD-3-Clause"]
+    );
+    assert!(is_license_id("MIT"));
+    assert!(!is_license_id("MIT2"));
+}
+
+#[test]
+fn deny_list() {
+    let expression = LicenseExpression::parse("MIT OR GPL-3.0-only").unwrap();
+
+    assert!(expression.is_allowed(&["GPL-3.0-only"]));
+    assert!(!expression.is_allowed(&["GPL-3.0-only", "MIT"]));
+    assert!(!LicenseExpression::parse("MIT AND GPL-3.0-only")
+        .unwrap()
+        .is_allowed(&["GPL-3.0-only"]));
+}
@@ -0,0 +1,107 @@
+use stellar_manifest::{
+    lockfile::{GitSource, LockedPackage, Lockfile, PackageSource},
+    GitReference, Version,
+};
+
+fn version(version: &str) -> Version {
+    Version::parse(version).unwrap()
+}
+
+fn lockfile() -> Lockfile {
+    Lockfile::new([
+        LockedPackage::new("serialization_engine", version("1.2.0"))
+            .with_author("quantumatic")
+            .with_source(PackageSource::Path("../serialization_engine".to_owned()))
+            .with_checksum("8f434346"),
+        LockedPackage::new("json", version("0.1.0")).with_dependencies(["serialization_engine"]),
+    ])
+}
+
+#[test]
+fn roundtrip() {
+    let source = lockfile().to_string();
+
+    assert_eq!(
+        source,
+        "# This file is generated by the Stellar compiler. Do not edit it manually.
+version = 1
+
+[[package]]
+name = \"json\"
+version = \"0.1.0\"
+dependencies = [\"serialization_engine\"]
+
+[[package]]
+name = \"serialization_engine\"
+version = \"1.2.0\"
+author = \"quantumatic\"
+source = \"path+../serialization_engine\"
+checksum = \"8f434346\"
+"
+    );
+    assert_eq!(Lockfile::parse(&source), Ok(lockfile()));
+}
+
+#[test]
+fn invalid_source() {
+    assert_eq!(
+        Lockfile::parse(
+            "version = 1
+
+[[package]]
+name = \"json\"
+version = \"0.1.0\"
+source = \"../json\""
+        ),
+        Err(
+            "invalid package source `../json`, expected `path+<path>`, `registry+<location>` or `git+<url>#<commit>`"
+                .to_owned()
+        )
+    );
+}
+
+#[test]
+fn git_source() {
+    for (source, expected) in [
+        (
+            "git+file:///srv/json#8f434346",
+            GitSource::new("file:///srv/json", GitReference::DefaultBranch, "8f434346"),
+        ),
+        (
+            "git+../json?tag=v0.1.0#8f434346",
+            GitSource::new(
+                "../json",
+                GitReference::Tag("v0.1.0".to_owned()),
+                "8f434346",
+            ),
+        ),
+    ] {
+        let parsed = source.parse::<PackageSource>();
+
+        assert_eq!(parsed, Ok(PackageSource::Git(expected)));
+        assert_eq!(parsed.unwrap().to_string(), source);
+    }
+}
+
+#[test]
+fn changes() {
+    let old = lockfile();
+    let new = Lockfile::new([
+        LockedPackage::new("serialization_engine", version("1.3.0"))
+            .with_author("quantumatic")
+            .with_source(PackageSource::Path("../serialization_engine".to_owned()))
+            .with_checksum("8f434346"),
+        LockedPackage::new("http", version("1.0.0"))
+            .with_source(PackageSource::Registry("/srv/registry".to_owned())),
+    ]);
+
+    assert_eq!(
+        old.changes(&new),
+        [
+            "removed json 0.1.0",
+            "changed serialization_engine 1.2.0 (path+../serialization_engine): version 1.2.0 -> 1.3.0",
+            "added http 1.0.0 (registry+/srv/registry)",
+        ]
+    );
+    assert!(new.changes(&new).is_empty());
+}
@@ -0,0 +1,73 @@
+use stellar_manifest::{
+    registry::{IndexFile, IndexVersion},
+    TomlDependency, TomlManifest, TomlPackage, Version, VersionReq,
+};
+
+fn version(version: &str) -> Version {
+    Version::parse(version).unwrap()
+}
+
+#[test]
+fn roundtrip() {
+    let index_file = IndexFile::new()
+        .with_author("quantumatic")
+        .with_version(IndexVersion::new(version("1.1.0"), "4e074085").yanked())
+        .with_version(
+            IndexVersion::new(version("1.0.0"), "8f434346")
+                .with_dependencies([
+                    ("json", VersionReq::parse("^0.1").unwrap()),
+                    ("yaml", VersionReq::parse("^0.3").unwrap()),
+                ])
+                .with_optional_dependencies(["yaml"])
+                .with_features([("yaml", vec!["dep:yaml".to_owned()])]),
+        );
+    let source = index_file.to_string();
+
+    assert_eq!(
+        source,
+        "author = \"quantumatic\"
+
+[[version]]
+version = \"1.0.0\"
+checksum = \"8f434346\"
+optional = [\"yaml\"]
+
+[version.dependencies]
+json = \"^0.1\"
+yaml = \"^0.3\"
+
+[version.features]
+yaml = [\"dep:yaml\"]
+
+[[version]]
+version = \"1.1.0\"
+checksum = \"4e074085\"
+yanked = true
+"
+    );
+    assert_eq!(IndexFile::parse(&source), Ok(index_file));
+}
+
+#[test]
+fn manifest_dependencies() {
+    let manifest = TomlManifest::new(TomlPackage::new("http", version("1.0.0")))
+        .with_dependencies([
+            (
+                "json",
+                TomlDependency::new().with_version(VersionReq::parse("^0.1").unwrap()),
+            ),
+            ("yaml", TomlDependency::new().optional()),
+        ])
+        .with_features([("yaml", vec!["dep:yaml".to_owned()])]);
+
+    assert_eq!(
+        IndexVersion::new(version("1.0.0"), "8f434346").with_manifest(&manifest),
+        IndexVersion::new(version("1.0.0"), "8f434346")
+            .with_dependencies([
+                ("json", VersionReq::parse("^0.1").unwrap()),
+                ("yaml", VersionReq::STAR),
+            ])
+            .with_optional_dependencies(["yaml"])
+            .with_features([("yaml", vec!["dep:yaml".to_owned()])])
+    );
+}
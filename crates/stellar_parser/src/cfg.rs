@@ -0,0 +1,223 @@
+//! Conditional compilation.
+//!
+//! A module item, a method or a statement can be enabled only under some
+//! condition with a directive comment placed before it:
+//!
+//! ```stellar
+//! // stellar: cfg(feature = "json")
+//! pub fun to_json(self): String { ... }
+//!
+//! // stellar: cfg(all(test, not(feature = "std")))
+//! fun helper() {}
+//! ```
+//!
+//! Conditions are:
+//!
+//! * `feature = "<name>"` - the feature is enabled in the package manifest
+//!   or with `--features`;
+//! * `test` - the package is being tested;
+//! * `not(<condition>)`, `all(<condition>, ...)` and `any(<condition>, ...)`.
+//!
+//! Code, which conditions are not satisfied, is still parsed, but is
+//! removed from the AST, so that it is never lowered and analyzed.
+
+use stellar_fx_hash::FxHashSet;
+
+/// Options, that conditions in `cfg` directives are checked against.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CfgOptions {
+    features: FxHashSet<String>,
+    test: bool,
+}
+
+impl CfgOptions {
+    /// Returns options with no features enabled.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds new options with given features enabled.
+    #[inline]
+    #[must_use]
+    pub fn with_features(mut self, features: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    /// Builds new options, in which the `test` condition is satisfied.
+    #[inline]
+    #[must_use]
+    pub const fn with_test(mut self) -> Self {
+        self.test = true;
+        self
+    }
+
+    /// Returns `true` if a feature with a given name is enabled.
+    #[inline]
+    #[must_use]
+    pub fn is_feature_enabled(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// Returns `true` if the package is being tested.
+    #[inline]
+    #[must_use]
+    pub const fn is_test(&self) -> bool {
+        self.test
+    }
+}
+
+/// A condition in a `cfg` directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CfgPredicate {
+    Feature(String),
+    Test,
+    Not(Box<Self>),
+    All(Vec<Self>),
+    Any(Vec<Self>),
+}
+
+impl CfgPredicate {
+    /// Parses a condition, e.g. `all(test, feature = "json")`.
+    pub(crate) fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = PredicateParser { rest: source };
+        let predicate = parser.predicate()?;
+
+        parser.skip_whitespace();
+
+        if parser.rest.is_empty() {
+            Ok(predicate)
+        } else {
+            Err(format!("unexpected `{}` after the condition", parser.rest))
+        }
+    }
+
+    /// Returns `true` if the condition is satisfied.
+    pub(crate) fn evaluate(&self, options: &CfgOptions) -> bool {
+        match self {
+            Self::Feature(feature) => options.is_feature_enabled(feature),
+            Self::Test => options.is_test(),
+            Self::Not(predicate) => !predicate.evaluate(options),
+            Self::All(predicates) => predicates
+                .iter()
+                .all(|predicate| predicate.evaluate(options)),
+            Self::Any(predicates) => predicates
+                .iter()
+                .any(|predicate| predicate.evaluate(options)),
+        }
+    }
+}
+
+struct PredicateParser<'s> {
+    rest: &'s str,
+}
+
+impl<'s> PredicateParser<'s> {
+    fn predicate(&mut self) -> Result<CfgPredicate, String> {
+        match self.identifier()? {
+            "test" => Ok(CfgPredicate::Test),
+            "feature" => {
+                self.expect('=')?;
+
+                Ok(CfgPredicate::Feature(self.string()?.to_owned()))
+            }
+            "not" => {
+                let mut predicates = self.predicate_list()?;
+
+                if predicates.len() == 1 {
+                    Ok(CfgPredicate::Not(Box::new(predicates.remove(0))))
+                } else {
+                    Err("`not` expects exactly one condition".to_owned())
+                }
+            }
+            "all" => Ok(CfgPredicate::All(self.predicate_list()?)),
+            "any" => Ok(CfgPredicate::Any(self.predicate_list()?)),
+            name => Err(format!(
+                "unknown condition `{name}`, expected `feature`, `test`, `not`, `all` or `any`"
+            )),
+        }
+    }
+
+    fn predicate_list(&mut self) -> Result<Vec<CfgPredicate>, String> {
+        self.expect('(')?;
+
+        let mut predicates = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest.starts_with(')') {
+                break;
+            }
+
+            predicates.push(self.predicate()?);
+            self.skip_whitespace();
+
+            if !self.rest.starts_with(',') {
+                break;
+            }
+
+            self.rest = &self.rest[1..];
+        }
+
+        self.expect(')')?;
+
+        Ok(predicates)
+    }
+
+    fn identifier(&mut self) -> Result<&'s str, String> {
+        self.skip_whitespace();
+
+        let length = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest.len());
+
+        if length == 0 {
+            return Err(self.unexpected("a condition"));
+        }
+
+        let (identifier, rest) = self.rest.split_at(length);
+        self.rest = rest;
+
+        Ok(identifier)
+    }
+
+    fn string(&mut self) -> Result<&'s str, String> {
+        self.expect('"')?;
+
+        let Some(length) = self.rest.find('"') else {
+            return Err("unterminated string".to_owned());
+        };
+
+        let (string, rest) = self.rest.split_at(length);
+        self.rest = &rest[1..];
+
+        Ok(string)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+
+        if let Some(rest) = self.rest.strip_prefix(expected) {
+            self.rest = rest;
+
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{expected}`")))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> String {
+        match self.rest.chars().next() {
+            Some(c) => format!("expected {expected}, found `{c}`"),
+            None => format!("expected {expected}, found end of the directive"),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+}
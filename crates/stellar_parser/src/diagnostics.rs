@@ -1,6 +1,6 @@
 //! Defines diagnostics for parser.
 
-use stellar_ast::token::{LexError, Token};
+use stellar_ast::token::{Edition, LexError, Token};
 use stellar_diagnostics::diagnostic::Label;
 use stellar_diagnostics::BuildDiagnostic;
 use stellar_diagnostics::{define_diagnostics, diagnostic::Diagnostic};
@@ -80,6 +80,37 @@ define_diagnostics! {
             secondary { self.got.location => "unexpected token" }
         }
     }
+
+    /// Diagnostic related to an identifier, that becomes a keyword in the next
+    /// edition of the language.
+    diagnostic(warning) NextEditionKeyword(
+        self,
+        name: String,
+        edition: Edition,
+        location: Location
+    ) {
+        code { "W005" }
+        message { format!("`{}` is a keyword in the {} edition", self.name, self.edition) }
+        labels {
+            primary { self.location => format!("help: write `` `{}` `` instead", self.name) }
+        }
+        notes {
+            "note: run `stellar migrate` to wrap such identifiers and update the edition"
+        }
+    }
+
+    /// Diagnostic related to a directive comment (`// stellar: ...`), that
+    /// cannot be parsed.
+    diagnostic(error) InvalidDirective(self, message: String, location: Location) {
+        code { "E013" }
+        message { format!("invalid directive: {}", self.message) }
+        labels {
+            primary { self.location }
+        }
+        notes {
+            "note: directives look like `// stellar: cfg(feature = \"name\")` or `// stellar: allow(lint_name)`"
+        }
+    }
 }
 
 /// Diagnostic related to an unnecessary visibility qualifier error.
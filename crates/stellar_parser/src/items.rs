@@ -1,8 +1,8 @@
 use stellar_ast::{
     token::{Keyword, Punctuator, RawToken},
-    Enum, EnumItem, Function, FunctionParameter, FunctionSignature, IdentifierAST, Interface,
-    ModuleItem, NotSelfFunctionParameter, SelfFunctionParameter, Struct, StructField, TupleField,
-    TupleLikeStruct, TypeAlias, Visibility,
+    Docstring, Enum, EnumItem, Function, FunctionParameter, FunctionSignature, IdentifierAST,
+    Interface, ModuleItem, NotSelfFunctionParameter, SelfFunctionParameter, Struct, StructField,
+    TupleField, TupleLikeStruct, TypeAlias, Visibility,
 };
 use stellar_english_commons::enumeration::one_of;
 use stellar_interner::builtin_identifiers;
@@ -19,7 +19,7 @@ use crate::{
         WherePredicatesParser,
     },
     statement::StatementsBlockParser,
-    OptionallyParse, Parse, ParseState, VisibilityParser,
+    Directives, OptionallyParse, Parse, ParseState, VisibilityParser,
 };
 
 struct ImportParser {
@@ -56,7 +56,7 @@ impl Parse for ImportParser {
 
 struct StructFieldParser {
     pub(crate) visibility: Visibility,
-    pub(crate) docstring: Option<String>,
+    pub(crate) docstring: Option<Docstring>,
 }
 
 impl Parse for StructFieldParser {
@@ -103,7 +103,7 @@ impl Parse for StructFieldsParser {
 
 struct StructParser {
     pub(crate) visibility: Visibility,
-    pub(crate) docstring: Option<String>,
+    pub(crate) docstring: Option<Docstring>,
 }
 
 impl Parse for StructParser {
@@ -167,19 +167,7 @@ impl Parse for StructParser {
             if state.next_token.raw != Punctuator::Semicolon {
                 state.consume(Punctuator::OpenBrace)?;
 
-                loop {
-                    if state.next_token.raw == Punctuator::CloseBrace {
-                        break;
-                    }
-
-                    methods.push(
-                        FunctionParser {
-                            visibility: VisibilityParser.parse(state),
-                            docstring: state.consume_local_docstring(),
-                        }
-                        .parse(state)?,
-                    );
-                }
+                methods = MethodsParser.parse(state)?;
 
                 state.advance();
             }
@@ -218,26 +206,12 @@ impl Parse for StructParser {
             )
             .parse(state)?;
 
-            let mut methods = vec![];
-
-            if state.next_token.raw == Keyword::Fun || state.next_token.raw == Keyword::Pub {
-                loop {
-                    if state.next_token.raw == Punctuator::CloseBrace {
-                        break;
-                    }
-
-                    let docstring = state.consume_local_docstring();
-                    let visibility = VisibilityParser.parse(state);
-
-                    methods.push(
-                        FunctionParser {
-                            visibility,
-                            docstring,
-                        }
-                        .parse(state)?,
-                    );
-                }
-            }
+            let methods =
+                if state.next_token.raw == Keyword::Fun || state.next_token.raw == Keyword::Pub {
+                    MethodsParser.parse(state)?
+                } else {
+                    vec![]
+                };
 
             state.advance();
 
@@ -281,7 +255,7 @@ impl Parse for NotSelfFunctionParameterParser {
 
 struct FunctionParser {
     visibility: Visibility,
-    docstring: Option<String>,
+    docstring: Option<Docstring>,
 }
 
 impl Parse for FunctionParser {
@@ -362,9 +336,46 @@ impl Parse for FunctionParser {
     }
 }
 
+/// Parses methods of a struct or an interface up to the closing brace, which
+/// is not consumed. Methods disabled by `cfg` directives are skipped.
+struct MethodsParser;
+
+impl Parse for MethodsParser {
+    type Output = Option<Vec<Function>>;
+
+    fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        let mut methods = vec![];
+
+        while state.next_token.raw != Punctuator::CloseBrace {
+            // Directives can be placed both before and after the docstring.
+            let mut directives = Directives::new();
+            state.consume_directives(&mut directives);
+
+            let start = state.next_token.location.start;
+            let docstring = state.consume_local_docstring();
+            state.consume_directives(&mut directives);
+            let visibility = VisibilityParser.parse(state);
+
+            let method = FunctionParser {
+                visibility,
+                docstring,
+            }
+            .parse(state)?;
+
+            state.allow_lints(&directives, start);
+
+            if directives.enabled {
+                methods.push(method);
+            }
+        }
+
+        Some(methods)
+    }
+}
+
 struct TypeAliasParser {
     visibility: Visibility,
-    docstring: Option<String>,
+    docstring: Option<Docstring>,
 }
 
 impl Parse for TypeAliasParser {
@@ -394,7 +405,7 @@ impl Parse for TypeAliasParser {
 
 struct InterfaceParser {
     visibility: Visibility,
-    docstring: Option<String>,
+    docstring: Option<Docstring>,
 }
 
 impl Parse for InterfaceParser {
@@ -419,19 +430,9 @@ impl Parse for InterfaceParser {
 
         state.consume(Punctuator::OpenBrace)?;
 
-        let mut methods = vec![];
-
-        loop {
-            if state.next_token.raw == Punctuator::CloseBrace {
-                break;
-            }
-
-            let method = FunctionParser {
-                docstring: state.consume_local_docstring(),
-                visibility: VisibilityParser.parse(state),
-            }
-            .parse(state)?;
+        let methods = MethodsParser.parse(state)?;
 
+        for method in &methods {
             if let Visibility::Public(location) = method.signature.visibility {
                 state
                     .diagnostics
@@ -442,8 +443,6 @@ impl Parse for InterfaceParser {
                         },
                     });
             }
-
-            methods.push(method);
         }
 
         state.advance();
@@ -462,7 +461,7 @@ impl Parse for InterfaceParser {
 
 struct EnumParser {
     visibility: Visibility,
-    docstring: Option<String>,
+    docstring: Option<Docstring>,
 }
 
 macro_rules! possibly_recover {
@@ -590,7 +589,7 @@ impl Parse for EnumItemParser {
 
 struct EnumItemStructParser {
     name: IdentifierAST,
-    docstring: Option<String>,
+    docstring: Option<Docstring>,
 }
 
 impl Parse for EnumItemStructParser {
@@ -671,10 +670,16 @@ impl Parse for ItemParser {
     type Output = Option<ModuleItem>;
 
     fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        // Directives can be placed both before and after the docstring.
+        let mut directives = Directives::new();
+        state.consume_directives(&mut directives);
+
+        let start = state.next_token.location.start;
         let docstring = state.consume_local_docstring();
+        state.consume_directives(&mut directives);
         let visibility = VisibilityParser.parse(state);
 
-        Some(match state.next_token.raw {
+        let item = Some(match state.next_token.raw {
             RawToken::Keyword(Keyword::Enum) => {
                 possibly_recover!(
                     state,
@@ -731,6 +736,10 @@ impl Parse for ItemParser {
 
                 return None;
             }
-        })
+        });
+
+        state.allow_lints(&directives, start);
+
+        item.filter(|_| directives.enabled)
     }
 }
@@ -64,6 +64,7 @@
     clippy::unnested_or_patterns
 )]
 
+mod cfg;
 pub mod diagnostics;
 mod expression;
 mod items;
@@ -76,8 +77,10 @@ mod r#type;
 
 #[cfg(feature = "debug")]
 use std::time::Instant;
-use std::{fs, io};
+use std::{collections::BTreeMap, fs, io, iter, path::PathBuf};
 
+pub use cfg::CfgOptions;
+use cfg::CfgPredicate;
 use diagnostics::LexErrorDiagnostic;
 pub use expression::ExpressionParser;
 use items::{ItemParser, ItemsParser};
@@ -85,19 +88,27 @@ use pattern::PatternParser;
 use r#type::TypeParser;
 use statement::StatementParser;
 use stellar_ast::{
-    token::{Keyword, LexError, RawToken, Token},
-    Expression, IdentifierAST, Module, ModuleItem, Pattern, Statement, Type, Visibility,
+    token::{resolve_next_edition_keyword, Edition, Keyword, LexError, RawToken, Token},
+    Docstring, Expression, IdentifierAST, Module, ModuleItem, Pattern, Statement, Type, Visibility,
 };
 use stellar_database::{ModuleData, ModuleId, PackageId, Path, State};
-use stellar_diagnostics::Diagnostics;
-use stellar_filesystem::location::{ByteOffset, Location};
-use stellar_interner::PathId;
+use stellar_diagnostics::{
+    lint::{is_lint_or_group, LintSuppression},
+    Diagnostics,
+};
+use stellar_filesystem::{
+    location::{ByteOffset, Location},
+    path_resolver::PackagePathResolver,
+};
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{IdentifierId, PathId};
 use stellar_lexer::Lexer;
 use stellar_stable_likely::unlikely;
 #[cfg(feature = "debug")]
 use tracing::trace;
+use walkdir::WalkDir;
 
-use crate::diagnostics::UnexpectedToken;
+use crate::diagnostics::{InvalidDirective, NextEditionKeyword, UnexpectedToken};
 
 /// Represents a parse state.
 #[derive(Debug)]
@@ -112,6 +123,13 @@ pub struct ParseState<'s, 'd> {
 
     /// Diagnostics that is emitted during parsing.
     diagnostics: &'d mut Diagnostics,
+
+    /// Options, that conditions of `cfg` directives are checked against.
+    cfg_options: CfgOptions,
+
+    /// Locations of directive comments (`// stellar: ...`), that come right before
+    /// the next token.
+    directives: Vec<Location>,
 }
 
 /// Represents AST node that can be parsed.
@@ -306,10 +324,16 @@ pub fn parse_statement(
 }
 
 /// Parse a statement.
+///
+/// Returns `None` also if the statement is disabled by a `cfg` directive, in
+/// that case no diagnostics are reported.
 #[inline]
 #[must_use]
 pub fn parse_statement_using(state: &mut ParseState<'_, '_>) -> Option<Statement> {
-    StatementParser.parse(state).map(|s| s.statement)
+    StatementParser
+        .parse(state)
+        .filter(|s| s.enabled)
+        .map(|s| s.statement)
 }
 
 /// Parse a type.
@@ -348,74 +372,211 @@ pub fn parse_pattern_using(state: &mut ParseState<'_, '_>) -> Option<Pattern> {
     PatternParser.parse(state)
 }
 
-// /// Traverses, reads and parses all package source files.
-// ///
-// /// # Errors
-// /// Returns an error if the package's source directory cannot be read.
-// pub fn parse_package_source_files(
-//     state: &mut State,
-//     root: impl AsRef<Path>,
-// ) -> Result<Vec<ParsedModule>, String> {
-//     fn module_name(path: PathId) -> IdentifierId {
-//         IdentifierId::from(
-//             path.resolve_or_panic()
-//                 .file_stem()
-//                 .unwrap()
-//                 .to_str()
-//                 .unwrap(),
-//         )
-//     }
-
-//     let root = root.as_ref();
-
-//     let source_directory = PackagePathResolver::new(root).source_directory();
-
-//     if !source_directory.exists() {
-//         return Err(format!(
-//             "cannot find package's source directory in {}",
-//             root.display()
-//         ));
-//     }
-
-//     Ok(
-//         WalkDir::new(PackagePathResolver::new(root).source_directory())
-//             .into_iter()
-//             .filter_map(|entry| {
-//                 let Ok(entry) = entry else {
-//                     return None;
-//                 };
-
-//                 #[cfg(feature = "debug")]
-//                 let now = Instant::now();
-
-//                 let filepath = PathId::from(entry.path());
-//                 let parsing_result = read_and_parse_module(
-//                     state,
-//                     PackageId(0), // TODO: package managment
-//                     module_name(filepath),
-//                     filepath,
-//                 );
-
-//                 #[cfg(feature = "debug")]
-//                 trace!(
-//                     "parse_module(module = '{}') <{} us>",
-//                     entry.path().display(),
-//                     now.elapsed().as_micros()
-//                 );
-
-//                 parsing_result.ok()
-//             })
-//             .collect(),
-//     )
-// }
+/// Traverses, reads and parses source files of the library target of a package,
+/// whose root module is `src/package.sr`.
+///
+/// See [`parse_target_source_files`] for more details.
+///
+/// # Errors
+/// Returns an error if the package's source directory or one of the source files
+/// cannot be read.
+pub fn parse_package_source_files(
+    state: &mut State,
+    package: PackageId,
+    root: impl AsRef<std::path::Path>,
+    cfg_options: &CfgOptions,
+    edition: Edition,
+) -> Result<Vec<ParseResult>, String> {
+    let source_directory = PackagePathResolver::new(root.as_ref()).source_directory();
+
+    parse_target_source_files(
+        state,
+        package,
+        &source_directory.join("package.sr"),
+        &[source_directory.join("bin")],
+        cfg_options,
+        edition,
+    )
+}
+
+/// Traverses, reads and parses source files of a build target, e.g. a library
+/// or a binary, whose root module is located at a given path.
+///
+/// Every source file in the module directory corresponds to a module, whose path
+/// is the name of the package followed by the path of the file relative to the
+/// module directory. The module directory is the directory of the root module,
+/// if it's named `package.sr`, or the directory with the same name as the root
+/// module otherwise. For example, the module directory of `src/package.sr` is
+/// `src` and `src/http/client.sr` becomes `<package>.http.client`, while the
+/// module directory of `src/bin/server.sr` is `src/bin/server`. Directories without
+/// a corresponding source file, as well as the root module if it doesn't exist,
+/// become empty modules, so that the module tree is always complete. Files and
+/// directories in `excluded`, e.g. root modules of other targets, are skipped.
+///
+/// Parsed modules are linked together as submodules and the root module is set
+/// in the package data. Module items disabled by `cfg` directives are removed
+/// according to given options, see [`CfgOptions`] for more details. Keywords are
+/// reserved according to a given edition of the package.
+///
+/// # Errors
+/// Returns an error if neither the root module nor the module directory exist,
+/// or one of the source files cannot be read.
+pub fn parse_target_source_files(
+    state: &mut State,
+    package: PackageId,
+    root_module: &std::path::Path,
+    excluded: &[PathBuf],
+    cfg_options: &CfgOptions,
+    edition: Edition,
+) -> Result<Vec<ParseResult>, String> {
+    let module_directory = module_directory(root_module);
+
+    if !root_module.is_file() && !module_directory.is_dir() {
+        return Err(format!(
+            "cannot find the root module {}",
+            root_module.display()
+        ));
+    }
+
+    // Module paths relative to the package are mapped to source files. Because the map
+    // is sorted, parent modules always come before their submodules.
+    let mut module_files = BTreeMap::<Vec<String>, Option<PathBuf>>::new();
+    module_files.insert(
+        Vec::new(),
+        root_module.is_file().then(|| root_module.to_owned()),
+    );
+
+    for entry in WalkDir::new(&module_directory)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !excluded.iter().any(|path| path == entry.path()))
+    {
+        let entry = match entry {
+            Ok(entry) => entry,
+            // Targets without submodules don't have a module directory.
+            Err(err) if err.depth() == 0 && !module_directory.exists() => continue,
+            Err(err) => return Err(err.to_string()),
+        };
+        let Ok(relative_path) = entry.path().strip_prefix(&module_directory) else {
+            continue;
+        };
+
+        if entry.path() == root_module {
+            continue;
+        }
+
+        if entry.file_type().is_dir() {
+            module_files
+                .entry(path_segments(relative_path))
+                .or_insert(None);
+        } else if relative_path
+            .extension()
+            .is_some_and(|extension| extension == "sr")
+        {
+            module_files.insert(
+                path_segments(relative_path.with_extension("").as_path()),
+                Some(entry.into_path()),
+            );
+        }
+    }
+
+    let package_name = package.name(state.db());
+    let mut modules = FxHashMap::<Vec<String>, ModuleId>::default();
+    let mut parse_results = Vec::with_capacity(module_files.len());
+
+    for (segments, filepath) in module_files {
+        #[cfg(feature = "debug")]
+        let now = Instant::now();
+
+        let path = Path::new(
+            iter::once(package_name)
+                .chain(segments.iter().map(IdentifierId::from))
+                .collect(),
+        );
+
+        let (filepath, source) = if let Some(filepath) = filepath {
+            let source = fs::read_to_string(&filepath)
+                .map_err(|err| format!("cannot read {}: {err}", filepath.display()))?;
+
+            (PathId::from(&filepath), source)
+        } else if segments.is_empty() {
+            (PathId::from(root_module), String::new())
+        } else {
+            (
+                PathId::from(module_directory.join(segments.join("/"))),
+                String::new(),
+            )
+        };
+
+        let module = ModuleData::alloc(state.db_mut(), package, path, filepath);
+        let mut parse_state = ParseState::new(filepath, &source, state.diagnostics_mut())
+            .with_edition(edition)
+            .with_cfg_options(cfg_options.clone());
+
+        let parse_result = ParseResult::new(
+            module,
+            Module {
+                filepath,
+                docstring: parse_state.consume_module_docstring(),
+                items: ItemsParser.parse(&mut parse_state),
+            },
+        );
+
+        #[cfg(feature = "debug")]
+        trace!(
+            "parse_module(module = '{}') <{} us>",
+            parse_result.module().filepath(state.db()),
+            now.elapsed().as_micros()
+        );
+
+        let module = parse_result.module();
+
+        if let Some((_, parent_segments)) = segments.split_last() {
+            modules[parent_segments].add_submodule(state.db_mut(), module);
+        } else {
+            package.set_root_module(state.db_mut(), module);
+        }
+
+        modules.insert(segments, module);
+        parse_results.push(parse_result);
+    }
+
+    Ok(parse_results)
+}
+
+/// Returns the directory, that contains submodules of a given root module: the
+/// directory of the root module, if it's named `package.sr`, or the directory
+/// with the same name as the root module otherwise.
+#[must_use]
+pub fn module_directory(root_module: &std::path::Path) -> PathBuf {
+    if root_module
+        .file_stem()
+        .is_some_and(|stem| stem == "package")
+    {
+        root_module.parent().unwrap_or(root_module).to_owned()
+    } else {
+        root_module.with_extension("")
+    }
+}
+
+/// Returns the components of a relative path as strings.
+fn path_segments(path: &std::path::Path) -> Vec<String> {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect()
+}
 
 impl<'s, 'd> ParseState<'s, 'd> {
     /// Creates an initial parse state from file source.
     #[must_use]
     pub fn new(filepath: PathId, source: &'s str, diagnostics: &'d mut Diagnostics) -> Self {
         let mut lexer = Lexer::new(filepath, source);
+        let mut directives = Vec::new();
 
-        let current_token = lexer.next_no_comments();
+        // The first token is both the current and the next one, so that the first
+        // `advance()` makes it current and checks, that it's not a keyword of
+        // the next edition, like every other token.
+        let current_token = next_token_collecting_directives(&mut lexer, &mut directives);
         let next_token = current_token;
 
         let mut state = Self {
@@ -423,12 +584,37 @@ impl<'s, 'd> ParseState<'s, 'd> {
             current_token,
             next_token,
             diagnostics,
+            cfg_options: CfgOptions::new(),
+            directives,
         };
         state.check_next_token();
 
         state
     }
 
+    /// Builds a new parse state, that reserves keywords of a given edition.
+    #[must_use]
+    pub fn with_edition(mut self, edition: Edition) -> Self {
+        // Only the first token was scanned before the edition was known, so it's
+        // the only one lexed twice. Lex errors don't depend on the edition, so they
+        // are already reported, while the edition keyword check happens, when
+        // the token is consumed.
+        self.lexer = Lexer::new(self.lexer.filepath, self.lexer.source).with_edition(edition);
+        self.directives.clear();
+        self.next_token = next_token_collecting_directives(&mut self.lexer, &mut self.directives);
+        self.current_token = self.next_token;
+        self
+    }
+
+    /// Builds a new parse state, that checks conditions of `cfg` directives
+    /// against given options.
+    #[inline]
+    #[must_use]
+    pub fn with_cfg_options(mut self, cfg_options: CfgOptions) -> Self {
+        self.cfg_options = cfg_options;
+        self
+    }
+
     /// Adds diagnostic if the next token has lex error in itself.
     #[inline]
     fn check_next_token(&mut self) {
@@ -441,6 +627,24 @@ impl<'s, 'd> ParseState<'s, 'd> {
         }
     }
 
+    /// Warns about the current token, if it's an identifier, that becomes a keyword
+    /// in the next edition. Wrapped identifiers are fine in every edition.
+    fn check_next_edition_keyword(&mut self) {
+        if self.current_token.raw != RawToken::Identifier {
+            return;
+        }
+
+        let name = self.resolve_current_token_str();
+
+        if let Some((_, edition)) = resolve_next_edition_keyword(name, self.lexer.edition()) {
+            self.diagnostics.add_diagnostic(NextEditionKeyword::new(
+                name.to_owned(),
+                edition,
+                self.current_token.location,
+            ));
+        }
+    }
+
     /// Returns string slice corresponding to the given location.
     #[inline]
     #[must_use]
@@ -460,7 +664,61 @@ impl<'s, 'd> ParseState<'s, 'd> {
         self.check_next_token();
 
         self.current_token = self.next_token;
-        self.next_token = self.lexer.next_no_comments();
+        self.check_next_edition_keyword();
+
+        // Directives, that came before the consumed token, are not attached to anything.
+        self.directives.clear();
+        self.next_token = next_token_collecting_directives(&mut self.lexer, &mut self.directives);
+    }
+
+    /// Consumes directive comments, that come right before the next token, and
+    /// adds them to given directives.
+    pub(crate) fn consume_directives(&mut self, directives: &mut Directives) {
+        let source = self.lexer.source;
+
+        for location in std::mem::take(&mut self.directives) {
+            let directive = source[location.start.0 + "//".len()..location.end.0]
+                .trim()
+                .strip_prefix(DIRECTIVE_PREFIX)
+                .unwrap_or_default()
+                .trim();
+
+            let result = if let Some(condition) = directive_arguments(directive, "cfg") {
+                CfgPredicate::parse(condition).map(|predicate| {
+                    directives.enabled &= predicate.evaluate(&self.cfg_options);
+                })
+            } else if let Some(names) = directive_arguments(directive, "allow") {
+                allowed_lints(names, source, self.lexer.filepath)
+                    .map(|lints| directives.allowed_lints.extend(lints))
+            } else {
+                Err(format!(
+                    "unknown directive `{directive}`, expected `cfg(<condition>)` or `allow(<lints>)`"
+                ))
+            };
+
+            if let Err(message) = result {
+                self.diagnostics
+                    .add_diagnostic(InvalidDirective::new(message, location));
+            }
+        }
+    }
+
+    /// Allows lints of `allow` directives in the code starting at a given offset
+    /// and ending with the current token.
+    ///
+    /// Nothing is allowed in code disabled by `cfg` directives, because it is
+    /// never checked, so the suppressions would be reported as unused.
+    pub(crate) fn allow_lints(&mut self, directives: &Directives, start: ByteOffset) {
+        if !directives.enabled {
+            return;
+        }
+
+        let scope = self.location_from(start);
+
+        for (name, location) in &directives.allowed_lints {
+            self.diagnostics
+                .add_suppression(LintSuppression::new(name.clone(), *location, scope));
+        }
     }
 
     /// Checks if the next token is [`expected`].
@@ -523,35 +781,40 @@ impl<'s, 'd> ParseState<'s, 'd> {
     }
 
     /// Consumes the docstring for a module.
-    pub(crate) fn consume_module_docstring(&mut self) -> Option<String> {
-        if self.next_token.raw == RawToken::GlobalDocComment {
-            let mut module_docstring = String::new();
+    pub(crate) fn consume_module_docstring(&mut self) -> Option<Docstring> {
+        self.consume_docstring(RawToken::GlobalDocComment)
+    }
 
-            while self.next_token.raw == RawToken::GlobalDocComment {
-                self.advance();
+    /// Consumes the docstring for a local item.
+    pub(crate) fn consume_local_docstring(&mut self) -> Option<Docstring> {
+        self.consume_docstring(RawToken::LocalDocComment)
+    }
 
-                module_docstring.push_str(self.resolve_location(self.current_token.location));
-            }
-            Some(module_docstring)
-        } else {
-            None
+    /// Consumes consecutive doc comments of a given kind.
+    fn consume_docstring(&mut self, kind: RawToken) -> Option<Docstring> {
+        if self.next_token.raw != kind {
+            return None;
         }
-    }
 
-    /// Consumes the docstring for a local item.
-    pub(crate) fn consume_local_docstring(&mut self) -> Option<String> {
-        if self.next_token.raw == RawToken::LocalDocComment {
-            let mut local_docstring = String::new();
+        let source = self.lexer.source;
+        let mut lines = vec![];
 
-            while self.next_token.raw == RawToken::LocalDocComment {
-                self.advance();
+        while self.next_token.raw == kind {
+            self.advance();
 
-                local_docstring.push_str(self.resolve_location(self.current_token.location));
-            }
-            Some(local_docstring)
-        } else {
-            None
+            let location = self.current_token.location;
+            let line = docstring_line(&source[location.start.0..location.end.0]);
+
+            lines.push((
+                line,
+                Location {
+                    start: location.end - ByteOffset(line.len()),
+                    ..location
+                },
+            ));
         }
+
+        Some(Docstring::new(lines))
     }
 
     /// Adds an unexpected token diagnostic.
@@ -567,6 +830,103 @@ impl<'s, 'd> ParseState<'s, 'd> {
     }
 }
 
+/// The prefix of directive comments after `//`.
+const DIRECTIVE_PREFIX: &str = "stellar:";
+
+/// Directive comments, that come before an item, a method or a statement.
+#[derive(Debug)]
+pub(crate) struct Directives {
+    /// Whether conditions of all `cfg` directives are satisfied.
+    pub(crate) enabled: bool,
+
+    /// Names of lints allowed with `allow` directives and their locations.
+    pub(crate) allowed_lints: Vec<(String, Location)>,
+}
+
+impl Directives {
+    /// Returns directives, that enable the code and don't allow any lints.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new() -> Self {
+        Self {
+            enabled: true,
+            allowed_lints: Vec::new(),
+        }
+    }
+}
+
+/// Returns arguments of a directive with a given name, e.g. `feature = "json"`
+/// for `cfg(feature = "json")`.
+fn directive_arguments<'a>(directive: &'a str, name: &str) -> Option<&'a str> {
+    directive
+        .strip_prefix(name)
+        .and_then(|rest| rest.trim_start().strip_prefix('('))
+        .and_then(|rest| rest.strip_suffix(')'))
+}
+
+/// Parses comma separated names of lints in an `allow` directive and returns
+/// them with their locations. `names` must be a part of `source`.
+fn allowed_lints(
+    names: &str,
+    source: &str,
+    filepath: PathId,
+) -> Result<Vec<(String, Location)>, String> {
+    let mut lints = Vec::new();
+
+    for name in names.split(',').map(str::trim) {
+        if name.is_empty() {
+            return Err("expected a lint name".to_owned());
+        }
+
+        if !is_lint_or_group(name) {
+            return Err(format!("unknown lint `{name}`"));
+        }
+
+        let start = ByteOffset(name.as_ptr() as usize - source.as_ptr() as usize);
+
+        lints.push((
+            name.to_owned(),
+            Location {
+                filepath,
+                start,
+                end: start + name.len(),
+            },
+        ));
+    }
+
+    Ok(lints)
+}
+
+/// Works the same as [`Lexer::next_no_comments`], but remembers locations
+/// of directive comments (`// stellar: ...`).
+fn next_token_collecting_directives(
+    lexer: &mut Lexer<'_>,
+    directives: &mut Vec<Location>,
+) -> Token {
+    loop {
+        let token = lexer.next_token();
+
+        if token.raw != RawToken::Comment {
+            return token;
+        }
+
+        if lexer.source[token.location.start.0 + "//".len()..token.location.end.0]
+            .trim_start()
+            .starts_with(DIRECTIVE_PREFIX)
+        {
+            directives.push(token.location);
+        }
+    }
+}
+
+/// Returns the contents of a doc comment line without the `///` or `//!` prefix
+/// and a single space after it.
+fn docstring_line(comment: &str) -> &str {
+    let line = &comment[3..];
+
+    line.strip_prefix(' ').unwrap_or(line)
+}
+
 pub(crate) struct VisibilityParser;
 
 impl Parse for VisibilityParser {
@@ -4,7 +4,8 @@ use stellar_ast::{
 };
 
 use crate::{
-    expression::ExpressionParser, pattern::PatternParser, r#type::TypeParser, Parse, ParseState,
+    expression::ExpressionParser, pattern::PatternParser, r#type::TypeParser, Directives, Parse,
+    ParseState,
 };
 
 pub(crate) struct StatementParser;
@@ -12,6 +13,8 @@ pub(crate) struct StatementParser;
 pub(crate) struct StatementParserResult {
     pub(crate) statement: Statement,
     pub(crate) last_expression_in_block: bool,
+    /// Whether conditions of `cfg` directives before the statement are satisfied.
+    pub(crate) enabled: bool,
 }
 
 impl StatementParser {
@@ -111,6 +114,11 @@ impl Parse for StatementParser {
     type Output = Option<StatementParserResult>;
 
     fn parse(self, state: &mut ParseState<'_, '_>) -> Self::Output {
+        let mut directives = Directives::new();
+        state.consume_directives(&mut directives);
+
+        let start = state.next_token.location.start;
+
         let (statement, last_expression_in_block) = match state.next_token.raw {
             RawToken::Keyword(Keyword::Return) => (self.parse_return_statement(state)?, false),
             RawToken::Keyword(Keyword::Defer) => (self.parse_defer_statement(state)?, false),
@@ -127,9 +135,12 @@ impl Parse for StatementParser {
             }
         };
 
+        state.allow_lints(&directives, start);
+
         Some(StatementParserResult {
             statement,
             last_expression_in_block,
+            enabled: directives.enabled,
         })
     }
 }
@@ -169,8 +180,12 @@ impl Parse for StatementsBlockParser {
             let StatementParserResult {
                 statement,
                 last_expression_in_block,
+                enabled,
             } = StatementParser.parse(state)?;
-            block.push(statement);
+
+            if enabled {
+                block.push(statement);
+            }
 
             if last_expression_in_block {
                 break;
@@ -0,0 +1,80 @@
+use stellar_ast::token::Edition;
+use stellar_diagnostics::Diagnostics;
+use stellar_interner::DUMMY_PATH_ID;
+use stellar_lint::register_lints;
+use stellar_parser::{parse_item_using, ParseState};
+
+/// Parses an item in the 2023 edition, reports unused suppressions and returns
+/// messages of all diagnostics.
+fn diagnostics(source: &str) -> Vec<String> {
+    register_lints();
+
+    let mut diagnostics = Diagnostics::new();
+    let _ = parse_item_using(
+        &mut ParseState::new(DUMMY_PATH_ID, source, &mut diagnostics)
+            .with_edition(Edition::Edition2023),
+    );
+
+    diagnostics.report_unused_suppressions();
+    diagnostics
+        .diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn item() {
+    assert!(diagnostics("// stellar: allow(next_edition_keyword)\nfun async() {}").is_empty());
+    assert!(diagnostics("// stellar: allow(compatibility)\nfun async() {}").is_empty());
+}
+
+#[test]
+fn method() {
+    let source = "struct Foo {
+    // stellar: allow(next_edition_keyword)
+    fun async() {}
+
+    fun const() {}
+}";
+
+    assert_eq!(
+        diagnostics(source),
+        ["`const` is a keyword in the 2024 edition"]
+    );
+}
+
+#[test]
+fn statement() {
+    let source = "fun main() {
+    // stellar: allow(next_edition_keyword)
+    let async = 1;
+    let mut = 2;
+}";
+
+    assert_eq!(
+        diagnostics(source),
+        ["`mut` is a keyword in the 2024 edition"]
+    );
+}
+
+#[test]
+fn unused_suppression() {
+    assert_eq!(
+        diagnostics("// stellar: allow(next_edition_keyword, style)\nfun async() {}"),
+        ["`style` is allowed, but never reported here"]
+    );
+}
+
+#[test]
+fn disabled_item() {
+    assert!(diagnostics("// stellar: cfg(test)\n// stellar: allow(style)\nfun f() {}").is_empty());
+}
+
+#[test]
+fn unknown_lint() {
+    assert_eq!(
+        diagnostics("// stellar: allow(next_edition_keywords)\nfun main() {}"),
+        ["invalid directive: unknown lint `next_edition_keywords`"]
+    );
+}
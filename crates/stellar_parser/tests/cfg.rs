@@ -0,0 +1,77 @@
+use stellar_diagnostics::Diagnostics;
+use stellar_interner::DUMMY_PATH_ID;
+use stellar_parser::{parse_item_using, CfgOptions, ParseState};
+
+/// Parses an item and returns `true` if it's enabled with given options.
+fn is_enabled(source: &str, cfg_options: CfgOptions) -> bool {
+    let mut diagnostics = Diagnostics::new();
+    let item = parse_item_using(
+        &mut ParseState::new(DUMMY_PATH_ID, source, &mut diagnostics).with_cfg_options(cfg_options),
+    );
+
+    assert!(diagnostics.is_ok(), "{diagnostics:?}");
+
+    item.is_some()
+}
+
+#[test]
+fn feature() {
+    let source = "// stellar: cfg(feature = \"json\")
+fun to_json() {}";
+
+    assert!(is_enabled(
+        source,
+        CfgOptions::new().with_features(["json"])
+    ));
+    assert!(!is_enabled(source, CfgOptions::new()));
+}
+
+#[test]
+fn directive_after_docstring() {
+    let source = "/// Converts the value into JSON.
+// stellar: cfg(not(feature = \"json\"))
+fun to_json() {}";
+
+    assert!(!is_enabled(
+        source,
+        CfgOptions::new().with_features(["json"])
+    ));
+    assert!(is_enabled(source, CfgOptions::new()));
+}
+
+#[test]
+fn combinators() {
+    let source = "// stellar: cfg(all(test, any(feature = \"a\", feature = \"b\")))
+fun helper() {}";
+
+    assert!(is_enabled(
+        source,
+        CfgOptions::new().with_test().with_features(["b"])
+    ));
+    assert!(!is_enabled(source, CfgOptions::new().with_test()));
+    assert!(!is_enabled(source, CfgOptions::new().with_features(["a"])));
+}
+
+#[test]
+fn usual_comments_are_ignored() {
+    assert!(is_enabled(
+        "// cfg(feature = \"json\")\nfun to_json() {}",
+        CfgOptions::new()
+    ));
+}
+
+#[test]
+fn invalid_directive() {
+    let mut diagnostics = Diagnostics::new();
+    let _ = parse_item_using(&mut ParseState::new(
+        DUMMY_PATH_ID,
+        "// stellar: cfg(feature = json)\nfun to_json() {}",
+        &mut diagnostics,
+    ));
+
+    assert_eq!(diagnostics.diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics.diagnostics[0].message,
+        "invalid directive: expected `\"`, found `j`"
+    );
+}
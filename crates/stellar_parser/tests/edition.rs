@@ -0,0 +1,55 @@
+use stellar_ast::token::Edition;
+use stellar_diagnostics::Diagnostics;
+use stellar_interner::DUMMY_PATH_ID;
+use stellar_parser::{parse_expression_using, parse_item_using, ParseState};
+
+/// Parses an item in a given edition and returns messages of reported diagnostics.
+fn diagnostics(source: &str, edition: Edition) -> Vec<String> {
+    let mut diagnostics = Diagnostics::new();
+    let _ = parse_item_using(
+        &mut ParseState::new(DUMMY_PATH_ID, source, &mut diagnostics).with_edition(edition),
+    );
+
+    diagnostics
+        .diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn next_edition_keyword() {
+    assert_eq!(
+        diagnostics("fun async() {}", Edition::Edition2023),
+        ["`async` is a keyword in the 2024 edition"]
+    );
+    assert_eq!(
+        diagnostics("fun async() {}", Edition::Edition2024),
+        ["expected identifier, found `async`"]
+    );
+}
+
+#[test]
+fn wrapped_identifier() {
+    for edition in Edition::all() {
+        assert!(diagnostics("fun `async`() {}", *edition).is_empty());
+    }
+}
+
+#[test]
+fn first_token() {
+    let mut diagnostics = Diagnostics::new();
+    let _ = parse_expression_using(
+        &mut ParseState::new(DUMMY_PATH_ID, "async + 1", &mut diagnostics)
+            .with_edition(Edition::Edition2023),
+    );
+
+    assert_eq!(
+        diagnostics
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>(),
+        ["`async` is a keyword in the 2024 edition"]
+    );
+}
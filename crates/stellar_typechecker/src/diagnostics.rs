@@ -2,12 +2,12 @@ use itertools::Itertools;
 use stellar_ast::{IdentifierAST, ModuleItemKind};
 use stellar_diagnostics::{
     define_diagnostics,
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Diagnostic, Label, Suggestion},
     BuildDiagnostic,
 };
 use stellar_english_commons::pluralize::PluralizeExt;
 use stellar_filesystem::location::Location;
-use stellar_interner::PathId;
+use stellar_interner::{IdentifierId, PathId};
 
 define_diagnostics! {
     /// Diagnostic related to an item defined multiple times error.
@@ -71,7 +71,8 @@ define_diagnostics! {
     diagnostic(error) FailedToResolvePackage(
         self,
         location: Location,
-        package_name: String
+        package_name: String,
+        similar_name: Option<IdentifierId>
     ) {
         code { "E008" }
         message { format!("failed to resolve the package `{}`", self.package_name) }
@@ -81,6 +82,9 @@ define_diagnostics! {
         notes {
             format!("help: add `{}` into the manifest file's [dependencies] section", self.package_name)
         }
+        optional_notes {
+            self.similar_name.map(|name| format!("help: did you mean `{name}`?"))
+        }
     }
 
     /// Diagnostic, that occurs when the compiler tries to resolve a submodule of a particular package/module that doesn't exist.
@@ -89,7 +93,9 @@ define_diagnostics! {
         module_name: String,
         module_name_location: Location,
         item_name: String,
-        item_name_location: Location
+        item_name_location: Location,
+        similar_name: Option<IdentifierId>,
+        suggestions: Vec<Suggestion>
     ) {
         code { "E008" }
         message { format!("failed to resolve the module item `{}`", self.item_name) }
@@ -99,7 +105,10 @@ define_diagnostics! {
                 self.module_name_location => format!("module `{}` doesn't contain the item `{}`", self.module_name, self.item_name)
             }
         }
-
+        optional_notes {
+            self.similar_name.map(|name| format!("help: did you mean `{name}`?"))
+        }
+        suggestions { self.suggestions }
     }
 
     /// Diagnostic, that occurs when the compiler tries to resolve a module's item that is defined as private.
@@ -182,7 +191,8 @@ define_diagnostics! {
     diagnostic(error) FailedToResolveEnumItem(
         self,
         enum_name: IdentifierAST,
-        enum_item_name: IdentifierAST
+        enum_item_name: IdentifierAST,
+        similar_name: Option<IdentifierId>
     ) {
         code { "E008" }
         message { format!("failed to resolve enum item `{}`", self.enum_item_name.id) }
@@ -192,18 +202,80 @@ define_diagnostics! {
                     self.enum_item_name.id, self.enum_name.id)
             }
         }
+        optional_notes {
+            self.similar_name.map(|name| format!("help: did you mean `{name}`?"))
+        }
     }
 
     /// Diagnostic, that occurs when the compiler tries to resolve a name in a module scope.
     diagnostic(error) FailedToResolveName(
         self,
-        name: IdentifierAST
+        name: IdentifierAST,
+        similar_name: Option<IdentifierId>,
+        suggestions: Vec<Suggestion>
     ) {
         code { "E008" }
         message { format!("failed to resolve the name `{}`", self.name.id) }
         labels {
             primary { self.name.location }
         }
+        optional_notes {
+            self.similar_name.map(|name| format!("help: did you mean `{name}`?"))
+        }
+        suggestions { self.suggestions }
+    }
+
+    /// Diagnostic, that occurs when an intra-doc link, e.g. [`Option`], cannot be resolved.
+    diagnostic(warning) UnresolvedDocLink(
+        self,
+        location: Location,
+        path: String,
+        reason: String,
+        help: Option<String>
+    ) {
+        code { "W003" }
+        message { format!("unresolved link to `{}`", self.path) }
+        labels {
+            primary { self.location => self.reason }
+        }
+        optional_notes {
+            self.help
+        }
+    }
+
+    /// Diagnostic, that occurs when the root module of a binary doesn't define
+    /// the `main` function.
+    diagnostic(error) MissingMainFunction(
+        self,
+        binary_name: String,
+        location: Location
+    ) {
+        code { "E018" }
+        message { format!("`main` function not found in binary `{}`", self.binary_name) }
+        labels {
+            primary { self.location => "binary defined here" }
+        }
+        notes {
+            "help: add `pub fun main() {}` to the root module of the binary"
+        }
+    }
+
+    /// Diagnostic, that occurs when the `main` function of a binary has a wrong
+    /// signature, e.g. has parameters.
+    diagnostic(error) InvalidMainFunction(
+        self,
+        binary_name: String,
+        reason: &'static str,
+        location: Location
+    ) {
+        code { "E019" }
+        message { format!("invalid `main` function in binary `{}`", self.binary_name) }
+        labels {
+            primary { self.location => self.reason }
+        }
+        notes {
+            "note: the signature of `main` must be `pub fun main()`"
+        }
     }
 
     diagnostic(error) UnderscoreTypeInSignature(
@@ -0,0 +1,71 @@
+//! Checks, that root modules of binaries define the entry point:
+//!
+//! ```txt
+//! pub fun main() {
+//!     ...
+//! }
+//! ```
+//!
+//! The entry point must be public and cannot have parameters, generic parameters,
+//! `where` predicates and a return type.
+
+use stellar_database::{ModuleId, PackageId, State};
+use stellar_filesystem::location::Location;
+use stellar_fx_hash::FxHashMap;
+use stellar_hir::{ModuleItem, Visibility};
+
+use crate::diagnostics::{InvalidMainFunction, MissingMainFunction};
+
+/// Checks the entry point of a binary with a given name, whose module tree was
+/// allocated as a given package. If the entry point is missing, the diagnostic
+/// points to a given location of the binary in the manifest.
+pub fn check_entry_point(
+    state: &mut State,
+    binary_name: &str,
+    binary_location: Location,
+    package: PackageId,
+    modules: &FxHashMap<ModuleId, stellar_hir::Module>,
+) {
+    let Some(module) = modules.get(&package.root_module(state.db())) else {
+        return;
+    };
+
+    let main = module.items.iter().find_map(|item| match item {
+        ModuleItem::Function(function) if function.signature.name.id.as_str() == "main" => {
+            Some(&function.signature)
+        }
+        _ => None,
+    });
+
+    let Some(main) = main else {
+        state
+            .diagnostics_mut()
+            .add_diagnostic(MissingMainFunction::new(
+                binary_name.to_owned(),
+                binary_location,
+            ));
+        return;
+    };
+
+    let reason = if main.visibility == Visibility::Private {
+        Some("`main` must be public")
+    } else if !main.generic_parameters.is_empty() || !main.where_predicates.is_empty() {
+        Some("`main` cannot have generic parameters")
+    } else if !main.parameters.is_empty() {
+        Some("`main` cannot have parameters")
+    } else if main.return_type.is_some() {
+        Some("`main` cannot return a value")
+    } else {
+        None
+    };
+
+    if let Some(reason) = reason {
+        state
+            .diagnostics_mut()
+            .add_diagnostic(InvalidMainFunction::new(
+                binary_name.to_owned(),
+                reason,
+                main.name.location,
+            ));
+    }
+}
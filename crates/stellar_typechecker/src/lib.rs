@@ -1,5 +1,6 @@
 #![allow(warnings)]
 
 mod diagnostics;
+pub mod entry_point;
 pub mod resolution;
 pub mod signature_analysis;
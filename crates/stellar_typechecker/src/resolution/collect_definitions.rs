@@ -4,8 +4,9 @@ use std::time::Instant;
 use stellar_ast::IdentifierAST;
 use stellar_ast_lowering::LoweredModule;
 use stellar_database::{
-    EnumData, EnumId, EnumItemData, FunctionData, InterfaceData, ModuleId, PackageId,
-    SignatureData, State, StructData, Symbol, TupleLikeStructData, TypeAliasData, TypeAliasId,
+    EnumData, EnumId, EnumItemData, FunctionData, ImportInsertionPoint, InterfaceData, ModuleId,
+    PackageId, SignatureData, State, StructData, Symbol, TupleLikeStructData, TypeAliasData,
+    TypeAliasId,
 };
 use stellar_fx_hash::FxHashMap;
 #[cfg(feature = "debug")]
@@ -35,6 +36,8 @@ impl<'s> CollectDefinitions<'s> {
         #[cfg(feature = "debug")]
         let now = Instant::now();
 
+        self.collect_import_insertion_point(module);
+
         for item in &module.items {
             match item {
                 stellar_hir::ModuleItem::Enum(enum_) => self.collect_definition_of_enum(enum_),
@@ -65,6 +68,29 @@ impl<'s> CollectDefinitions<'s> {
         );
     }
 
+    /// Remembers where imports suggested for the module are inserted: after
+    /// the last import, or after the module docstring.
+    fn collect_import_insertion_point(&mut self, module: &stellar_hir::Module) {
+        let last_import = module.items.iter().rev().find_map(|item| match item {
+            stellar_hir::ModuleItem::Import { location, .. } => Some(location.end),
+            _ => None,
+        });
+        let docstring_end = module
+            .docstring
+            .as_ref()
+            .and_then(|docstring| docstring.lines.last())
+            .map(|line| line.end);
+
+        let point = match (last_import, docstring_end) {
+            (Some(end), _) => ImportInsertionPoint::AfterImport(end),
+            (None, Some(end)) => ImportInsertionPoint::AfterDocstring(end),
+            (None, None) => ImportInsertionPoint::Start,
+        };
+
+        self.module
+            .set_import_insertion_point(self.state.db_mut(), point);
+    }
+
     fn collect_definition_of_enum(&mut self, enum_hir: &stellar_hir::Enum) {
         #[cfg(feature = "debug")]
         let now = Instant::now();
@@ -0,0 +1,149 @@
+//! Collects `import` candidates for names that failed to resolve.
+//!
+//! When a name cannot be found in a module, the fix is nearly always a missing import.
+//! The candidates are public module items, enum items and submodules with the same name
+//! in the current package and in all of its dependencies.
+
+use stellar_database::{Database, ImportInsertionPoint, ModuleId, PackageId, Path, Symbol};
+use stellar_diagnostics::diagnostic::{Suggestion, TextEdit};
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::IdentifierId;
+
+use super::is_public;
+
+/// Returns suggestions that add an `import` of a symbol with a given name
+/// to a given module.
+///
+/// Imports are inserted after the last import of the module, or after the module
+/// docstring, if there are no imports. Suggestions are sorted by the import path,
+/// so that diagnostics are deterministic.
+pub(crate) fn import_suggestions(
+    db: &Database,
+    module: ModuleId,
+    name: IdentifierId,
+) -> Vec<Suggestion> {
+    let mut paths = import_candidates(db, module, name)
+        .into_iter()
+        .map(|path| path.to_string())
+        .collect::<Vec<_>>();
+
+    paths.sort();
+    paths.dedup();
+
+    let (offset, prefix, suffix) = match module.import_insertion_point(db) {
+        ImportInsertionPoint::Start => (ByteOffset(0), "", "\n"),
+        ImportInsertionPoint::AfterDocstring(end) => (end, "\n\n", ""),
+        ImportInsertionPoint::AfterImport(end) => (end, "\n", ""),
+    };
+    let insertion_point = Location {
+        filepath: module.filepath(db),
+        start: offset,
+        end: offset,
+    };
+
+    paths
+        .into_iter()
+        .map(|path| {
+            Suggestion::new(format!("consider importing `{path}`")).with_edit(TextEdit::new(
+                insertion_point,
+                format!("{prefix}import {path};{suffix}"),
+            ))
+        })
+        .collect()
+}
+
+/// Returns import paths of all public symbols with a given name, that are visible
+/// from a given module.
+pub(crate) fn import_candidates(db: &Database, module: ModuleId, name: IdentifierId) -> Vec<Path> {
+    let package = module.package();
+    let mut candidates = Vec::new();
+
+    collect_candidates_in_package(db, module, package, None, name, &mut candidates);
+
+    if let Some(dependencies) = package.dependencies_or_none(db) {
+        for (&alias, &dependency) in dependencies {
+            collect_candidates_in_package(
+                db,
+                module,
+                dependency,
+                Some(alias),
+                name,
+                &mut candidates,
+            );
+        }
+    }
+
+    candidates
+}
+
+fn collect_candidates_in_package(
+    db: &Database,
+    current_module: ModuleId,
+    package: PackageId,
+    alias: Option<IdentifierId>,
+    name: IdentifierId,
+    candidates: &mut Vec<Path>,
+) {
+    if db.package_or_none(package).is_none() {
+        return;
+    }
+
+    for module in package.modules(db) {
+        if module == current_module {
+            continue;
+        }
+
+        for symbol in symbols_with_name_in_module(db, module, name) {
+            candidates.push(import_path(db, symbol, alias));
+        }
+    }
+}
+
+/// Returns all importable symbols with a given name, that are defined directly in a module
+/// or in one of the public enums of the module.
+fn symbols_with_name_in_module(db: &Database, module: ModuleId, name: IdentifierId) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+
+    if let Some(submodule) = module.submodule(db, name) {
+        symbols.push(Symbol::Module(submodule));
+    }
+
+    if let Some(symbol) = module.module_item_symbol_or_none(db, name) {
+        if is_public(db, symbol) {
+            symbols.push(symbol);
+        }
+    }
+
+    for symbol in module.module_item_symbols(db).values() {
+        let Some(enum_) = symbol.to_enum_or_none() else {
+            continue;
+        };
+
+        if !is_public(db, *symbol) {
+            continue;
+        }
+
+        if let Some(item) = enum_.item(db, name) {
+            symbols.push(Symbol::EnumItem(item));
+        }
+    }
+
+    symbols
+}
+
+/// Returns the path of the symbol as it should be written in an `import`.
+///
+/// Symbols from dependencies are imported using the name of the dependency in
+/// the manifest, which might differ from the name of the package itself.
+fn import_path(db: &Database, symbol: Symbol, alias: Option<IdentifierId>) -> Path {
+    let path = symbol.path(db);
+
+    match alias {
+        Some(alias) => Path::new(
+            std::iter::once(alias)
+                .chain(path.segments().iter().skip(1).copied())
+                .collect(),
+        ),
+        None => path,
+    }
+}
@@ -1,15 +1,23 @@
 pub mod collect_definitions;
+mod import_suggestions;
+pub mod resolve_doc_links;
 pub mod resolve_imports;
+mod similar_names;
 
 use std::iter;
 
 use itertools::Itertools;
-use stellar_ast::IdentifierAST;
-use stellar_database::{EnumId, ModuleId, PackageId, State, Symbol, TypeAliasId};
+use stellar_ast::{IdentifierAST, Visibility};
+use stellar_database::{Database, EnumId, ModuleId, PackageId, State, Symbol, TypeAliasId};
 
+use self::import_suggestions::import_suggestions;
+use self::similar_names::{
+    similar_enum_item_name, similar_name_in_module, similar_name_in_scope, similar_package_name,
+};
 use crate::diagnostics::{
-    EnumItemsDoNotServeAsNamespaces, FailedToResolveEnumItem, FailedToResolveNameInModule,
-    FailedToResolvePackage, ModuleItemsExceptEnumsDoNotServeAsNamespaces,
+    EnumItemsDoNotServeAsNamespaces, FailedToResolveEnumItem, FailedToResolveName,
+    FailedToResolveNameInModule, FailedToResolvePackage,
+    ModuleItemsExceptEnumsDoNotServeAsNamespaces,
 };
 
 pub(crate) fn resolve_global_path_in_module_context(
@@ -21,24 +29,29 @@ pub(crate) fn resolve_global_path_in_module_context(
     let namespace = identifiers.next()?;
 
     let Some(namespace_symbol) = module.symbol_or_none(state.db(), namespace.id) else {
+        let similar_name = similar_name_in_scope(state.db(), module, namespace.id);
+        let suggestions = import_suggestions(state.db(), module, namespace.id);
+
         state
             .diagnostics_mut()
-            .add_diagnostic(FailedToResolvePackage::new(
-                namespace.location,
-                namespace.id,
+            .add_diagnostic(FailedToResolveName::new(
+                *namespace,
+                similar_name,
+                suggestions,
             ));
 
         return None;
     };
 
-    resolve_global_path_by_first_symbol(state, namespace_symbol, namespace, identifiers)
+    resolve_global_path_by_first_symbol(state, module, namespace_symbol, namespace, identifiers)
 }
 
 pub(crate) fn resolve_global_path(
     state: &mut State,
-    package: PackageId,
+    context: ModuleId,
     path: &stellar_ast::ImportPath,
 ) -> Option<Symbol> {
+    let package = context.package();
     let mut identifiers = path.path.identifiers.iter();
     let namespace = identifiers.next()?;
 
@@ -47,11 +60,14 @@ pub(crate) fn resolve_global_path(
     } else {
         package.dependencies(state.db()).get(&namespace.id).copied()
     }) else {
+        let similar_name = similar_package_name(state.db(), package, namespace.id);
+
         state
             .diagnostics_mut()
             .add_diagnostic(FailedToResolvePackage::new(
                 namespace.location,
                 namespace.id,
+                similar_name,
             ));
 
         return None;
@@ -59,11 +75,18 @@ pub(crate) fn resolve_global_path(
 
     let root_module = package.root_module(state.db());
 
-    resolve_global_path_by_first_symbol(state, Symbol::Module(root_module), namespace, identifiers)
+    resolve_global_path_by_first_symbol(
+        state,
+        context,
+        Symbol::Module(root_module),
+        namespace,
+        identifiers,
+    )
 }
 
 fn resolve_global_path_by_first_symbol<'a>(
     state: &mut State,
+    context: ModuleId,
     symbol: Symbol,
     namespace: &'a IdentifierAST,
     identifiers: impl Iterator<Item = &'a IdentifierAST>,
@@ -72,19 +95,20 @@ fn resolve_global_path_by_first_symbol<'a>(
         .chain(identifiers)
         .tuple_windows()
         .try_fold(symbol, |symbol, (namespace, member)| {
-            resolve_global_path_segment(state, symbol, *namespace, *member)
+            resolve_global_path_segment(state, context, symbol, *namespace, *member)
         })
 }
 
 fn resolve_global_path_segment(
     state: &mut State,
+    context: ModuleId,
     symbol: Symbol,
     namespace: IdentifierAST,
     member: IdentifierAST,
 ) -> Option<Symbol> {
     match symbol {
         Symbol::Module(module) => {
-            resolve_symbol_in_module_namespace(state, module, namespace, member)
+            resolve_symbol_in_module_namespace(state, context, module, namespace, member)
         }
         Symbol::Enum(enum_) => resolve_symbol_in_enum_namespace(state, enum_, namespace, member),
         Symbol::EnumItem(_) => {
@@ -110,6 +134,7 @@ fn resolve_global_path_segment(
 
 fn resolve_symbol_in_module_namespace(
     state: &mut State,
+    context: ModuleId,
     module: ModuleId,
     namespace: IdentifierAST,
     member: IdentifierAST,
@@ -121,13 +146,18 @@ fn resolve_symbol_in_module_namespace(
     {
         Some(symbol)
     } else {
+        let similar_name = similar_name_in_module(state.db(), module, member.id);
+        let suggestions = import_suggestions(state.db(), context, member.id);
+
         state
             .diagnostics_mut()
             .add_diagnostic(FailedToResolveNameInModule::new(
-                member.id,
-                member.location,
                 namespace.id,
                 namespace.location,
+                member.id,
+                member.location,
+                similar_name,
+                suggestions,
             ));
 
         None
@@ -143,10 +173,20 @@ fn resolve_symbol_in_enum_namespace(
     if let Some(symbol) = enum_.item(state.db(), member.id) {
         Some(Symbol::EnumItem(symbol))
     } else {
+        let similar_name = similar_enum_item_name(state.db(), enum_, member.id);
+
         state
             .diagnostics_mut()
-            .add_diagnostic(FailedToResolveEnumItem::new(namespace, member));
+            .add_diagnostic(FailedToResolveEnumItem::new(
+                namespace,
+                member,
+                similar_name,
+            ));
 
         None
     }
 }
+
+fn is_public(db: &Database, symbol: Symbol) -> bool {
+    matches!(symbol.signature(db).visibility(db), Visibility::Public(..))
+}
@@ -0,0 +1,288 @@
+//! Resolves intra-doc links, e.g. [`Option`] or [`std.result.Result`], in docstrings.
+//!
+//! Links are resolved in the context of the module, in which the docstring is located.
+//! Resolved links are stored in the module data (see [`ModuleId::doc_links`]), broken
+//! links are reported as warnings.
+
+use std::{mem, ops::Range};
+
+use stellar_ast::{Docstring, IdentifierAST, ImportPath};
+use stellar_database::{DocLink, ModuleId, State, Symbol};
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::IdentifierId;
+
+use super::{
+    resolve_global_path, resolve_global_path_by_first_symbol, resolve_global_path_in_module_context,
+};
+use crate::diagnostics::UnresolvedDocLink;
+
+pub struct ResolveDocLinks<'s> {
+    state: &'s mut State,
+    module: ModuleId,
+}
+
+impl<'s> ResolveDocLinks<'s> {
+    pub fn run_all(state: &'s mut State, modules: &FxHashMap<ModuleId, stellar_hir::Module>) {
+        for module in modules {
+            ResolveDocLinks {
+                state,
+                module: *module.0,
+            }
+            .run(module.1);
+        }
+    }
+
+    fn run(mut self, module: &stellar_hir::Module) {
+        self.resolve_docstring(module.docstring.as_ref());
+
+        for item in &module.items {
+            match item {
+                stellar_hir::ModuleItem::Enum(enum_) => {
+                    self.resolve_docstring(enum_.docstring.as_ref());
+
+                    for item in &enum_.items {
+                        self.resolve_enum_item(item);
+                    }
+
+                    self.resolve_methods(&enum_.methods);
+                }
+                stellar_hir::ModuleItem::Function(function) => {
+                    self.resolve_docstring(function.signature.docstring.as_ref());
+                }
+                stellar_hir::ModuleItem::Interface(interface) => {
+                    self.resolve_docstring(interface.docstring.as_ref());
+                    self.resolve_methods(&interface.methods);
+                }
+                stellar_hir::ModuleItem::Struct(struct_) => {
+                    self.resolve_docstring(struct_.docstring.as_ref());
+                    self.resolve_fields(&struct_.fields);
+                    self.resolve_methods(&struct_.methods);
+                }
+                stellar_hir::ModuleItem::TupleLikeStruct(struct_) => {
+                    self.resolve_docstring(struct_.docstring.as_ref());
+                    self.resolve_methods(&struct_.methods);
+                }
+                stellar_hir::ModuleItem::TypeAlias(alias) => {
+                    self.resolve_docstring(alias.docstring.as_ref());
+                }
+                stellar_hir::ModuleItem::Import { .. } => {}
+            }
+        }
+    }
+
+    fn resolve_enum_item(&mut self, item: &stellar_hir::EnumItem) {
+        match item {
+            stellar_hir::EnumItem::Just { docstring, .. }
+            | stellar_hir::EnumItem::TupleLike { docstring, .. } => {
+                self.resolve_docstring(docstring.as_ref());
+            }
+            stellar_hir::EnumItem::Struct {
+                docstring, fields, ..
+            } => {
+                self.resolve_docstring(docstring.as_ref());
+                self.resolve_fields(fields);
+            }
+        }
+    }
+
+    fn resolve_fields(&mut self, fields: &[stellar_hir::StructField]) {
+        for field in fields {
+            self.resolve_docstring(field.docstring.as_ref());
+        }
+    }
+
+    fn resolve_methods(&mut self, methods: &[stellar_hir::Function]) {
+        for method in methods {
+            self.resolve_docstring(method.signature.docstring.as_ref());
+        }
+    }
+
+    fn resolve_docstring(&mut self, docstring: Option<&Docstring>) {
+        let Some(docstring) = docstring else {
+            return;
+        };
+
+        for range in link_references(docstring.as_str()) {
+            self.resolve_link(docstring, range);
+        }
+    }
+
+    fn resolve_link(&mut self, docstring: &Docstring, range: Range<usize>) {
+        let text = &docstring.as_str()[range.clone()];
+
+        // Link references never span over multiple lines, so the locations always exist.
+        let Some(location) = docstring.location_of(range.clone()) else {
+            return;
+        };
+
+        let mut offset = range.start;
+        let identifiers = text
+            .split('.')
+            .map(|segment| {
+                let identifier = IdentifierAST {
+                    location: docstring
+                        .location_of(offset..offset + segment.len())
+                        .unwrap_or(location),
+                    id: IdentifierId::from(segment),
+                };
+
+                offset += segment.len() + 1;
+                identifier
+            })
+            .collect::<Vec<_>>();
+
+        let path = stellar_ast::Path {
+            location,
+            identifiers,
+        };
+
+        // Diagnostics emitted by the name resolution are errors, but broken links
+        // are reported as warnings, so they are collected separately.
+        let diagnostics = mem::take(self.state.diagnostics_mut());
+        let symbol = self.resolve_path(&path);
+        let failures = mem::replace(self.state.diagnostics_mut(), diagnostics);
+
+        if let Some(target) = symbol {
+            self.module.add_doc_link(
+                self.state.db_mut(),
+                DocLink {
+                    location,
+                    path: text.to_owned(),
+                    target,
+                },
+            );
+        } else {
+            let failure = failures.diagnostics.into_iter().next();
+
+            self.state
+                .diagnostics_mut()
+                .add_diagnostic(UnresolvedDocLink::new(
+                    location,
+                    text,
+                    failure.as_ref().map_or_else(
+                        || "cannot resolve the link".to_owned(),
+                        |failure| failure.message.clone(),
+                    ),
+                    failure.and_then(|failure| failure.notes.into_iter().next()),
+                ));
+        }
+    }
+
+    /// Resolves a link path: either relative to the module, e.g. `Option` (including
+    /// imported names), or starting with a package name, e.g. `std.option.Option`.
+    fn resolve_path(&mut self, path: &stellar_ast::Path) -> Option<Symbol> {
+        let (first, rest) = path.identifiers.split_first()?;
+        let package = self.module.package();
+
+        if self
+            .module
+            .symbol_or_none(self.state.db(), first.id)
+            .is_some()
+        {
+            return resolve_global_path_in_module_context(self.state, path, self.module);
+        }
+
+        if let Some(symbol) = self
+            .module
+            .resolved_imports(self.state.db())
+            .get(&first.id)
+            .copied()
+        {
+            return resolve_global_path_by_first_symbol(
+                self.state,
+                self.module,
+                symbol,
+                first,
+                rest.iter(),
+            );
+        }
+
+        if first.id == package.name(self.state.db())
+            || package
+                .dependencies(self.state.db())
+                .contains_key(&first.id)
+        {
+            resolve_global_path(
+                self.state,
+                self.module,
+                &ImportPath {
+                    path: path.clone(),
+                    as_: None,
+                },
+            )
+        } else {
+            resolve_global_path_in_module_context(self.state, path, self.module)
+        }
+    }
+}
+
+/// Returns byte ranges of paths in intra-doc links, e.g. `Option` in [`Option`].
+///
+/// Fenced code blocks, inline links, e.g. [`Option`](https://...), and reference
+/// links are skipped.
+fn link_references(text: &str) -> Vec<Range<usize>> {
+    let mut references = vec![];
+    let mut in_code_block = false;
+    let mut line_start = 0;
+
+    for line in text.split('\n') {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            let mut cursor = 0;
+
+            while let Some(open) = line[cursor..].find("[`") {
+                let start = cursor + open + 2;
+
+                let Some(close) = line[start..].find("`]") else {
+                    break;
+                };
+
+                let end = start + close;
+                cursor = end + 2;
+
+                if is_path(&line[start..end])
+                    && !matches!(line[cursor..].chars().next(), Some('(' | '[' | ':'))
+                {
+                    references.push(line_start + start..line_start + end);
+                }
+            }
+        }
+
+        line_start += line.len() + 1;
+    }
+
+    references
+}
+
+fn is_path(text: &str) -> bool {
+    text.split('.').all(|segment| {
+        let mut chars = segment.chars();
+
+        chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resolution::resolve_doc_links::link_references;
+
+    #[test]
+    fn test_link_references() {
+        let text = "See [`Option`] and [`std.result.Result`].\n\
+                    [`a b`], [`c`](https://example.com), [`d`][e], [`e`]: f\n\
+                    ```\n\
+                    [`g`]\n\
+                    ```\n\
+                    [`h`]";
+
+        assert_eq!(
+            link_references(text)
+                .into_iter()
+                .map(|range| &text[range])
+                .collect::<Vec<_>>(),
+            vec!["Option", "std.result.Result", "h"]
+        );
+    }
+}
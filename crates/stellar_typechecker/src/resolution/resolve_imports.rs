@@ -39,7 +39,7 @@ impl<'s> ResolveImports<'s> {
         #[cfg(feature = "debug")]
         let now = Instant::now();
 
-        let Some(symbol) = resolve_global_path(self.state, self.module.package(), path) else {
+        let Some(symbol) = resolve_global_path(self.state, self.module, path) else {
             return;
         };
 
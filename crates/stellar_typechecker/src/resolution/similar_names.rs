@@ -0,0 +1,90 @@
+//! Looks for names similar to the ones that failed to resolve, to give "did you mean" hints.
+//!
+//! Private module items are only suggested when there are no similar public ones,
+//! because pointing to an item that cannot be used anyway is rarely helpful.
+
+use stellar_database::{Database, EnumId, ModuleId, PackageId};
+use stellar_english_commons::similarity::find_similar;
+use stellar_interner::IdentifierId;
+
+use super::is_public;
+
+/// Returns a name of a submodule or a module item, that is similar to a given one.
+pub(crate) fn similar_name_in_module(
+    db: &Database,
+    module: ModuleId,
+    name: IdentifierId,
+) -> Option<IdentifierId> {
+    let mut public = module.submodules(db).keys().copied().collect::<Vec<_>>();
+    let mut private = Vec::new();
+
+    for (&item_name, &symbol) in module.module_item_symbols(db) {
+        if is_public(db, symbol) {
+            public.push(item_name);
+        } else {
+            private.push(item_name);
+        }
+    }
+
+    find_similar_name(name, public).or_else(|| find_similar_name(name, private))
+}
+
+/// Returns a name, that can be used in a given module without a path and is
+/// similar to a given one: a submodule, a module item, an imported name, the
+/// package itself or one of its dependencies.
+pub(crate) fn similar_name_in_scope(
+    db: &Database,
+    module: ModuleId,
+    name: IdentifierId,
+) -> Option<IdentifierId> {
+    let package = module.package();
+
+    find_similar_name(
+        name,
+        module
+            .submodules(db)
+            .keys()
+            .chain(module.module_item_symbols(db).keys())
+            .chain(module.resolved_imports(db).keys())
+            .copied()
+            .chain(std::iter::once(package.name(db)))
+            .chain(package.dependencies(db).keys().copied()),
+    )
+}
+
+/// Returns a name of an enum item, that is similar to a given one.
+pub(crate) fn similar_enum_item_name(
+    db: &Database,
+    enum_: EnumId,
+    name: IdentifierId,
+) -> Option<IdentifierId> {
+    find_similar_name(name, enum_.items(db).keys().copied())
+}
+
+/// Returns a name of the package itself or one of its dependencies, that is similar
+/// to a given one.
+pub(crate) fn similar_package_name(
+    db: &Database,
+    package: PackageId,
+    name: IdentifierId,
+) -> Option<IdentifierId> {
+    find_similar_name(
+        name,
+        std::iter::once(package.name(db)).chain(package.dependencies(db).keys().copied()),
+    )
+}
+
+fn find_similar_name(
+    name: IdentifierId,
+    candidates: impl IntoIterator<Item = IdentifierId>,
+) -> Option<IdentifierId> {
+    // Sorting makes the result independent of the hash map iteration order,
+    // when there are multiple candidates with the same distance.
+    let mut candidates = candidates
+        .into_iter()
+        .map(IdentifierId::as_str)
+        .collect::<Vec<_>>();
+    candidates.sort_unstable();
+
+    find_similar(name.as_str(), candidates).map(IdentifierId::from)
+}
@@ -0,0 +1,81 @@
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{PackageData, State};
+use stellar_diagnostics::diagnostic::Diagnostic;
+use stellar_filesystem::location::{ByteOffset, Location};
+use stellar_interner::{IdentifierId, PathId};
+use stellar_parser::parse_module;
+use stellar_typechecker::entry_point::check_entry_point;
+
+/// Returns the location of the `[[bin]]` entry, that diagnostics of a missing
+/// entry point should point to.
+fn binary_location() -> Location {
+    Location {
+        filepath: PathId::from("app/package.toml"),
+        start: ByteOffset(60),
+        end: ByteOffset(65),
+    }
+}
+
+/// Checks the entry point of a binary with a given root module and returns
+/// all diagnostics.
+fn check(source_code: &str) -> Vec<Diagnostic> {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(
+        state.db_mut(),
+        IdentifierId::from("app"),
+        PathId::from("app"),
+    );
+    let parse_result = parse_module(
+        &mut state,
+        package,
+        IdentifierId::from("app").into(),
+        PathId::from("app/src/bin/app.sr"),
+        source_code,
+    );
+    package.set_root_module(state.db_mut(), parse_result.module());
+
+    let hir = LowerToHir::run_all(vec![parse_result]);
+    check_entry_point(&mut state, "app", binary_location(), package, &hir);
+
+    state.diagnostics().diagnostics.clone()
+}
+
+#[test]
+fn valid_main() {
+    assert!(check("pub fun main() {}").is_empty());
+}
+
+#[test]
+fn missing_main() {
+    let diagnostics = check("pub fun run() {}");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("E018"));
+    assert_eq!(diagnostics[0].labels[0].location, binary_location());
+}
+
+#[test]
+fn invalid_main() {
+    for (source_code, reason) in [
+        ("fun main() {}", "`main` must be public"),
+        (
+            "pub fun main[T]() {}",
+            "`main` cannot have generic parameters",
+        ),
+        (
+            "pub fun main(argc: int32) {}",
+            "`main` cannot have parameters",
+        ),
+        (
+            "pub fun main(): int32 { 0 }",
+            "`main` cannot return a value",
+        ),
+    ] {
+        let diagnostics = check(source_code);
+
+        assert_eq!(diagnostics.len(), 1, "{source_code}");
+        assert_eq!(diagnostics[0].code.as_deref(), Some("E019"));
+        assert_eq!(diagnostics[0].labels[0].message, reason);
+    }
+}
@@ -1,2 +1,3 @@
+mod entry_point;
 mod resolution;
 mod signature_analysis;
@@ -1,6 +1,6 @@
 use stellar_ast_lowering::LowerToHir;
 use stellar_database::{PackageData, State};
-use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID};
 use stellar_parser::parse_module;
 use stellar_typechecker::resolution::collect_definitions::CollectDefinitions;
 
@@ -10,18 +10,18 @@ fn test_enum() {
     let filepath = PathId::from("test.sr");
     let source_code = "enum A {}\nenum B {}";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     let module = parse_result.module();
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
@@ -36,17 +36,17 @@ fn test_duplicate_definition() {
     let filepath = PathId::from("test.sr");
     let source_code = "enum A {}\nenum A {}";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
@@ -59,18 +59,18 @@ fn test_enum_items() {
     let filepath = PathId::from("test.sr");
     let source_code = "enum A { A, B, C }";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     let module = parse_result.module();
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
@@ -92,17 +92,17 @@ fn duplicate_enum_item_definitions() {
     let filepath = PathId::from("test.sr");
     let source_code = "enum A { A, A }";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
@@ -115,18 +115,18 @@ fn test_function() {
     let filepath = PathId::from("test.sr");
     let source_code = "fun a() {}";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     let module = parse_result.module();
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
@@ -142,18 +142,18 @@ fn test_struct() {
     let filepath = PathId::from("test.sr");
     let source_code = "struct A {}";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     let module = parse_result.module();
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
@@ -169,18 +169,18 @@ fn test_interface() {
     let filepath = PathId::from("test.sr");
     let source_code = "interface A {}";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     let module = parse_result.module();
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
@@ -196,18 +196,18 @@ fn test_type_alias() {
     let filepath = PathId::from("test.sr");
     let source_code = "type A = int8;";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         filepath,
         source_code,
     );
     let module = parse_result.module();
     package.set_root_module(state.db_mut(), parse_result.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
 
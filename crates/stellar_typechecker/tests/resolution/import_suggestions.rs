@@ -0,0 +1,38 @@
+use stellar_filesystem::location::ByteOffset;
+
+use super::resolve;
+
+#[test]
+fn suggest_import_for_unresolved_module_item() {
+    let (state, _) = resolve(
+        "//! The root module.\n\nimport a.c.Foo;\n\nfun main() {}",
+        &[("b", "pub struct Foo {}"), ("c", "")],
+    );
+
+    let diagnostic = state
+        .diagnostics()
+        .diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.code.as_deref() == Some("E008"))
+        .expect("expected unresolved name diagnostic");
+    let actions = diagnostic.code_actions();
+
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].title, "consider importing `a.b.Foo`");
+    assert_eq!(actions[0].edits[0].replacement, "\nimport a.b.Foo;");
+
+    // The import is inserted after the existing one, not above the docstring.
+    assert_eq!(actions[0].edits[0].location.start, ByteOffset(37));
+}
+
+#[test]
+fn do_not_suggest_private_items() {
+    let (state, _) = resolve("import a.Foo;", &[("b", "struct Foo {}")]);
+
+    assert!(state.diagnostics().is_fatal());
+    assert!(state
+        .diagnostics()
+        .diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.suggestions.is_empty()));
+}
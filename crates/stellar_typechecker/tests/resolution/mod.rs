@@ -1,2 +1,64 @@
 mod collect_definitions;
+mod import_suggestions;
+mod resolve_doc_links;
 mod resolve_imports;
+mod similar_names;
+
+use stellar_ast_lowering::LowerToHir;
+use stellar_database::{ModuleId, PackageData, Path, State};
+use stellar_fx_hash::FxHashMap;
+use stellar_interner::{IdentifierId, PathId};
+use stellar_parser::parse_module;
+use stellar_typechecker::resolution::{
+    collect_definitions::CollectDefinitions, resolve_imports::ResolveImports,
+};
+
+fn path(segments: &[&str]) -> Path {
+    Path::new(segments.iter().map(|&s| IdentifierId::from(s)).collect())
+}
+
+/// Collects definitions and resolves imports in the package `a`, that consists of
+/// a root module and its submodules, given by names and source code, e.g.
+/// `resolve("import a.b.Foo;", &[("b", "pub struct Foo {}")])`.
+fn resolve(
+    root_source: &str,
+    submodules: &[(&str, &str)],
+) -> (State, FxHashMap<ModuleId, stellar_hir::Module>) {
+    let mut state = State::new();
+
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
+    let root = parse_module(
+        &mut state,
+        package,
+        path(&["a"]),
+        PathId::from("a/package.sr"),
+        root_source,
+    );
+
+    package.set_root_module(state.db_mut(), root.module());
+
+    let mut modules = vec![];
+
+    for (name, source) in submodules {
+        let submodule = parse_module(
+            &mut state,
+            package,
+            path(&["a", name]),
+            PathId::from(format!("a/{name}.sr")),
+            source,
+        );
+
+        root.module()
+            .add_submodule(state.db_mut(), submodule.module());
+        modules.push(submodule);
+    }
+
+    modules.insert(0, root);
+
+    let hir = LowerToHir::run_all(modules);
+
+    CollectDefinitions::run_all(&mut state, &hir);
+    ResolveImports::run_all(&mut state, &hir);
+
+    (state, hir)
+}
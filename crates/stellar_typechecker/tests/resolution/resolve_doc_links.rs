@@ -0,0 +1,96 @@
+use stellar_database::State;
+use stellar_diagnostics::diagnostic::Severity;
+use stellar_filesystem::location::ByteOffset;
+use stellar_typechecker::resolution::resolve_doc_links::ResolveDocLinks;
+
+use super::resolve;
+
+/// Resolves doc links in the root module `a`, that has a single submodule `b`,
+/// and returns the state.
+fn resolve_doc_links(submodule_source: &str, root_source: &str) -> State {
+    let (mut state, hir) = resolve(root_source, &[("b", submodule_source)]);
+
+    ResolveDocLinks::run_all(&mut state, &hir);
+
+    state
+}
+
+#[test]
+fn resolved_links() {
+    let source = "import a.b.Bar;\n\
+                  /// Returns [`Foo`], [`Bar`] or [`a.b.Bar`].\n\
+                  pub fun foo() {}\n\
+                  pub struct Foo {}";
+    let state = resolve_doc_links("pub struct Bar {}", source);
+
+    assert!(state.diagnostics().is_ok());
+
+    let root = state
+        .db()
+        .packages()
+        .next()
+        .unwrap()
+        .root_module(state.db());
+    let links = root
+        .doc_links(state.db())
+        .iter()
+        .map(|link| {
+            (
+                link.path.as_str(),
+                link.location.start,
+                link.target.path(state.db()).to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        links,
+        vec![
+            (
+                "Foo",
+                ByteOffset(source.find("Foo`").unwrap()),
+                "a.Foo".to_owned()
+            ),
+            (
+                "Bar",
+                ByteOffset(source.find("Bar`").unwrap()),
+                "a.b.Bar".to_owned()
+            ),
+            (
+                "a.b.Bar",
+                ByteOffset(source.find("a.b.Bar`").unwrap()),
+                "a.b.Bar".to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn broken_link() {
+    let source = "/// See [`Optoin`].\npub struct Option {}";
+    let state = resolve_doc_links("", source);
+
+    assert!(state.diagnostics().is_ok());
+
+    let diagnostic = &state.diagnostics().diagnostics[0];
+    let start = source.find("Optoin").unwrap();
+
+    assert_eq!(diagnostic.severity, Severity::Warning);
+    assert_eq!(diagnostic.code.as_deref(), Some("W003"));
+    assert_eq!(diagnostic.labels[0].location.start, ByteOffset(start));
+    assert_eq!(diagnostic.labels[0].location.end, ByteOffset(start + 6));
+    assert_eq!(diagnostic.notes, vec!["help: did you mean `Option`?"]);
+}
+
+#[test]
+fn broken_link_to_imported_name() {
+    let state = resolve_doc_links(
+        "pub struct Bar {}",
+        "import a.b.Bar;\n/// See [`Baz`].\npub struct Foo {}",
+    );
+
+    assert_eq!(
+        state.diagnostics().diagnostics[0].notes,
+        vec!["help: did you mean `Bar`?"]
+    );
+}
@@ -1,6 +1,6 @@
 use stellar_ast_lowering::LowerToHir;
 use stellar_database::{PackageData, State};
-use stellar_interner::{IdentifierId, PathId, DUMMY_PATH_ID};
+use stellar_interner::{IdentifierId, PathId};
 use stellar_parser::parse_module;
 use stellar_typechecker::{
     resolution::collect_definitions::CollectDefinitions,
@@ -11,18 +11,18 @@ use stellar_typechecker::{
 fn resolve_submodule_import_ok() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b;",
     );
@@ -32,7 +32,7 @@ fn resolve_submodule_import_ok() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     ResolveImports::run_all(&mut state, &hir);
 
@@ -43,18 +43,18 @@ fn resolve_submodule_import_ok() {
 fn resolve_submodule_import_err() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.c;",
     );
@@ -63,7 +63,7 @@ fn resolve_submodule_import_err() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     ResolveImports::run_all(&mut state, &hir);
 
@@ -74,18 +74,18 @@ fn resolve_submodule_import_err() {
 fn resolve_module_item_ok() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.foo;",
     );
@@ -94,7 +94,7 @@ fn resolve_module_item_ok() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
@@ -106,18 +106,18 @@ fn resolve_module_item_ok() {
 fn resolve_module_item_err1() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.foo;
 import a.b.foo2;",
@@ -127,7 +127,7 @@ import a.b.foo2;",
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
@@ -139,18 +139,18 @@ import a.b.foo2;",
 fn resolve_module_item_err2() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.c.foo;",
     );
@@ -159,7 +159,7 @@ fn resolve_module_item_err2() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
@@ -171,18 +171,18 @@ fn resolve_module_item_err2() {
 fn resolve_enum_item_ok() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "enum Result[T, E] { Ok(T), Err(E) }",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.Result;
 import a.b.Result.Ok;
@@ -193,7 +193,7 @@ import a.b.Result.Err;",
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
@@ -205,18 +205,18 @@ import a.b.Result.Err;",
 fn resolve_enum_item_err1() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "enum Result[T, E] { Ok(T), Err(E) }",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.Result.Foo;",
     );
@@ -225,7 +225,7 @@ fn resolve_enum_item_err1() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
@@ -237,18 +237,18 @@ fn resolve_enum_item_err1() {
 fn resolve_enum_item_err2() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "enum Result[T, E] { Ok(T), Err(E) }",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.Result.Ok.Foo;",
     );
@@ -257,7 +257,7 @@ fn resolve_enum_item_err2() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
@@ -269,18 +269,18 @@ fn resolve_enum_item_err2() {
 fn resolve_name_in_module_items_except_enums() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "fun foo() {}",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "import a.b.foo.foo;",
     );
@@ -289,7 +289,7 @@ fn resolve_name_in_module_items_except_enums() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
@@ -301,18 +301,18 @@ fn resolve_name_in_module_items_except_enums() {
 fn importing_package() {
     let mut state = State::new();
 
-    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), IdentifierId::from("a"), PathId::from("a"));
     let submodule = parse_module(
         &mut state,
         package,
-        IdentifierId::from("b"),
+        IdentifierId::from("b").into(),
         PathId::from("a/b.sr"),
         "import a;",
     );
     let root = parse_module(
         &mut state,
         package,
-        IdentifierId::from("a"),
+        IdentifierId::from("a").into(),
         PathId::from("a/package.sr"),
         "",
     );
@@ -321,7 +321,7 @@ fn importing_package() {
     root.module()
         .add_submodule(state.db_mut(), submodule.module());
 
-    let hir = LowerToHir::run_all(&mut state, vec![root, submodule]);
+    let hir = LowerToHir::run_all(vec![root, submodule]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     ResolveImports::run_all(&mut state, &hir);
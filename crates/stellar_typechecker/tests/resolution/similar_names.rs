@@ -0,0 +1,72 @@
+use super::resolve;
+
+/// Resolves the imports of the root module `a`, that has a single submodule `b`,
+/// and returns the notes of all reported diagnostics.
+fn resolve_and_collect_notes(submodule_source: &str, root_source: &str) -> Vec<String> {
+    let (state, _) = resolve(root_source, &[("b", submodule_source)]);
+
+    assert!(state.diagnostics().is_fatal());
+
+    state
+        .diagnostics()
+        .diagnostics
+        .iter()
+        .flat_map(|diagnostic| diagnostic.notes.clone())
+        .collect()
+}
+
+#[test]
+fn similar_module_item() {
+    assert_eq!(
+        resolve_and_collect_notes("pub fun foo() {} pub fun bar() {}", "import a.b.fooo;"),
+        vec!["help: did you mean `foo`?"]
+    );
+}
+
+#[test]
+fn similar_submodule() {
+    assert_eq!(
+        resolve_and_collect_notes("", "import a.c;"),
+        vec!["help: did you mean `b`?"]
+    );
+}
+
+#[test]
+fn public_items_are_preferred() {
+    assert_eq!(
+        resolve_and_collect_notes("fun fooa() {} pub fun foob() {}", "import a.b.foo;"),
+        vec!["help: did you mean `foob`?"]
+    );
+}
+
+#[test]
+fn private_item_is_suggested_if_it_is_the_only_match() {
+    assert_eq!(
+        resolve_and_collect_notes("fun foo() {}", "import a.b.fooo;"),
+        vec!["help: did you mean `foo`?"]
+    );
+}
+
+#[test]
+fn similar_enum_item() {
+    assert_eq!(
+        resolve_and_collect_notes("pub enum Color { Red, Green }", "import a.b.Color.Gren;"),
+        vec!["help: did you mean `Green`?"]
+    );
+}
+
+#[test]
+fn similar_package() {
+    assert_eq!(
+        resolve_and_collect_notes("", "import aa.b;"),
+        vec![
+            "help: add `aa` into the manifest file's [dependencies] section",
+            "help: did you mean `a`?"
+        ]
+    );
+}
+
+#[test]
+fn no_similar_names() {
+    assert!(resolve_and_collect_notes("pub fun foo() {}", "import a.b.something;").is_empty());
+}
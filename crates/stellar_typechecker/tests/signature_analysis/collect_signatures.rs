@@ -1,6 +1,6 @@
 use stellar_ast_lowering::LowerToHir;
 use stellar_database::{PackageData, State};
-use stellar_interner::{IdentifierId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
+use stellar_interner::{IdentifierId, PathId, DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID};
 use stellar_parser::parse_module;
 use stellar_typechecker::{
     resolution::collect_definitions::CollectDefinitions,
@@ -8,20 +8,21 @@ use stellar_typechecker::{
 };
 
 #[test]
+#[ignore = "signature analysis in `CollectSignatures::run_all` is commented out"]
 fn simple_generic_parameter() {
     let mut state = State::new();
     let source_code = "struct Box[T](T);";
 
-    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, DUMMY_PATH_ID);
+    let package = PackageData::alloc(state.db_mut(), DUMMY_IDENTIFIER_ID, PathId::from("a"));
     let parse_result = parse_module(
         &mut state,
         package,
-        DUMMY_IDENTIFIER_ID,
+        DUMMY_IDENTIFIER_ID.into(),
         DUMMY_PATH_ID,
         source_code,
     );
     let module = parse_result.module();
-    let hir = LowerToHir::run_all(&mut state, vec![parse_result]);
+    let hir = LowerToHir::run_all(vec![parse_result]);
 
     CollectDefinitions::run_all(&mut state, &hir);
     CollectSignatures::run_all(&mut state, &hir);